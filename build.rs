@@ -2,4 +2,8 @@ fn main() {
     if cfg!(all(not(target_os = "windows"), not(feature = "no-libffi-linking"))) {
         println!("cargo:rustc-link-lib=dylib=ffi");
     }
+
+    if cfg!(feature = "thinlto") {
+        println!("cargo:rustc-link-lib=dylib=LTO");
+    }
 }
@@ -0,0 +1,825 @@
+//! Lowering of C function signatures to a platform's calling convention.
+//!
+//! LLVM only knows about the types of a `FunctionType`, not how a C compiler for the target
+//! would pass them. Calling a C function that takes or returns a struct by value therefore
+//! requires rewriting the signature the same way the platform's C compiler would: small
+//! aggregates are coerced into register sized types, large ones are passed through memory
+//! with `byval` or returned through an `sret` pointer. A `FnAbi` performs that rewriting for
+//! a signature and marshals values across it at call sites and in function prologues.
+//!
+//! The signedness of small integers isn't known from their LLVM types, so no `zeroext` or
+//! `signext` attributes are added for them. Aggregates containing `x86_fp80` are always
+//! passed and returned in memory on x86-64.
+
+use llvm_sys::core::LLVMGetTypeKind;
+use llvm_sys::LLVMTypeKind;
+
+use either::Either;
+
+use crate::AddressSpace;
+use crate::attributes::{Attribute, AttributeLoc};
+use crate::builder::Builder;
+use crate::context::Context;
+use crate::module::{Linkage, Module};
+use crate::targets::{Arch, ByteOrdering, OS, TargetData, Triple};
+use crate::types::{AsTypeRef, BasicType, BasicTypeEnum, FloatType, FunctionType};
+use crate::values::{BasicValueEnum, CallSiteValue, FunctionValue, InstructionValue, PointerValue};
+
+use std::cmp::{max, min};
+use std::fmt;
+
+/// The calling conventions a `FnAbi` can lower signatures for.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Abi {
+    /// The System V AMD64 ABI, used by x86-64 Linux, the BSDs and macOS.
+    X86_64SysV,
+    /// The procedure call standard for the 64-bit ARM architecture (AAPCS64).
+    AArch64AAPCS,
+}
+
+impl Abi {
+    /// Gets the C calling convention of a `Triple`, if it is one `FnAbi` supports.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::abi::Abi;
+    /// use inkwell::targets::TargetTriple;
+    ///
+    /// let triple = TargetTriple::create("x86_64-unknown-linux-gnu").parse().unwrap();
+    ///
+    /// assert_eq!(Abi::from_triple(&triple), Some(Abi::X86_64SysV));
+    /// ```
+    pub fn from_triple(triple: &Triple) -> Option<Abi> {
+        match (triple.get_arch(), triple.get_os()) {
+            (_, OS::Windows) => None,
+            (Arch::X86_64, _) => Some(Abi::X86_64SysV),
+            (Arch::AArch64, _) | (Arch::AArch64BE, _) => Some(Abi::AArch64AAPCS),
+            _ => None,
+        }
+    }
+}
+
+/// How a value is passed across a lowered signature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PassMode<'ctx> {
+    /// The value is passed unchanged.
+    Direct,
+    /// The bytes of the value are reinterpreted as, and passed as, the given type.
+    Coerce(BasicTypeEnum<'ctx>),
+    /// A pointer to a copy of the value is passed instead. If `byval` is set the pointer
+    /// carries the `byval` attribute and the copy lives in the callee's argument area,
+    /// otherwise the caller owns the copy. A return value passed this way is written through
+    /// an `sret` pointer given as the first parameter.
+    Indirect {
+        /// Whether the pointer is marked `byval`.
+        byval: bool,
+        /// The alignment of the copy.
+        align: u32,
+    },
+    /// The value takes up no space and isn't passed at all.
+    Ignore,
+}
+
+/// A parameter or return value of a `FnAbi`, with how it is passed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ArgAbi<'ctx> {
+    ty: BasicTypeEnum<'ctx>,
+    mode: PassMode<'ctx>,
+    index: Option<u32>,
+}
+
+impl<'ctx> ArgAbi<'ctx> {
+    /// Gets the type this value has in the high-level signature.
+    pub fn get_type(&self) -> BasicTypeEnum<'ctx> {
+        self.ty
+    }
+
+    /// Gets how this value is passed.
+    pub fn get_pass_mode(&self) -> PassMode<'ctx> {
+        self.mode
+    }
+
+    /// Gets the index of the parameter of the lowered `FunctionType` holding this value, if
+    /// any. This is only ever `Some` for a return value when it is returned through `sret`.
+    pub fn get_lowered_index(&self) -> Option<u32> {
+        self.index
+    }
+}
+
+/// A high-level function signature lowered to a calling convention.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::abi::{Abi, FnAbi, PassMode};
+/// use inkwell::context::Context;
+/// use inkwell::targets::TargetData;
+///
+/// let context = Context::create();
+/// let target_data = TargetData::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
+/// let i64_type = context.i64_type();
+/// let big = context.struct_type(&[i64_type.into(), i64_type.into(), i64_type.into()], false);
+///
+/// // struct big make_big(long);
+/// let fn_abi = FnAbi::new(Abi::X86_64SysV, &context, &target_data, Some(big.into()), &[i64_type.into()], false);
+///
+/// assert_eq!(fn_abi.get_return().unwrap().get_pass_mode(), PassMode::Indirect { byval: false, align: 8 });
+/// assert_eq!(fn_abi.get_fn_type().get_return_type(), None);
+/// assert_eq!(fn_abi.get_fn_type().count_param_types(), 2);
+/// ```
+pub struct FnAbi<'ctx> {
+    abi: Abi,
+    context: &'ctx Context,
+    target_data: TargetData,
+    ret: Option<ArgAbi<'ctx>>,
+    params: Vec<ArgAbi<'ctx>>,
+    fn_type: FunctionType<'ctx>,
+    registers: Registers,
+}
+
+impl<'ctx> FnAbi<'ctx> {
+    /// Lowers the signature made of `return_type` and `param_types` according to `abi`, using
+    /// `target_data` for the sizes and alignments of the types.
+    ///
+    /// # Panics
+    ///
+    /// Panics if one of the types is not sized.
+    pub fn new(
+        abi: Abi,
+        context: &'ctx Context,
+        target_data: &TargetData,
+        return_type: Option<BasicTypeEnum<'ctx>>,
+        param_types: &[BasicTypeEnum<'ctx>],
+        is_var_args: bool,
+    ) -> Self {
+        let target_data = TargetData::create(&target_data.get_data_layout().as_str().to_string_lossy());
+        let lowering = Lowering { abi, context, target_data: &target_data };
+        let mut registers = Registers::new(abi);
+        let mut lowered_params = Vec::with_capacity(param_types.len() + 1);
+
+        let ret = return_type.map(|ty| {
+            assert!(ty.is_sized(), "Return type {:?} is not sized", ty);
+
+            let mode = lowering.classify_return(ty, &mut registers);
+            let index = match mode {
+                PassMode::Indirect { .. } => {
+                    lowered_params.push(ty.ptr_type(AddressSpace::Generic).into());
+
+                    Some(0)
+                },
+                _ => None,
+            };
+
+            ArgAbi { ty, mode, index }
+        });
+
+        let params: Vec<_> = param_types.iter().map(|&ty| {
+            assert!(ty.is_sized(), "Parameter type {:?} is not sized", ty);
+
+            let mode = lowering.classify_arg(ty, &mut registers);
+            let index = lowered_type(ty, mode).map(|lowered| {
+                lowered_params.push(lowered);
+
+                lowered_params.len() as u32 - 1
+            });
+
+            ArgAbi { ty, mode, index }
+        }).collect();
+
+        let fn_type = match ret {
+            Some(ArgAbi { ty, mode: PassMode::Direct, .. }) => ty.fn_type(&lowered_params, is_var_args),
+            Some(ArgAbi { mode: PassMode::Coerce(ty), .. }) => ty.fn_type(&lowered_params, is_var_args),
+            _ => context.void_type().fn_type(&lowered_params, is_var_args),
+        };
+
+        FnAbi {
+            abi,
+            context,
+            target_data,
+            ret,
+            params,
+            fn_type,
+            registers,
+        }
+    }
+
+    /// Gets the calling convention this signature was lowered for.
+    pub fn get_abi(&self) -> Abi {
+        self.abi
+    }
+
+    /// Gets the lowered `FunctionType`, which is what functions following this signature
+    /// should be declared and called with.
+    pub fn get_fn_type(&self) -> FunctionType<'ctx> {
+        self.fn_type
+    }
+
+    /// Gets how the return value is passed, or `None` if the function returns nothing.
+    pub fn get_return(&self) -> Option<&ArgAbi<'ctx>> {
+        self.ret.as_ref()
+    }
+
+    /// Gets how each parameter of the high-level signature is passed.
+    pub fn get_params(&self) -> &[ArgAbi<'ctx>] {
+        &self.params
+    }
+
+    /// Gets the `Attribute`s the lowered signature requires, which must be present both on
+    /// the function and at every call site.
+    pub fn get_attributes(&self) -> Vec<(AttributeLoc, Attribute)> {
+        let mut attributes = Vec::new();
+
+        if let Some(ArgAbi { mode: PassMode::Indirect { .. }, index: Some(index), .. }) = self.ret {
+            attributes.push((AttributeLoc::Param(index), self.enum_attribute("sret", 0)));
+            attributes.push((AttributeLoc::Param(index), self.enum_attribute("noalias", 0)));
+        }
+
+        for param in &self.params {
+            self.push_param_attributes(param, &mut attributes);
+        }
+
+        attributes
+    }
+
+    /// Adds the `Attribute`s this signature requires to a function.
+    pub fn apply_attributes(&self, function: FunctionValue<'ctx>) {
+        for (loc, attribute) in self.get_attributes() {
+            function.add_attribute(loc, attribute);
+        }
+    }
+
+    /// Adds the `Attribute`s this signature requires to a call site.
+    pub fn apply_call_attributes(&self, call_site_value: CallSiteValue<'ctx>) {
+        for (loc, attribute) in self.get_attributes() {
+            call_site_value.add_attribute(loc, attribute);
+        }
+    }
+
+    /// Adds a function with the lowered signature and its `Attribute`s to a `Module`.
+    pub fn add_function(&self, module: &Module<'ctx>, name: &str, linkage: Option<Linkage>) -> FunctionValue<'ctx> {
+        let function = module.add_function(name, self.fn_type, linkage);
+
+        self.apply_attributes(function);
+
+        function
+    }
+
+    /// Builds a call to a function with the lowered signature, taking the arguments and
+    /// producing the return value as they appear in the high-level signature. Arguments past
+    /// the declared parameters of a variadic signature are lowered as they are encountered.
+    ///
+    /// Temporaries are allocated at the start of the entry block of the function the
+    /// `builder` is positioned in.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of arguments doesn't match the signature, or if the `builder`
+    /// isn't positioned inside a function.
+    pub fn build_call<F>(&self, builder: &Builder<'ctx>, function: F, args: &[BasicValueEnum<'ctx>], name: &str) -> Option<BasicValueEnum<'ctx>>
+    where
+        F: Into<Either<FunctionValue<'ctx>, PointerValue<'ctx>>>,
+    {
+        assert!(
+            args.len() == self.params.len() || (self.fn_type.is_var_arg() && args.len() > self.params.len()),
+            "Expected {} arguments but got {}", self.params.len(), args.len()
+        );
+
+        let lowering = Lowering { abi: self.abi, context: self.context, target_data: &self.target_data };
+        let mut registers = self.registers;
+        let mut attributes = self.get_attributes();
+        let mut lowered_args = Vec::with_capacity(args.len() + 1);
+
+        let sret = match self.ret {
+            Some(ArgAbi { ty, mode: PassMode::Indirect { align, .. }, .. }) => {
+                let slot = self.build_entry_alloca(builder, ty, align, "abi.sret");
+
+                lowered_args.push(slot.into());
+
+                Some(slot)
+            },
+            _ => None,
+        };
+
+        for (i, &arg) in args.iter().enumerate() {
+            let param = match self.params.get(i) {
+                Some(param) => *param,
+                None => {
+                    let ty = arg.get_type();
+                    let mode = lowering.classify_arg(ty, &mut registers);
+                    let index = lowered_type(ty, mode).map(|_| lowered_args.len() as u32);
+                    let param = ArgAbi { ty, mode, index };
+
+                    self.push_param_attributes(&param, &mut attributes);
+
+                    param
+                },
+            };
+
+            match param.mode {
+                PassMode::Direct => lowered_args.push(arg),
+                PassMode::Coerce(ty) => lowered_args.push(self.build_coercion(builder, arg, ty)),
+                PassMode::Indirect { align, .. } => {
+                    let slot = self.build_entry_alloca(builder, param.ty, align, "abi.indirect");
+
+                    builder.build_store(slot, arg);
+                    lowered_args.push(slot.into());
+                },
+                PassMode::Ignore => {},
+            }
+        }
+
+        let call_site_value = builder.build_call(function, &lowered_args, name);
+
+        for (loc, attribute) in attributes {
+            call_site_value.add_attribute(loc, attribute);
+        }
+
+        let ret = self.ret?;
+
+        match ret.mode {
+            PassMode::Direct => call_site_value.try_as_basic_value().left(),
+            PassMode::Coerce(_) => {
+                let value = call_site_value.try_as_basic_value().left()?;
+
+                Some(self.build_coercion(builder, value, ret.ty))
+            },
+            PassMode::Indirect { .. } => sret.map(|slot| builder.build_load(slot, name)),
+            PassMode::Ignore => Some(ret.ty.const_zero()),
+        }
+    }
+
+    /// Builds the code turning the parameters of `function`, which must have the lowered
+    /// signature, back into the values of the high-level signature. The `builder` should be
+    /// positioned in the entry block of `function`.
+    pub fn build_prologue(&self, builder: &Builder<'ctx>, function: FunctionValue<'ctx>) -> Vec<BasicValueEnum<'ctx>> {
+        self.params.iter().map(|param| {
+            let lowered = param.index.and_then(|index| function.get_nth_param(index));
+
+            match (param.mode, lowered) {
+                (PassMode::Direct, Some(value)) => value,
+                (PassMode::Coerce(_), Some(value)) => self.build_coercion(builder, value, param.ty),
+                (PassMode::Indirect { .. }, Some(value)) => builder.build_load(value.into_pointer_value(), ""),
+                (PassMode::Ignore, _) => param.ty.const_zero(),
+                (_, None) => panic!("Function does not have the lowered signature"),
+            }
+        }).collect()
+    }
+
+    /// Builds a return of `value`, which must have the high-level return type, from
+    /// `function`, which must have the lowered signature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is `None` but the signature returns a value, or the other way around.
+    pub fn build_return(&self, builder: &Builder<'ctx>, function: FunctionValue<'ctx>, value: Option<BasicValueEnum<'ctx>>) -> InstructionValue<'ctx> {
+        let (ret, value) = match (self.ret, value) {
+            (None, None) => return builder.build_return(None),
+            (Some(ret), Some(value)) => (ret, value),
+            (ret, _) => panic!("Expected a return value of type {:?}", ret.map(|ret| ret.ty)),
+        };
+
+        match ret.mode {
+            PassMode::Direct => builder.build_return(Some(&value)),
+            PassMode::Coerce(ty) => {
+                let coerced = self.build_coercion(builder, value, ty);
+
+                builder.build_return(Some(&coerced))
+            },
+            PassMode::Indirect { .. } => {
+                let sret = ret.index
+                    .and_then(|index| function.get_nth_param(index))
+                    .expect("Function does not have the lowered signature");
+
+                builder.build_store(sret.into_pointer_value(), value);
+                builder.build_return(None)
+            },
+            PassMode::Ignore => builder.build_return(None),
+        }
+    }
+
+    fn enum_attribute(&self, name: &str, value: u64) -> Attribute {
+        self.context.create_enum_attribute(Attribute::get_named_enum_kind_id(name), value)
+    }
+
+    fn push_param_attributes(&self, param: &ArgAbi<'ctx>, attributes: &mut Vec<(AttributeLoc, Attribute)>) {
+        if let ArgAbi { mode: PassMode::Indirect { byval: true, align }, index: Some(index), .. } = *param {
+            attributes.push((AttributeLoc::Param(index), self.enum_attribute("byval", 0)));
+            attributes.push((AttributeLoc::Param(index), self.enum_attribute("align", align as u64)));
+        }
+    }
+
+    fn build_entry_alloca(&self, builder: &Builder<'ctx>, ty: BasicTypeEnum<'ctx>, align: u32, name: &str) -> PointerValue<'ctx> {
+        let entry = builder.get_insert_block()
+            .and_then(|block| block.get_parent())
+            .and_then(|function| function.get_first_basic_block())
+            .expect("Builder must be positioned inside a function");
+        let alloca_builder = self.context.create_builder();
+
+        match entry.get_first_instruction() {
+            Some(instruction) => alloca_builder.position_before(&instruction),
+            None => alloca_builder.position_at_end(entry),
+        }
+
+        let slot = alloca_builder.build_alloca(ty, name);
+
+        if let Some(instruction) = slot.as_instruction() {
+            instruction.set_alignment(align).expect("Alignment must be a power of two");
+        }
+
+        slot
+    }
+
+    // Reinterprets the bytes of a value as another type by going through a stack slot large
+    // enough for either type, the same way C compilers do.
+    fn build_coercion(&self, builder: &Builder<'ctx>, value: BasicValueEnum<'ctx>, ty: BasicTypeEnum<'ctx>) -> BasicValueEnum<'ctx> {
+        let value_type = value.get_type();
+
+        if value_type == ty {
+            return value;
+        }
+
+        let align = max(self.target_data.get_abi_alignment(&value_type), self.target_data.get_abi_alignment(&ty));
+        let slot_type = if self.target_data.get_abi_size(&ty) > self.target_data.get_abi_size(&value_type) {
+            ty
+        } else {
+            value_type
+        };
+        let slot = self.build_entry_alloca(builder, slot_type, align, "abi.coerce");
+        let store_ptr = builder.build_pointer_cast(slot, value_type.ptr_type(AddressSpace::Generic), "");
+
+        builder.build_store(store_ptr, value);
+
+        let load_ptr = builder.build_pointer_cast(slot, ty.ptr_type(AddressSpace::Generic), "");
+
+        builder.build_load(load_ptr, "")
+    }
+}
+
+impl fmt::Debug for FnAbi<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FnAbi")
+            .field("abi", &self.abi)
+            .field("ret", &self.ret)
+            .field("params", &self.params)
+            .field("fn_type", &self.fn_type)
+            .finish()
+    }
+}
+
+fn lowered_type<'ctx>(ty: BasicTypeEnum<'ctx>, mode: PassMode<'ctx>) -> Option<BasicTypeEnum<'ctx>> {
+    match mode {
+        PassMode::Direct => Some(ty),
+        PassMode::Coerce(coerced) => Some(coerced),
+        PassMode::Indirect { .. } => Some(ty.ptr_type(AddressSpace::Generic).into()),
+        PassMode::Ignore => None,
+    }
+}
+
+fn is_aggregate(ty: BasicTypeEnum) -> bool {
+    ty.is_struct_type() || ty.is_array_type()
+}
+
+fn float_kind(float_type: FloatType) -> LLVMTypeKind {
+    unsafe {
+        LLVMGetTypeKind(float_type.as_type_ref())
+    }
+}
+
+// The argument registers left, which decides whether an x86-64 aggregate still fits in
+// registers. AAPCS64 aggregates that don't fit are put on the stack by the backend itself.
+#[derive(Clone, Copy, Debug)]
+struct Registers {
+    int: u32,
+    sse: u32,
+}
+
+impl Registers {
+    fn new(abi: Abi) -> Self {
+        match abi {
+            Abi::X86_64SysV => Registers { int: 6, sse: 8 },
+            Abi::AArch64AAPCS => Registers { int: 8, sse: 8 },
+        }
+    }
+}
+
+// The x86-64 classes of an eightbyte, minus the X87 ones which force memory here. Empty is
+// the psABI's NO_CLASS, for eightbytes holding only padding.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Class {
+    Empty,
+    Integer,
+    Sse,
+    Memory,
+}
+
+impl Class {
+    fn merge(self, other: Class) -> Class {
+        match (self, other) {
+            (a, b) if a == b => a,
+            (Class::Empty, other) | (other, Class::Empty) => other,
+            (Class::Memory, _) | (_, Class::Memory) => Class::Memory,
+            (Class::Integer, _) | (_, Class::Integer) => Class::Integer,
+            _ => Class::Sse,
+        }
+    }
+}
+
+struct Lowering<'a, 'ctx> {
+    abi: Abi,
+    context: &'ctx Context,
+    target_data: &'a TargetData,
+}
+
+impl<'a, 'ctx> Lowering<'a, 'ctx> {
+    fn classify_return(&self, ty: BasicTypeEnum<'ctx>, registers: &mut Registers) -> PassMode<'ctx> {
+        if !is_aggregate(ty) {
+            return PassMode::Direct;
+        }
+
+        let size = self.target_data.get_abi_size(&ty);
+
+        if size == 0 {
+            return PassMode::Ignore;
+        }
+
+        let indirect = PassMode::Indirect { byval: false, align: self.target_data.get_abi_alignment(&ty) };
+
+        match self.abi {
+            Abi::X86_64SysV => match self.x86_64_aggregate(ty) {
+                Some((coerced, _, _)) => PassMode::Coerce(coerced),
+                None => {
+                    registers.int -= 1;
+
+                    indirect
+                },
+            },
+            Abi::AArch64AAPCS => {
+                if let Some((base, count)) = self.aarch64_homogeneous_aggregate(ty) {
+                    return PassMode::Coerce(base.array_type(count).into());
+                }
+
+                if size > 16 {
+                    return indirect;
+                }
+
+                if size <= 8 && self.target_data.get_byte_ordering() == ByteOrdering::LittleEndian {
+                    return PassMode::Coerce(self.context.custom_width_int_type(size as u32 * 8).into());
+                }
+
+                self.aarch64_small_aggregate(ty, size)
+            },
+        }
+    }
+
+    fn classify_arg(&self, ty: BasicTypeEnum<'ctx>, registers: &mut Registers) -> PassMode<'ctx> {
+        let size = self.target_data.get_abi_size(&ty);
+        let align = self.target_data.get_abi_alignment(&ty);
+
+        if !is_aggregate(ty) {
+            // Scalars and vectors are passed as they are, the backend picks their registers
+            // or stack slots. On x86-64 they still use up registers aggregates could have had.
+            if self.abi == Abi::X86_64SysV {
+                match self.x86_64_class(ty) {
+                    Class::Integer => registers.int = registers.int.saturating_sub(if size > 8 { 2 } else { 1 }),
+                    Class::Sse => registers.sse = registers.sse.saturating_sub(1),
+                    _ => {},
+                }
+            }
+
+            return PassMode::Direct;
+        }
+
+        if size == 0 {
+            return PassMode::Ignore;
+        }
+
+        match self.abi {
+            Abi::X86_64SysV => match self.x86_64_aggregate(ty) {
+                Some((coerced, ints, sses)) if ints <= registers.int && sses <= registers.sse => {
+                    registers.int -= ints;
+                    registers.sse -= sses;
+
+                    PassMode::Coerce(coerced)
+                },
+                _ => PassMode::Indirect { byval: true, align: max(8, align) },
+            },
+            Abi::AArch64AAPCS => {
+                if let Some((base, count)) = self.aarch64_homogeneous_aggregate(ty) {
+                    return PassMode::Coerce(base.array_type(count).into());
+                }
+
+                if size > 16 {
+                    return PassMode::Indirect { byval: false, align };
+                }
+
+                self.aarch64_small_aggregate(ty, size)
+            },
+        }
+    }
+
+    // Collects the scalar and vector fields of a type along with their byte offsets.
+    fn leaves(&self, ty: BasicTypeEnum<'ctx>, offset: u64, leaves: &mut Vec<(u64, BasicTypeEnum<'ctx>)>) {
+        match ty {
+            BasicTypeEnum::StructType(struct_type) => {
+                for (i, field) in struct_type.get_field_types().into_iter().enumerate() {
+                    let field_offset = self.target_data.offset_of_element(&struct_type, i as u32).unwrap_or(0);
+
+                    self.leaves(field, offset + field_offset, leaves);
+                }
+            },
+            BasicTypeEnum::ArrayType(array_type) => {
+                let element = array_type.get_element_type();
+                let element_size = self.target_data.get_abi_size(&element);
+
+                for i in 0..array_type.len() as u64 {
+                    self.leaves(element, offset + i * element_size, leaves);
+                }
+            },
+            _ => leaves.push((offset, ty)),
+        }
+    }
+
+    fn x86_64_class(&self, ty: BasicTypeEnum<'ctx>) -> Class {
+        match ty {
+            BasicTypeEnum::IntType(_) | BasicTypeEnum::PointerType(_) => Class::Integer,
+            BasicTypeEnum::FloatType(float_type) => match float_kind(float_type) {
+                LLVMTypeKind::LLVMHalfTypeKind |
+                LLVMTypeKind::LLVMFloatTypeKind |
+                LLVMTypeKind::LLVMDoubleTypeKind |
+                LLVMTypeKind::LLVMFP128TypeKind => Class::Sse,
+                _ => Class::Memory,
+            },
+            // Like GCC, vectors of up to 4 bytes are passed as integers.
+            BasicTypeEnum::VectorType(_) => match self.target_data.get_abi_size(&ty) {
+                0..=4 => Class::Integer,
+                8 | 16 => Class::Sse,
+                _ => Class::Memory,
+            },
+            BasicTypeEnum::ArrayType(_) | BasicTypeEnum::StructType(_) => Class::Memory,
+        }
+    }
+
+    // Classifies an aggregate into eightbytes and builds the type it is coerced to, along
+    // with the number of integer and SSE registers it needs. Returns `None` for MEMORY.
+    fn x86_64_aggregate(&self, ty: BasicTypeEnum<'ctx>) -> Option<(BasicTypeEnum<'ctx>, u32, u32)> {
+        let size = self.target_data.get_abi_size(&ty);
+
+        if size > 16 {
+            return None;
+        }
+
+        let mut leaves = Vec::new();
+
+        self.leaves(ty, 0, &mut leaves);
+
+        // A lone 16 byte vector or fp128 is SSE + SSEUP, so it is passed in a single register.
+        if let [(0, leaf)] = leaves[..] {
+            if size == 16 && self.target_data.get_abi_size(&leaf) == 16 && self.x86_64_class(leaf) == Class::Sse {
+                return Some((leaf, 0, 1));
+            }
+        }
+
+        let mut classes = [Class::Empty; 2];
+
+        for &(offset, leaf) in &leaves {
+            let leaf_size = self.target_data.get_store_size(&leaf);
+            let class = self.x86_64_class(leaf);
+
+            if leaf_size == 0 {
+                continue;
+            }
+
+            // Unaligned fields, as found in packed structs, and anything that doesn't fit in
+            // a general purpose register pair put the whole aggregate in memory.
+            if class == Class::Memory || offset % self.target_data.get_abi_alignment(&leaf) as u64 != 0 ||
+                (class == Class::Sse && leaf_size > 8) {
+                return None;
+            }
+
+            for eightbyte in &mut classes[offset as usize / 8..=(offset + leaf_size - 1) as usize / 8] {
+                *eightbyte = eightbyte.merge(class);
+            }
+        }
+
+        let mut parts = Vec::with_capacity(2);
+        let mut ints = 0;
+        let mut sses = 0;
+
+        for (i, &class) in classes.iter().enumerate() {
+            let start = i as u64 * 8;
+
+            if start >= size {
+                break;
+            }
+
+            let part_size = min(8, size - start);
+
+            if class == Class::Sse {
+                parts.push(self.x86_64_sse_part(&leaves, start, part_size));
+                sses += 1;
+            } else {
+                parts.push(self.context.custom_width_int_type(part_size as u32 * 8).into());
+                ints += 1;
+            }
+        }
+
+        let coerced = match parts[..] {
+            [part] => part,
+            _ => self.context.struct_type(&parts, false).into(),
+        };
+
+        Some((coerced, ints, sses))
+    }
+
+    fn x86_64_sse_part(&self, leaves: &[(u64, BasicTypeEnum<'ctx>)], start: u64, part_size: u64) -> BasicTypeEnum<'ctx> {
+        let part_leaves: Vec<_> = leaves.iter()
+            .filter(|&&(offset, _)| offset >= start && offset < start + 8)
+            .collect();
+
+        if let [&(offset, leaf)] = part_leaves[..] {
+            if offset == start && self.target_data.get_store_size(&leaf) == 8 {
+                return leaf;
+            }
+        }
+
+        let f32_type = self.context.f32_type();
+        let f16_type = self.context.f16_type();
+
+        if part_leaves.iter().all(|&&(_, leaf)| leaf == f32_type.as_basic_type_enum()) {
+            return match part_size {
+                0..=4 => f32_type.into(),
+                _ => f32_type.vec_type(2).into(),
+            };
+        }
+
+        if part_leaves.iter().all(|&&(_, leaf)| leaf == f16_type.as_basic_type_enum()) {
+            return match part_size {
+                0..=2 => f16_type.into(),
+                _ => f16_type.vec_type(part_size as u32 / 2).into(),
+            };
+        }
+
+        match part_size {
+            0..=4 => f32_type.into(),
+            _ => self.context.f64_type().into(),
+        }
+    }
+
+    // A homogeneous floating-point or short vector aggregate has one to four members of the
+    // same type and is passed in consecutive SIMD registers.
+    fn aarch64_homogeneous_aggregate(&self, ty: BasicTypeEnum<'ctx>) -> Option<(BasicTypeEnum<'ctx>, u32)> {
+        let size = self.target_data.get_abi_size(&ty);
+
+        if size > 64 {
+            return None;
+        }
+
+        let mut leaves = Vec::new();
+
+        self.leaves(ty, 0, &mut leaves);
+
+        let &(_, base) = leaves.first()?;
+        let base_size = self.target_data.get_abi_size(&base);
+        let is_base = match base {
+            BasicTypeEnum::FloatType(float_type) => match float_kind(float_type) {
+                LLVMTypeKind::LLVMHalfTypeKind |
+                LLVMTypeKind::LLVMFloatTypeKind |
+                LLVMTypeKind::LLVMDoubleTypeKind |
+                LLVMTypeKind::LLVMFP128TypeKind => true,
+                _ => false,
+            },
+            BasicTypeEnum::VectorType(_) => base_size == 8 || base_size == 16,
+            _ => false,
+        };
+
+        if !is_base || leaves.len() > 4 || base_size * leaves.len() as u64 != size {
+            return None;
+        }
+
+        let is_homogeneous = leaves.iter()
+            .enumerate()
+            .all(|(i, &(offset, leaf))| leaf == base && offset == i as u64 * base_size);
+
+        if is_homogeneous {
+            Some((base, leaves.len() as u32))
+        } else {
+            None
+        }
+    }
+
+    // Aggregates of up to 16 bytes are passed in one or two general purpose registers, the
+    // pair being represented as `[2 x i64]` unless the aggregate is 16 byte aligned.
+    fn aarch64_small_aggregate(&self, ty: BasicTypeEnum<'ctx>, size: u64) -> PassMode<'ctx> {
+        // u64::div_ceil is newer than our MSRV
+        #[allow(clippy::manual_div_ceil)]
+        let rounded_size = (size + 7) / 8 * 8;
+        let i64_type = self.context.i64_type();
+
+        if rounded_size == 16 && self.target_data.get_abi_alignment(&ty) < 16 {
+            PassMode::Coerce(i64_type.array_type(2).into())
+        } else {
+            PassMode::Coerce(self.context.custom_width_int_type(rounded_size as u32 * 8).into())
+        }
+    }
+}
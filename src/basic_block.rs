@@ -6,7 +6,7 @@ use llvm_sys::core::LLVMGetBasicBlockName;
 use llvm_sys::prelude::{LLVMValueRef, LLVMBasicBlockRef};
 
 use crate::context::ContextRef;
-use crate::values::{BasicValueUse, FunctionValue, InstructionValue};
+use crate::values::{BasicValueUse, BasicValueUseIter, FunctionValue, InstructionValue};
 
 use std::fmt;
 use std::ffi::CStr;
@@ -516,6 +516,134 @@ impl<'ctx> BasicBlock<'ctx> {
 
         Some(BasicValueUse::new(use_))
     }
+
+    /// Iterates over the uses of this `BasicBlock`, such as the branches which target it.
+    pub fn get_uses(self) -> BasicValueUseIter<'ctx> {
+        BasicValueUseIter::new(self.get_first_use())
+    }
+
+    /// Iterates over the `InstructionValue`s in this `BasicBlock`, from either end. The next
+    /// instruction is looked up before the current one is yielded, so the current instruction
+    /// may be removed or erased while iterating.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::values::InstructionOpcode;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("my_module");
+    /// let void_type = context.void_type();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    /// let function = module.add_function("do_nothing", fn_type, None);
+    /// let basic_block = context.append_basic_block(function, "entry");
+    ///
+    /// builder.position_at_end(basic_block);
+    /// builder.build_alloca(i32_type, "ptr");
+    /// builder.build_return(None);
+    ///
+    /// let opcodes: Vec<_> = basic_block.get_instructions().map(|instruction| instruction.get_opcode()).collect();
+    ///
+    /// assert_eq!(opcodes, [InstructionOpcode::Alloca, InstructionOpcode::Return]);
+    /// assert_eq!(basic_block.get_instructions().next_back(), basic_block.get_last_instruction());
+    /// ```
+    pub fn get_instructions(self) -> InstructionIter<'ctx> {
+        InstructionIter {
+            front: self.get_first_instruction(),
+            back: self.get_last_instruction(),
+        }
+    }
+}
+
+/// A double ended iterator over the `InstructionValue`s of a `BasicBlock`, created by
+/// `BasicBlock::get_instructions`.
+#[derive(Debug)]
+pub struct InstructionIter<'ctx> {
+    front: Option<InstructionValue<'ctx>>,
+    back: Option<InstructionValue<'ctx>>,
+}
+
+impl<'ctx> Iterator for InstructionIter<'ctx> {
+    type Item = InstructionValue<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let instruction = self.front?;
+
+        if self.back == Some(instruction) {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.front = instruction.get_next_instruction();
+        }
+
+        Some(instruction)
+    }
+}
+
+impl<'ctx> DoubleEndedIterator for InstructionIter<'ctx> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let instruction = self.back?;
+
+        if self.front == Some(instruction) {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.back = instruction.get_previous_instruction();
+        }
+
+        Some(instruction)
+    }
+}
+
+/// A double ended iterator over the `BasicBlock`s of a `FunctionValue`, created by
+/// `FunctionValue::get_basic_block_iter`.
+#[derive(Debug)]
+pub struct BasicBlockIter<'ctx> {
+    front: Option<BasicBlock<'ctx>>,
+    back: Option<BasicBlock<'ctx>>,
+}
+
+impl<'ctx> BasicBlockIter<'ctx> {
+    pub(crate) fn new(front: Option<BasicBlock<'ctx>>, back: Option<BasicBlock<'ctx>>) -> Self {
+        BasicBlockIter {
+            front,
+            back,
+        }
+    }
+}
+
+impl<'ctx> Iterator for BasicBlockIter<'ctx> {
+    type Item = BasicBlock<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let basic_block = self.front?;
+
+        if self.back == Some(basic_block) {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.front = basic_block.get_next_basic_block();
+        }
+
+        Some(basic_block)
+    }
+}
+
+impl<'ctx> DoubleEndedIterator for BasicBlockIter<'ctx> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let basic_block = self.back?;
+
+        if self.front == Some(basic_block) {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.back = basic_block.get_previous_basic_block();
+        }
+
+        Some(basic_block)
+    }
 }
 
 impl fmt::Debug for BasicBlock<'_> {
@@ -1,26 +1,136 @@
 //! A `Builder` enables you to build instructions.
 
 use either::{Either, Left, Right};
-use llvm_sys::core::{LLVMBuildAdd, LLVMBuildAlloca, LLVMBuildAnd, LLVMBuildArrayAlloca, LLVMBuildArrayMalloc, LLVMBuildAtomicRMW, LLVMBuildBr, LLVMBuildCall, LLVMBuildCast, LLVMBuildCondBr, LLVMBuildExtractValue, LLVMBuildFAdd, LLVMBuildFCmp, LLVMBuildFDiv, LLVMBuildFence, LLVMBuildFMul, LLVMBuildFNeg, LLVMBuildFree, LLVMBuildFSub, LLVMBuildGEP, LLVMBuildICmp, LLVMBuildInsertValue, LLVMBuildIsNotNull, LLVMBuildIsNull, LLVMBuildLoad, LLVMBuildMalloc, LLVMBuildMul, LLVMBuildNeg, LLVMBuildNot, LLVMBuildOr, LLVMBuildPhi, LLVMBuildPointerCast, LLVMBuildRet, LLVMBuildRetVoid, LLVMBuildStore, LLVMBuildSub, LLVMBuildUDiv, LLVMBuildUnreachable, LLVMBuildXor, LLVMDisposeBuilder, LLVMGetElementType, LLVMGetInsertBlock, LLVMGetReturnType, LLVMGetTypeKind, LLVMInsertIntoBuilder, LLVMPositionBuilderAtEnd, LLVMTypeOf, LLVMBuildExtractElement, LLVMBuildInsertElement, LLVMBuildIntToPtr, LLVMBuildPtrToInt, LLVMInsertIntoBuilderWithName, LLVMClearInsertionPosition, LLVMPositionBuilder, LLVMPositionBuilderBefore, LLVMBuildAggregateRet, LLVMBuildStructGEP, LLVMBuildInBoundsGEP, LLVMBuildPtrDiff, LLVMBuildNSWAdd, LLVMBuildNUWAdd, LLVMBuildNSWSub, LLVMBuildNUWSub, LLVMBuildNSWMul, LLVMBuildNUWMul, LLVMBuildSDiv, LLVMBuildSRem, LLVMBuildURem, LLVMBuildFRem, LLVMBuildNSWNeg, LLVMBuildNUWNeg, LLVMBuildFPToUI, LLVMBuildFPToSI, LLVMBuildSIToFP, LLVMBuildUIToFP, LLVMBuildFPTrunc, LLVMBuildFPExt, LLVMBuildIntCast, LLVMBuildFPCast, LLVMBuildSExtOrBitCast, LLVMBuildZExtOrBitCast, LLVMBuildTruncOrBitCast, LLVMBuildSwitch, LLVMAddCase, LLVMBuildShl, LLVMBuildAShr, LLVMBuildLShr, LLVMBuildGlobalString, LLVMBuildGlobalStringPtr, LLVMBuildExactSDiv, LLVMBuildTrunc, LLVMBuildSExt, LLVMBuildZExt, LLVMBuildSelect, LLVMBuildAddrSpaceCast, LLVMBuildBitCast, LLVMBuildShuffleVector, LLVMBuildVAArg, LLVMBuildIndirectBr, LLVMAddDestination};
+use llvm_sys::core::{LLVMBuildAdd, LLVMBuildAlloca, LLVMBuildAnd, LLVMBuildArrayAlloca, LLVMBuildArrayMalloc, LLVMBuildAtomicRMW, LLVMBuildBr, LLVMBuildCall, LLVMBuildCast, LLVMBuildCondBr, LLVMBuildExtractValue, LLVMBuildFAdd, LLVMBuildFCmp, LLVMBuildFDiv, LLVMBuildFence, LLVMBuildFMul, LLVMBuildFNeg, LLVMBuildFree, LLVMBuildFSub, LLVMBuildGEP, LLVMBuildICmp, LLVMBuildInsertValue, LLVMBuildIsNotNull, LLVMBuildIsNull, LLVMBuildLoad, LLVMBuildMalloc, LLVMBuildMul, LLVMBuildNeg, LLVMBuildNot, LLVMBuildOr, LLVMBuildPhi, LLVMBuildPointerCast, LLVMBuildRet, LLVMBuildRetVoid, LLVMBuildStore, LLVMBuildSub, LLVMBuildUDiv, LLVMBuildUnreachable, LLVMBuildXor, LLVMDisposeBuilder, LLVMGetElementType, LLVMGetInsertBlock, LLVMGetReturnType, LLVMGetTypeKind, LLVMInsertIntoBuilder, LLVMPositionBuilderAtEnd, LLVMTypeOf, LLVMBuildExtractElement, LLVMBuildInsertElement, LLVMBuildIntToPtr, LLVMBuildPtrToInt, LLVMInsertIntoBuilderWithName, LLVMClearInsertionPosition, LLVMPositionBuilder, LLVMPositionBuilderBefore, LLVMBuildAggregateRet, LLVMBuildStructGEP, LLVMBuildInBoundsGEP, LLVMBuildPtrDiff, LLVMBuildNSWAdd, LLVMBuildNUWAdd, LLVMBuildNSWSub, LLVMBuildNUWSub, LLVMBuildNSWMul, LLVMBuildNUWMul, LLVMBuildSDiv, LLVMBuildSRem, LLVMBuildURem, LLVMBuildFRem, LLVMBuildNSWNeg, LLVMBuildNUWNeg, LLVMBuildFPToUI, LLVMBuildFPToSI, LLVMBuildSIToFP, LLVMBuildUIToFP, LLVMBuildFPTrunc, LLVMBuildFPExt, LLVMBuildIntCast, LLVMBuildFPCast, LLVMBuildSExtOrBitCast, LLVMBuildZExtOrBitCast, LLVMBuildTruncOrBitCast, LLVMBuildSwitch, LLVMAddCase, LLVMBuildShl, LLVMBuildAShr, LLVMBuildLShr, LLVMBuildGlobalString, LLVMBuildGlobalStringPtr, LLVMBuildExactSDiv, LLVMBuildTrunc, LLVMBuildSExt, LLVMBuildZExt, LLVMBuildSelect, LLVMBuildAddrSpaceCast, LLVMBuildBitCast, LLVMBuildShuffleVector, LLVMBuildVAArg, LLVMBuildIndirectBr, LLVMAddDestination, LLVMIsConstant, LLVMGetPointerAddressSpace};
 #[llvm_versions(3.9..=latest)]
 use llvm_sys::core::LLVMBuildAtomicCmpXchg;
 #[llvm_versions(8.0..=latest)]
 use llvm_sys::core::{LLVMBuildMemCpy, LLVMBuildMemMove};
+use llvm_sys::core::{LLVMBuildInvoke, LLVMBuildLandingPad, LLVMBuildResume, LLVMAddClause, LLVMSetCleanup};
+#[llvm_versions(8.0..=latest)]
+use llvm_sys::core::{LLVMBuildCatchSwitch, LLVMBuildCatchPad, LLVMBuildCleanupPad, LLVMBuildCatchRet, LLVMBuildCleanupRet, LLVMAddHandler, LLVMConstNull, LLVMTokenTypeInContext, LLVMGetTypeContext, LLVMBasicBlockAsValue};
 use llvm_sys::prelude::{LLVMBuilderRef, LLVMValueRef};
 use llvm_sys::{LLVMTypeKind};
 
 use crate::{AtomicOrdering, AtomicRMWBinOp, IntPredicate, FloatPredicate};
 use crate::basic_block::BasicBlock;
 use crate::support::to_c_str;
-use crate::values::{AggregateValue, AggregateValueEnum, AsValueRef, BasicValue, BasicValueEnum, PhiValue, FunctionValue, IntValue, PointerValue, VectorValue, InstructionValue, GlobalValue, IntMathValue, FloatMathValue, PointerMathValue, InstructionOpcode, CallSiteValue};
+use crate::values::{AggregateValue, AggregateValueEnum, AsValueRef, BasicValue, BasicValueEnum, PhiValue, FunctionValue, IntValue, PointerValue, VectorValue, InstructionValue, GlobalValue, IntMathValue, FloatMathValue, PointerMathValue, InstructionOpcode, CallSiteValue, GEPError};
 #[llvm_versions(7.0..=latest)]
 use crate::debug_info::DILocation;
 #[llvm_versions(3.9..=latest)]
 use crate::values::StructValue;
-use crate::types::{AsTypeRef, BasicType, IntMathType, FloatMathType, PointerType, PointerMathType};
+use crate::types::{AsTypeRef, BasicType, BasicTypeEnum, IntMathType, FloatMathType, FunctionType, PointerType, PointerMathType};
 
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
 use std::marker::PhantomData;
 
+/// Errors returned by the `try_build_*` methods of a `Builder` when the instruction
+/// being built would be malformed.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BuilderError {
+    /// The builder is not positioned inside of a basic block.
+    UnsetPosition,
+    /// The callee is a `PointerValue` which does not point to a function.
+    NotAFunctionPointer,
+    /// The number of arguments does not match the callee's parameter count.
+    WrongArgumentCount { expected: u32, found: usize, is_var_args: bool },
+    /// The argument at `position` does not match the type of the corresponding parameter.
+    ArgumentTypeMismatch { position: usize },
+    /// The operands of a binary instruction do not have the same type.
+    OperandTypeMismatch,
+    /// The returned value does not match the return type of the enclosing function.
+    ReturnTypeMismatch,
+    /// The stored value does not match the pointee type of the pointer.
+    StoreTypeMismatch,
+    /// The pointer points to a type which cannot be loaded, such as a function.
+    UnsizedPointee,
+    /// The condition of a branch is not an `i1`, or that of a select is neither an `i1` nor a
+    /// vector of `i1`s as long as the selected vectors.
+    NonBooleanCondition,
+    /// The incoming value at `position` does not match the type of the phi.
+    IncomingTypeMismatch { position: usize },
+    /// The pointer given to `try_build_struct_gep` does not point to a struct.
+    NotAStructPointer,
+    /// A GEP's indices are invalid for its pointee type.
+    GEPError(GEPError),
+    /// The type being allocated is unsized.
+    UnsizedType,
+    /// The cast is not valid between the value's type and the destination type.
+    InvalidCast,
+    /// The address of an indirect branch is not a pointer.
+    NotAPointer,
+    /// The case value at `position` does not have the type of the value being switched on.
+    CaseTypeMismatch { position: usize },
+    /// The case value at `position` is not a constant integer.
+    NonConstantCase { position: usize },
+    /// The landing pad clause at `position` is not a constant.
+    NonConstantClause { position: usize },
+    /// The index is past the last element of the aggregate.
+    AggregateIndexOutOfBounds { index: u32, len: u32 },
+    /// The inserted value does not match the type of the element it replaces.
+    ElementTypeMismatch,
+    /// The shuffle mask is not a constant vector of `i32`s.
+    InvalidShuffleMask,
+    /// The pad operand of an exception handling instruction is not the kind of pad it expects.
+    InvalidPad,
+    /// An alignment is not a power of two.
+    InvalidAlignment,
+    /// The value of an atomic instruction does not have a type it supports.
+    InvalidAtomicOperand,
+    /// The ordering is too weak, or not allowed, for the atomic instruction.
+    InvalidAtomicOrdering,
+}
+
+impl Error for BuilderError {}
+
+impl Display for BuilderError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            BuilderError::UnsetPosition => write!(f, "BuilderError(Builder position is not set)"),
+            BuilderError::NotAFunctionPointer => write!(f, "BuilderError(Callee is not a function pointer)"),
+            BuilderError::WrongArgumentCount { expected, found, is_var_args: false } => {
+                write!(f, "BuilderError(Expected {} arguments but found {})", expected, found)
+            },
+            BuilderError::WrongArgumentCount { expected, found, is_var_args: true } => {
+                write!(f, "BuilderError(Expected at least {} arguments but found {})", expected, found)
+            },
+            BuilderError::ArgumentTypeMismatch { position } => write!(f, "BuilderError(Argument at position {} has the wrong type)", position),
+            BuilderError::OperandTypeMismatch => write!(f, "BuilderError(Operands do not have the same type)"),
+            BuilderError::ReturnTypeMismatch => write!(f, "BuilderError(Return value does not match the function's return type)"),
+            BuilderError::StoreTypeMismatch => write!(f, "BuilderError(Stored value does not match the pointee type)"),
+            BuilderError::UnsizedPointee => write!(f, "BuilderError(Pointee type is unsized)"),
+            BuilderError::NonBooleanCondition => write!(f, "BuilderError(Condition is not an i1)"),
+            BuilderError::IncomingTypeMismatch { position } => write!(f, "BuilderError(Incoming value at position {} does not match the phi type)", position),
+            BuilderError::NotAStructPointer => write!(f, "BuilderError(Pointer does not point to a struct)"),
+            BuilderError::GEPError(err) => write!(f, "BuilderError({})", err),
+            BuilderError::UnsizedType => write!(f, "BuilderError(Type is unsized)"),
+            BuilderError::InvalidCast => write!(f, "BuilderError(Cast is not valid between these types)"),
+            BuilderError::NotAPointer => write!(f, "BuilderError(Address is not a pointer)"),
+            BuilderError::CaseTypeMismatch { position } => write!(f, "BuilderError(Case at position {} does not match the switch type)", position),
+            BuilderError::NonConstantCase { position } => write!(f, "BuilderError(Case at position {} is not a constant integer)", position),
+            BuilderError::NonConstantClause { position } => write!(f, "BuilderError(Clause at position {} is not a constant)", position),
+            BuilderError::AggregateIndexOutOfBounds { index, len } => {
+                write!(f, "BuilderError(Index {} is out of bounds for an aggregate with {} elements)", index, len)
+            },
+            BuilderError::ElementTypeMismatch => write!(f, "BuilderError(Inserted value does not match the element type)"),
+            BuilderError::InvalidShuffleMask => write!(f, "BuilderError(Shuffle mask is not a constant vector of i32s)"),
+            BuilderError::InvalidPad => write!(f, "BuilderError(Pad operand is not the expected kind of pad)"),
+            BuilderError::InvalidAlignment => write!(f, "BuilderError(Alignment is not a power of 2)"),
+            BuilderError::InvalidAtomicOperand => write!(f, "BuilderError(Value type is not supported by the atomic instruction)"),
+            BuilderError::InvalidAtomicOrdering => write!(f, "BuilderError(Atomic ordering is not allowed for the instruction)"),
+        }
+    }
+}
+
+impl From<GEPError> for BuilderError {
+    fn from(err: GEPError) -> Self {
+        BuilderError::GEPError(err)
+    }
+}
+
 #[derive(Debug)]
 pub struct Builder<'ctx> {
     builder: LLVMBuilderRef,
@@ -135,41 +245,309 @@ impl<'ctx> Builder<'ctx> {
     where
         F: Into<FunctionOrPointerValue<'ctx>>,
     {
-        let fn_val_ref = match function.into() {
-            Left(val) => val.as_value_ref(),
-            Right(val) => {
-                // If using a pointer value, we must validate it's a valid function ptr
-                let value_ref = val.as_value_ref();
-                let ty_kind = unsafe { LLVMGetTypeKind(LLVMGetElementType(LLVMTypeOf(value_ref))) };
-                let is_a_fn_ptr = match ty_kind {
-                    LLVMTypeKind::LLVMFunctionTypeKind => true,
-                    _ => false,
-                };
-
-                // REVIEW: We should probably turn this into a Result?
-                assert!(is_a_fn_ptr, "build_call called with a pointer which is not a function pointer");
-
-                value_ref
-            },
+        let fn_val_ref = callee_value_ref(function.into(), "build_call");
+        let name = callee_value_name(fn_val_ref, name);
+
+        let c_string = to_c_str(name);
+        let mut args: Vec<LLVMValueRef> = args.iter()
+                                              .map(|val| val.as_value_ref())
+                                              .collect();
+        let value = unsafe {
+            LLVMBuildCall(self.builder, fn_val_ref, args.as_mut_ptr(), args.len() as u32, c_string.as_ptr())
+        };
+
+        CallSiteValue::new(value)
+    }
+
+    /// Builds an invoke instruction. An invoke behaves like a call, except that control flow
+    /// continues in `then_block` if the callee returns normally and in `catch_block` if it unwinds.
+    /// Like `build_call`, it can take either a `FunctionValue` or a function `PointerValue` and
+    /// will panic if the `PointerValue` is not a function pointer.
+    ///
+    /// The `catch_block` must start with a landing pad (see `build_landing_pad`) or, on LLVM 8+,
+    /// with a funclet pad such as a `catchswitch` or `cleanuppad`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::AddressSpace;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("eh");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    /// let exception_type = context.struct_type(&[i8_ptr_type.into(), i32_type.into()], false);
+    ///
+    /// let personality_type = i32_type.fn_type(&[], true);
+    /// let personality = module.add_function("__gxx_personality_v0", personality_type, None);
+    ///
+    /// let may_throw = module.add_function("may_throw", i32_type.fn_type(&[], false), None);
+    /// let function = module.add_function("wrapper", i32_type.fn_type(&[], false), None);
+    /// let entry = context.append_basic_block(function, "entry");
+    /// let then_block = context.append_basic_block(function, "then");
+    /// let catch_block = context.append_basic_block(function, "catch");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let call_site = builder.build_invoke(may_throw, &[], then_block, catch_block, "call");
+    ///
+    /// builder.position_at_end(then_block);
+    /// builder.build_return(Some(&call_site.try_as_basic_value().left().unwrap()));
+    ///
+    /// builder.position_at_end(catch_block);
+    ///
+    /// let landing_pad = builder.build_landing_pad(exception_type, personality, &[], true, "res");
+    ///
+    /// builder.build_resume(landing_pad);
+    ///
+    /// assert!(module.verify().is_ok());
+    /// ```
+    pub fn build_invoke<F>(
+        &self,
+        function: F,
+        args: &[BasicValueEnum<'ctx>],
+        then_block: BasicBlock<'ctx>,
+        catch_block: BasicBlock<'ctx>,
+        name: &str,
+    ) -> CallSiteValue<'ctx>
+    where
+        F: Into<FunctionOrPointerValue<'ctx>>,
+    {
+        let fn_val_ref = callee_value_ref(function.into(), "build_invoke");
+        let name = callee_value_name(fn_val_ref, name);
+
+        let c_string = to_c_str(name);
+        let mut args: Vec<LLVMValueRef> = args.iter()
+                                              .map(|val| val.as_value_ref())
+                                              .collect();
+        let value = unsafe {
+            LLVMBuildInvoke(
+                self.builder,
+                fn_val_ref,
+                args.as_mut_ptr(),
+                args.len() as u32,
+                then_block.basic_block,
+                catch_block.basic_block,
+                c_string.as_ptr(),
+            )
+        };
+
+        CallSiteValue::new(value)
+    }
+
+    /// Builds a landing pad instruction, which must be the first non-phi instruction of the
+    /// unwind destination of an `invoke`. The `clauses` are the `catch` type infos (pointers) and
+    /// `filter` arrays the landing pad should match on, and `is_cleanup` marks it as a cleanup
+    /// landing pad which is entered regardless of whether any clause matches.
+    ///
+    /// The `personality_function` is also set as the personality of the function containing
+    /// the landing pad, the same as calling `FunctionValue::set_personality_function`.
+    ///
+    /// The returned value has type `exception_type`, which is conventionally `{ i8*, i32 }`.
+    /// See `build_invoke` for an example.
+    pub fn build_landing_pad<T: BasicType<'ctx>>(
+        &self,
+        exception_type: T,
+        personality_function: FunctionValue<'ctx>,
+        clauses: &[BasicValueEnum<'ctx>],
+        is_cleanup: bool,
+        name: &str,
+    ) -> BasicValueEnum<'ctx> {
+        let c_string = to_c_str(name);
+
+        let value = unsafe {
+            LLVMBuildLandingPad(
+                self.builder,
+                exception_type.as_type_ref(),
+                personality_function.as_value_ref(),
+                clauses.len() as u32,
+                c_string.as_ptr(),
+            )
+        };
+
+        for clause in clauses {
+            unsafe {
+                LLVMAddClause(value, clause.as_value_ref());
+            }
+        }
+
+        unsafe {
+            LLVMSetCleanup(value, is_cleanup as i32);
+        }
+
+        BasicValueEnum::new(value)
+    }
+
+    /// Builds a resume instruction, which continues propagating an in-flight exception
+    /// (usually the value produced by `build_landing_pad`) up the stack.
+    /// See `build_invoke` for an example.
+    pub fn build_resume<V: BasicValue<'ctx>>(&self, value: V) -> InstructionValue<'ctx> {
+        let val = unsafe {
+            LLVMBuildResume(self.builder, value.as_value_ref())
+        };
+
+        InstructionValue::new(val)
+    }
+
+    /// Builds a catchswitch instruction, which dispatches an exception to one of several
+    /// `catchpad` `handlers` in funclet based (ie MSVC style) exception handling.
+    ///
+    /// `parent_pad` should be `None` when the catchswitch is not nested within another funclet, and
+    /// `unwind_block` should be `None` if exceptions not caught by any handler unwind to the caller.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("eh");
+    /// let builder = context.create_builder();
+    /// let void_type = context.void_type();
+    /// let i32_type = context.i32_type();
+    ///
+    /// let personality = module.add_function("__CxxFrameHandler3", i32_type.fn_type(&[], true), None);
+    /// let may_throw = module.add_function("may_throw", void_type.fn_type(&[], false), None);
+    /// let function = module.add_function("wrapper", void_type.fn_type(&[], false), None);
+    ///
+    /// function.set_personality_function(personality);
+    ///
+    /// let entry = context.append_basic_block(function, "entry");
+    /// let exit = context.append_basic_block(function, "exit");
+    /// let dispatch = context.append_basic_block(function, "dispatch");
+    /// let handler = context.append_basic_block(function, "handler");
+    ///
+    /// builder.position_at_end(entry);
+    /// builder.build_invoke(may_throw, &[], exit, dispatch, "");
+    ///
+    /// builder.position_at_end(exit);
+    /// builder.build_return(None);
+    ///
+    /// builder.position_at_end(dispatch);
+    ///
+    /// let catch_switch = builder.build_catch_switch(None, None, &[handler], "cs");
+    ///
+    /// builder.position_at_end(handler);
+    ///
+    /// let catch_pad = builder.build_catch_pad(catch_switch, &[], "cp");
+    ///
+    /// builder.build_catch_ret(catch_pad, exit);
+    ///
+    /// assert!(module.verify().is_ok());
+    /// ```
+    #[llvm_versions(8.0..=latest)]
+    pub fn build_catch_switch(
+        &self,
+        parent_pad: Option<InstructionValue<'ctx>>,
+        unwind_block: Option<BasicBlock<'ctx>>,
+        handlers: &[BasicBlock<'ctx>],
+        name: &str,
+    ) -> InstructionValue<'ctx> {
+        let c_string = to_c_str(name);
+        let parent_pad = parent_pad.map_or_else(|| self.none_token(), |pad| pad.as_value_ref());
+        let unwind_block = unwind_block.map_or(std::ptr::null_mut(), |bb| bb.basic_block);
+
+        let value = unsafe {
+            LLVMBuildCatchSwitch(self.builder, parent_pad, unwind_block, handlers.len() as u32, c_string.as_ptr())
         };
 
-        // LLVM gets upset when void return calls are named because they don't return anything
-        let name = unsafe {
-            match LLVMGetTypeKind(LLVMGetReturnType(LLVMGetElementType(LLVMTypeOf(fn_val_ref)))) {
-                LLVMTypeKind::LLVMVoidTypeKind => "",
-                _ => name,
+        for handler in handlers {
+            unsafe {
+                LLVMAddHandler(value, handler.basic_block);
             }
+        }
+
+        InstructionValue::new(value)
+    }
+
+    /// Builds a catchpad instruction, which must be the first non-phi instruction of each handler
+    /// of a `catchswitch`. The `args` are passed to the personality function to decide whether
+    /// the handler matches. See `build_catch_switch` for an example.
+    #[llvm_versions(8.0..=latest)]
+    pub fn build_catch_pad(
+        &self,
+        catch_switch: InstructionValue<'ctx>,
+        args: &[BasicValueEnum<'ctx>],
+        name: &str,
+    ) -> InstructionValue<'ctx> {
+        let c_string = to_c_str(name);
+        let mut args: Vec<LLVMValueRef> = args.iter()
+                                              .map(|val| val.as_value_ref())
+                                              .collect();
+
+        let value = unsafe {
+            LLVMBuildCatchPad(self.builder, catch_switch.as_value_ref(), args.as_mut_ptr(), args.len() as u32, c_string.as_ptr())
         };
 
+        InstructionValue::new(value)
+    }
+
+    /// Builds a cleanuppad instruction, which must be the first non-phi instruction of a block
+    /// running cleanups (ie destructors) while an exception unwinds through it.
+    ///
+    /// `parent_pad` should be `None` when the cleanup is not nested within another funclet.
+    #[llvm_versions(8.0..=latest)]
+    pub fn build_cleanup_pad(
+        &self,
+        parent_pad: Option<InstructionValue<'ctx>>,
+        args: &[BasicValueEnum<'ctx>],
+        name: &str,
+    ) -> InstructionValue<'ctx> {
         let c_string = to_c_str(name);
+        let parent_pad = parent_pad.map_or_else(|| self.none_token(), |pad| pad.as_value_ref());
         let mut args: Vec<LLVMValueRef> = args.iter()
                                               .map(|val| val.as_value_ref())
                                               .collect();
+
         let value = unsafe {
-            LLVMBuildCall(self.builder, fn_val_ref, args.as_mut_ptr(), args.len() as u32, c_string.as_ptr())
+            LLVMBuildCleanupPad(self.builder, parent_pad, args.as_mut_ptr(), args.len() as u32, c_string.as_ptr())
         };
 
-        CallSiteValue::new(value)
+        InstructionValue::new(value)
+    }
+
+    /// Builds a catchret instruction, which ends the handler started by `catch_pad` and
+    /// transfers control to `block`. See `build_catch_switch` for an example.
+    #[llvm_versions(8.0..=latest)]
+    pub fn build_catch_ret(&self, catch_pad: InstructionValue<'ctx>, block: BasicBlock<'ctx>) -> InstructionValue<'ctx> {
+        let value = unsafe {
+            LLVMBuildCatchRet(self.builder, catch_pad.as_value_ref(), block.basic_block)
+        };
+
+        InstructionValue::new(value)
+    }
+
+    /// Builds a cleanupret instruction, which ends the cleanup started by `cleanup_pad` and
+    /// continues unwinding to `unwind_block`, or to the caller if `None`.
+    #[llvm_versions(8.0..=latest)]
+    pub fn build_cleanup_ret(
+        &self,
+        cleanup_pad: InstructionValue<'ctx>,
+        unwind_block: Option<BasicBlock<'ctx>>,
+    ) -> InstructionValue<'ctx> {
+        let unwind_block = unwind_block.map_or(std::ptr::null_mut(), |bb| bb.basic_block);
+
+        let value = unsafe {
+            LLVMBuildCleanupRet(self.builder, cleanup_pad.as_value_ref(), unwind_block)
+        };
+
+        InstructionValue::new(value)
+    }
+
+    /// Funclet pads which are not nested in another pad use the `none` token as their parent.
+    #[llvm_versions(8.0..=latest)]
+    fn none_token(&self) -> LLVMValueRef {
+        unsafe {
+            let block = LLVMGetInsertBlock(self.builder);
+
+            assert!(!block.is_null(), "Builder must be positioned in a basic block to build a funclet pad");
+
+            let context = LLVMGetTypeContext(LLVMTypeOf(LLVMBasicBlockAsValue(block)));
+
+            LLVMConstNull(LLVMTokenTypeInContext(context))
+        }
     }
 
     // REVIEW: Doesn't GEP work on array too?
@@ -199,6 +577,57 @@ impl<'ctx> Builder<'ctx> {
         PointerValue::new(value)
     }
 
+    /// Builds a GEP instruction after checking the indices against the pointee type.
+    /// Struct indices must be constant `i32`s within the bounds of the struct, and every
+    /// index after the first must step into a struct, array or vector.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("checked_gep");
+    /// let void_type = context.void_type();
+    /// let i32_type = context.i32_type();
+    /// let array_type = i32_type.array_type(4);
+    /// let struct_type = context.struct_type(&[i32_type.into(), array_type.into()], false);
+    /// let struct_ptr_type = struct_type.ptr_type(AddressSpace::Generic);
+    /// let fn_type = void_type.fn_type(&[struct_ptr_type.into()], false);
+    /// let fn_value = module.add_function("", fn_type, None);
+    /// let entry = context.append_basic_block(fn_value, "entry");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let struct_ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let zero = i32_type.const_zero();
+    /// let one = i32_type.const_int(1, false);
+    /// let two = i32_type.const_int(2, false);
+    ///
+    /// assert!(builder.build_checked_gep(struct_ptr, &[zero, one, two], "array_elem").is_ok());
+    /// assert!(builder.build_checked_gep(struct_ptr, &[zero, two], "oob_field").is_err());
+    /// assert!(builder.build_checked_gep(struct_ptr, &[zero, zero, zero], "too_deep").is_err());
+    /// ```
+    pub fn build_checked_gep(&self, ptr: PointerValue<'ctx>, ordered_indexes: &[IntValue<'ctx>], name: &str) -> Result<PointerValue<'ctx>, GEPError> {
+        ptr.validate_gep_indexes(ordered_indexes)?;
+
+        unsafe {
+            Ok(self.build_gep(ptr, ordered_indexes, name))
+        }
+    }
+
+    /// Builds an in bounds GEP instruction after checking the indices against the pointee type.
+    /// See `build_checked_gep` for the checks performed.
+    pub fn build_checked_in_bounds_gep(&self, ptr: PointerValue<'ctx>, ordered_indexes: &[IntValue<'ctx>], name: &str) -> Result<PointerValue<'ctx>, GEPError> {
+        ptr.validate_gep_indexes(ordered_indexes)?;
+
+        unsafe {
+            Ok(self.build_in_bounds_gep(ptr, ordered_indexes, name))
+        }
+    }
+
     /// Builds a GEP instruction on a struct pointer. Returns `Err(())` if input `PointerValue` doesn't
     /// point to a struct or if index is out of bounds.
     ///
@@ -1821,6 +2250,1171 @@ impl<'ctx> Builder<'ctx> {
     }
 }
 
+macro_rules! try_build_binary_op {
+    ($lt:lifetime; $($try_name:ident => $name:ident: $value_trait:ident,)+) => {
+        $(
+            /// A variant of the similarly named `build_*` method which returns an error if the
+            /// builder is not positioned or if the operands have different types.
+            pub fn $try_name<T: $value_trait<$lt>>(&self, lhs: T, rhs: T, name: &str) -> Result<T, BuilderError> {
+                self.check_position()?;
+                check_same_type(&lhs, &rhs)?;
+
+                Ok(self.$name(lhs, rhs, name))
+            }
+        )+
+    };
+}
+
+macro_rules! try_build_unary_op {
+    ($lt:lifetime; $($try_name:ident => $name:ident: $value_trait:ident,)+) => {
+        $(
+            /// A variant of the similarly named `build_*` method which returns an error if the
+            /// builder is not positioned.
+            pub fn $try_name<T: $value_trait<$lt>>(&self, value: T, name: &str) -> Result<T, BuilderError> {
+                self.check_position()?;
+
+                Ok(self.$name(value, name))
+            }
+        )+
+    };
+}
+
+/// `Result` returning variants of the build methods. Rather than handing malformed
+/// instructions to LLVM, which may abort the process, these check the builder's position
+/// and the types of their operands and return a `BuilderError` describing the problem.
+impl<'ctx> Builder<'ctx> {
+    fn check_position(&self) -> Result<BasicBlock<'ctx>, BuilderError> {
+        self.get_insert_block().ok_or(BuilderError::UnsetPosition)
+    }
+
+    /// Builds a function return instruction, checking that the value matches the return type
+    /// of the function the builder is positioned in.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::builder::BuilderError;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("ret");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[], false);
+    /// let fn_value = module.add_function("ret", fn_type, None);
+    ///
+    /// assert_eq!(builder.try_build_return(None), Err(BuilderError::UnsetPosition));
+    ///
+    /// let entry = context.append_basic_block(fn_value, "entry");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// assert_eq!(builder.try_build_return(None), Err(BuilderError::ReturnTypeMismatch));
+    /// assert!(builder.try_build_return(Some(&i32_type.const_zero())).is_ok());
+    /// ```
+    pub fn try_build_return(&self, value: Option<&dyn BasicValue<'ctx>>) -> Result<InstructionValue<'ctx>, BuilderError> {
+        let block = self.check_position()?;
+        let return_type = block.get_parent()
+            .and_then(|function| function.get_type().get_return_type())
+            .map(|ty| ty.as_type_ref());
+        let value_type = value.map(|value| unsafe { LLVMTypeOf(value.as_value_ref()) });
+
+        if return_type != value_type {
+            return Err(BuilderError::ReturnTypeMismatch);
+        }
+
+        Ok(self.build_return(value))
+    }
+
+    /// Builds a function call instruction, checking the callee and the number and types of
+    /// the arguments against its `FunctionType`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::builder::BuilderError;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("call");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let i64_type = context.i64_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    /// let fn_value = module.add_function("call", fn_type, None);
+    /// let entry = context.append_basic_block(fn_value, "entry");
+    ///
+    /// builder.position_at_end(entry);
+    ///
+    /// let wrong_count = builder.try_build_call(fn_value, &[], "call");
+    /// let wrong_type = builder.try_build_call(fn_value, &[i64_type.const_zero().into()], "call");
+    ///
+    /// assert_eq!(wrong_count, Err(BuilderError::WrongArgumentCount { expected: 1, found: 0, is_var_args: false }));
+    /// assert_eq!(wrong_type, Err(BuilderError::ArgumentTypeMismatch { position: 0 }));
+    /// assert!(builder.try_build_call(fn_value, &[i32_type.const_zero().into()], "call").is_ok());
+    /// ```
+    pub fn try_build_call<F>(&self, function: F, args: &[BasicValueEnum<'ctx>], name: &str) -> Result<CallSiteValue<'ctx>, BuilderError>
+    where
+        F: Into<FunctionOrPointerValue<'ctx>>,
+    {
+        self.check_position()?;
+
+        let function = function.into();
+
+        check_call_args(callee_fn_type(function)?, args)?;
+
+        Ok(self.build_call(function, args, name))
+    }
+
+    /// Builds an invoke instruction, performing the same checks as `try_build_call`.
+    pub fn try_build_invoke<F>(
+        &self,
+        function: F,
+        args: &[BasicValueEnum<'ctx>],
+        then_block: BasicBlock<'ctx>,
+        catch_block: BasicBlock<'ctx>,
+        name: &str,
+    ) -> Result<CallSiteValue<'ctx>, BuilderError>
+    where
+        F: Into<FunctionOrPointerValue<'ctx>>,
+    {
+        self.check_position()?;
+
+        let function = function.into();
+
+        check_call_args(callee_fn_type(function)?, args)?;
+
+        Ok(self.build_invoke(function, args, then_block, catch_block, name))
+    }
+
+    /// Builds a store instruction, checking that the value matches the pointee type.
+    pub fn try_build_store<V: BasicValue<'ctx>>(&self, ptr: PointerValue<'ctx>, value: V) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        let pointee_type = ptr.get_type().get_element_type();
+
+        if pointee_type.as_type_ref() != unsafe { LLVMTypeOf(value.as_value_ref()) } {
+            return Err(BuilderError::StoreTypeMismatch);
+        }
+
+        Ok(self.build_store(ptr, value))
+    }
+
+    /// Builds a load instruction, checking that the pointee type is sized.
+    pub fn try_build_load(&self, ptr: PointerValue<'ctx>, name: &str) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        let pointee_type = ptr.get_type().get_element_type();
+
+        if pointee_type.is_function_type() || pointee_type.is_void_type() || !pointee_type.to_basic_type_enum().is_sized() {
+            return Err(BuilderError::UnsizedPointee);
+        }
+
+        Ok(self.build_load(ptr, name))
+    }
+
+    /// Builds a function return instruction returning an aggregate built from `values`, checking
+    /// that they match the fields of the return type of the function the builder is positioned in.
+    pub fn try_build_aggregate_return(&self, values: &[BasicValueEnum<'ctx>]) -> Result<InstructionValue<'ctx>, BuilderError> {
+        let block = self.check_position()?;
+        let field_types = match block.get_parent().and_then(|function| function.get_type().get_return_type()) {
+            Some(BasicTypeEnum::StructType(struct_type)) => struct_type.get_field_types(),
+            Some(BasicTypeEnum::ArrayType(array_type)) => vec![array_type.get_element_type(); array_type.len() as usize],
+            _ => return Err(BuilderError::ReturnTypeMismatch),
+        };
+
+        if field_types.len() != values.len() || values.iter().zip(field_types).any(|(value, ty)| value.get_type() != ty) {
+            return Err(BuilderError::ReturnTypeMismatch);
+        }
+
+        Ok(self.build_aggregate_return(values))
+    }
+
+    /// Builds a landing pad, checking that each of the clauses is a constant.
+    pub fn try_build_landing_pad<T: BasicType<'ctx>>(
+        &self,
+        exception_type: T,
+        personality_function: FunctionValue<'ctx>,
+        clauses: &[BasicValueEnum<'ctx>],
+        is_cleanup: bool,
+        name: &str,
+    ) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        if let Some(position) = clauses.iter().position(|clause| unsafe { LLVMIsConstant(clause.as_value_ref()) } == 0) {
+            return Err(BuilderError::NonConstantClause { position });
+        }
+
+        Ok(self.build_landing_pad(exception_type, personality_function, clauses, is_cleanup, name))
+    }
+
+    /// Builds a resume instruction, checking the builder's position.
+    pub fn try_build_resume<V: BasicValue<'ctx>>(&self, value: V) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        Ok(self.build_resume(value))
+    }
+
+    /// Builds a catchswitch instruction, checking that `parent_pad` is a funclet pad.
+    #[llvm_versions(8.0..=latest)]
+    pub fn try_build_catch_switch(
+        &self,
+        parent_pad: Option<InstructionValue<'ctx>>,
+        unwind_block: Option<BasicBlock<'ctx>>,
+        handlers: &[BasicBlock<'ctx>],
+        name: &str,
+    ) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+        check_parent_pad(parent_pad)?;
+
+        Ok(self.build_catch_switch(parent_pad, unwind_block, handlers, name))
+    }
+
+    /// Builds a catchpad instruction, checking that `catch_switch` is a catchswitch.
+    #[llvm_versions(8.0..=latest)]
+    pub fn try_build_catch_pad(
+        &self,
+        catch_switch: InstructionValue<'ctx>,
+        args: &[BasicValueEnum<'ctx>],
+        name: &str,
+    ) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+        check_pad(catch_switch, InstructionOpcode::CatchSwitch)?;
+
+        Ok(self.build_catch_pad(catch_switch, args, name))
+    }
+
+    /// Builds a cleanuppad instruction, checking that `parent_pad` is a funclet pad.
+    #[llvm_versions(8.0..=latest)]
+    pub fn try_build_cleanup_pad(
+        &self,
+        parent_pad: Option<InstructionValue<'ctx>>,
+        args: &[BasicValueEnum<'ctx>],
+        name: &str,
+    ) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+        check_parent_pad(parent_pad)?;
+
+        Ok(self.build_cleanup_pad(parent_pad, args, name))
+    }
+
+    /// Builds a catchret instruction, checking that `catch_pad` is a catchpad.
+    #[llvm_versions(8.0..=latest)]
+    pub fn try_build_catch_ret(&self, catch_pad: InstructionValue<'ctx>, block: BasicBlock<'ctx>) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+        check_pad(catch_pad, InstructionOpcode::CatchPad)?;
+
+        Ok(self.build_catch_ret(catch_pad, block))
+    }
+
+    /// Builds a cleanupret instruction, checking that `cleanup_pad` is a cleanuppad.
+    #[llvm_versions(8.0..=latest)]
+    pub fn try_build_cleanup_ret(
+        &self,
+        cleanup_pad: InstructionValue<'ctx>,
+        unwind_block: Option<BasicBlock<'ctx>>,
+    ) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+        check_pad(cleanup_pad, InstructionOpcode::CleanupPad)?;
+
+        Ok(self.build_cleanup_ret(cleanup_pad, unwind_block))
+    }
+
+    /// Builds a pointer difference, checking that both pointers have the same type.
+    pub fn try_build_ptr_diff(&self, lhs_ptr: PointerValue<'ctx>, rhs_ptr: PointerValue<'ctx>, name: &str) -> Result<IntValue<'ctx>, BuilderError> {
+        self.check_position()?;
+        check_same_type(&lhs_ptr, &rhs_ptr)?;
+
+        Ok(self.build_ptr_diff(lhs_ptr, rhs_ptr, name))
+    }
+
+    /// Builds a stack allocation, checking that the type is sized.
+    pub fn try_build_alloca<T: BasicType<'ctx>>(&self, ty: T, name: &str) -> Result<PointerValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        if !ty.is_sized() {
+            return Err(BuilderError::UnsizedType);
+        }
+
+        Ok(self.build_alloca(ty, name))
+    }
+
+    /// Builds a stack allocation of `size` elements, checking that the type is sized.
+    pub fn try_build_array_alloca<T: BasicType<'ctx>>(&self, ty: T, size: IntValue<'ctx>, name: &str) -> Result<PointerValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        if !ty.is_sized() {
+            return Err(BuilderError::UnsizedType);
+        }
+
+        Ok(self.build_array_alloca(ty, size, name))
+    }
+
+    /// Builds a memcpy, checking the builder's position and both alignments.
+    #[llvm_versions(8.0..=latest)]
+    pub fn try_build_memcpy(
+        &self,
+        dest: PointerValue<'ctx>,
+        dest_align_bytes: u32,
+        src: PointerValue<'ctx>,
+        src_align_bytes: u32,
+        size: IntValue<'ctx>,
+    ) -> Result<PointerValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        self.build_memcpy(dest, dest_align_bytes, src, src_align_bytes, size).map_err(|_| BuilderError::InvalidAlignment)
+    }
+
+    /// Builds a memmove, checking the builder's position and both alignments.
+    #[llvm_versions(8.0..=latest)]
+    pub fn try_build_memmove(
+        &self,
+        dest: PointerValue<'ctx>,
+        dest_align_bytes: u32,
+        src: PointerValue<'ctx>,
+        src_align_bytes: u32,
+        size: IntValue<'ctx>,
+    ) -> Result<PointerValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        self.build_memmove(dest, dest_align_bytes, src, src_align_bytes, size).map_err(|_| BuilderError::InvalidAlignment)
+    }
+
+    /// Builds a heap allocation, checking that the type is sized.
+    pub fn try_build_malloc<T: BasicType<'ctx>>(&self, ty: T, name: &str) -> Result<PointerValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        self.build_malloc(ty, name).map_err(|_| BuilderError::UnsizedType)
+    }
+
+    /// Builds a heap allocation of `size` elements, checking that the type is sized.
+    pub fn try_build_array_malloc<T: BasicType<'ctx>>(&self, ty: T, size: IntValue<'ctx>, name: &str) -> Result<PointerValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        self.build_array_malloc(ty, size, name).map_err(|_| BuilderError::UnsizedType)
+    }
+
+    /// Builds a call to free, checking the builder's position.
+    pub fn try_build_free(&self, ptr: PointerValue<'ctx>) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        Ok(self.build_free(ptr))
+    }
+
+    /// Builds a cast of the given `op`, checking that it is a cast instruction which is
+    /// valid between the value's type and `to_type`.
+    pub fn try_build_cast<T: BasicType<'ctx>, V: BasicValue<'ctx>>(
+        &self,
+        op: InstructionOpcode,
+        from_value: V,
+        to_type: T,
+        name: &str,
+    ) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+        self.check_position()?;
+        check_cast(op, from_value.as_basic_value_enum().get_type(), to_type.as_basic_type_enum())?;
+
+        Ok(self.build_cast(op, from_value, to_type, name))
+    }
+
+    /// Builds a bitcast, checking that both types have the same bit width, or are pointers
+    /// in the same address space.
+    pub fn try_build_bitcast<T, V>(&self, val: V, ty: T, name: &str) -> Result<BasicValueEnum<'ctx>, BuilderError>
+    where
+        T: BasicType<'ctx>,
+        V: BasicValue<'ctx>,
+    {
+        self.check_position()?;
+        check_cast(InstructionOpcode::BitCast, val.as_basic_value_enum().get_type(), ty.as_basic_type_enum())?;
+
+        Ok(self.build_bitcast(val, ty, name))
+    }
+
+    /// Builds an address space cast, checking that the address spaces differ.
+    pub fn try_build_address_space_cast(&self, ptr_val: PointerValue<'ctx>, ptr_type: PointerType<'ctx>, name: &str) -> Result<PointerValue<'ctx>, BuilderError> {
+        self.check_position()?;
+        check_cast(InstructionOpcode::AddrSpaceCast, ptr_val.get_type().as_basic_type_enum(), ptr_type.as_basic_type_enum())?;
+
+        Ok(self.build_address_space_cast(ptr_val, ptr_type, name))
+    }
+
+    /// Builds a pointer cast, checking that the cast between the two pointer types is valid.
+    pub fn try_build_pointer_cast<T: PointerMathValue<'ctx>>(&self, from: T, to: T::BaseType, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+
+        let from_type = from.as_basic_value_enum().get_type();
+        let to_type = to.as_basic_type_enum();
+        let op = if address_space_of(from_type) == address_space_of(to_type) { InstructionOpcode::BitCast } else { InstructionOpcode::AddrSpaceCast };
+
+        check_cast(op, from_type, to_type)?;
+
+        Ok(self.build_pointer_cast(from, to, name))
+    }
+
+    /// Builds an integer cast, checking that the value and type have the same shape.
+    pub fn try_build_int_cast<T: IntMathValue<'ctx>>(&self, int: T, int_type: T::BaseType, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_resizing_cast(&int, &int_type, InstructionOpcode::Trunc, InstructionOpcode::SExt)?;
+
+        Ok(self.build_int_cast(int, int_type, name))
+    }
+
+    /// Builds a floating point cast, checking that the value and type have the same shape.
+    pub fn try_build_float_cast<T: FloatMathValue<'ctx>>(&self, float: T, float_type: T::BaseType, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_resizing_cast(&float, &float_type, InstructionOpcode::FPTrunc, InstructionOpcode::FPExt)?;
+
+        Ok(self.build_float_cast(float, float_type, name))
+    }
+
+    /// Builds a sign extension or, between types of the same width, a bitcast.
+    pub fn try_build_int_s_extend_or_bit_cast<T: IntMathValue<'ctx>>(&self, int_value: T, int_type: T::BaseType, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_resizing_cast(&int_value, &int_type, InstructionOpcode::SExt, InstructionOpcode::SExt)?;
+
+        Ok(self.build_int_s_extend_or_bit_cast(int_value, int_type, name))
+    }
+
+    /// Builds a zero extension or, between types of the same width, a bitcast.
+    pub fn try_build_int_z_extend_or_bit_cast<T: IntMathValue<'ctx>>(&self, int_value: T, int_type: T::BaseType, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_resizing_cast(&int_value, &int_type, InstructionOpcode::ZExt, InstructionOpcode::ZExt)?;
+
+        Ok(self.build_int_z_extend_or_bit_cast(int_value, int_type, name))
+    }
+
+    /// Builds a truncation or, between types of the same width, a bitcast.
+    pub fn try_build_int_truncate_or_bit_cast<T: IntMathValue<'ctx>>(&self, int_value: T, int_type: T::BaseType, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_resizing_cast(&int_value, &int_type, InstructionOpcode::Trunc, InstructionOpcode::Trunc)?;
+
+        Ok(self.build_int_truncate_or_bit_cast(int_value, int_type, name))
+    }
+
+    /// Builds a phi instruction along with its incoming values, checking that each of them
+    /// matches the phi's type.
+    pub fn try_build_phi<T: BasicType<'ctx>>(
+        &self,
+        type_: T,
+        incoming: &[(&dyn BasicValue<'ctx>, BasicBlock<'ctx>)],
+        name: &str,
+    ) -> Result<PhiValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        let type_ref = type_.as_type_ref();
+
+        for (position, (value, _)) in incoming.iter().enumerate() {
+            if unsafe { LLVMTypeOf(value.as_value_ref()) } != type_ref {
+                return Err(BuilderError::IncomingTypeMismatch { position });
+            }
+        }
+
+        let phi = self.build_phi(type_, name);
+
+        phi.add_incoming(incoming);
+
+        Ok(phi)
+    }
+
+    /// Builds a GEP instruction after validating its indices. See `build_checked_gep`.
+    pub fn try_build_gep(&self, ptr: PointerValue<'ctx>, ordered_indexes: &[IntValue<'ctx>], name: &str) -> Result<PointerValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        Ok(self.build_checked_gep(ptr, ordered_indexes, name)?)
+    }
+
+    /// Builds an in bounds GEP instruction after validating its indices. See `build_checked_gep`.
+    pub fn try_build_in_bounds_gep(&self, ptr: PointerValue<'ctx>, ordered_indexes: &[IntValue<'ctx>], name: &str) -> Result<PointerValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        Ok(self.build_checked_in_bounds_gep(ptr, ordered_indexes, name)?)
+    }
+
+    /// Builds a GEP instruction on a struct pointer, describing why the GEP is invalid
+    /// rather than returning `Err(())` as `build_struct_gep` does.
+    pub fn try_build_struct_gep(&self, ptr: PointerValue<'ctx>, index: u32, name: &str) -> Result<PointerValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        let pointee_type = ptr.get_type().get_element_type();
+
+        if !pointee_type.is_struct_type() {
+            return Err(BuilderError::NotAStructPointer);
+        }
+
+        let num_fields = pointee_type.into_struct_type().count_fields();
+
+        if index >= num_fields {
+            return Err(GEPError::StructIndexOutOfBounds { position: 1, index: index as u64, num_fields }.into());
+        }
+
+        Ok(self.build_struct_gep(ptr, index, name).expect("struct GEP to have been validated"))
+    }
+
+    /// Builds a truncation, checking that the type is narrower than the value's.
+    pub fn try_build_int_truncate<T: IntMathValue<'ctx>>(&self, int_value: T, int_type: T::BaseType, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_value_cast(InstructionOpcode::Trunc, &int_value, &int_type)?;
+
+        Ok(self.build_int_truncate(int_value, int_type, name))
+    }
+
+    /// Builds a sign extension, checking that the type is wider than the value's.
+    pub fn try_build_int_s_extend<T: IntMathValue<'ctx>>(&self, int_value: T, int_type: T::BaseType, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_value_cast(InstructionOpcode::SExt, &int_value, &int_type)?;
+
+        Ok(self.build_int_s_extend(int_value, int_type, name))
+    }
+
+    /// Builds a zero extension, checking that the type is wider than the value's.
+    pub fn try_build_int_z_extend<T: IntMathValue<'ctx>>(&self, int_value: T, int_type: T::BaseType, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_value_cast(InstructionOpcode::ZExt, &int_value, &int_type)?;
+
+        Ok(self.build_int_z_extend(int_value, int_type, name))
+    }
+
+    /// Builds a floating point truncation, checking that the type is narrower than the value's.
+    pub fn try_build_float_trunc<T: FloatMathValue<'ctx>>(&self, float: T, float_type: T::BaseType, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_value_cast(InstructionOpcode::FPTrunc, &float, &float_type)?;
+
+        Ok(self.build_float_trunc(float, float_type, name))
+    }
+
+    /// Builds a floating point extension, checking that the type is wider than the value's.
+    pub fn try_build_float_ext<T: FloatMathValue<'ctx>>(&self, float: T, float_type: T::BaseType, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_value_cast(InstructionOpcode::FPExt, &float, &float_type)?;
+
+        Ok(self.build_float_ext(float, float_type, name))
+    }
+
+    /// Builds a floating point to unsigned integer conversion, checking that vector lengths match.
+    pub fn try_build_float_to_unsigned_int<T: FloatMathValue<'ctx>>(
+        &self,
+        float: T,
+        int_type: <T::BaseType as FloatMathType<'ctx>>::MathConvType,
+        name: &str,
+    ) -> Result<<<T::BaseType as FloatMathType<'ctx>>::MathConvType as IntMathType<'ctx>>::ValueType, BuilderError> {
+        self.check_position()?;
+        check_value_cast(InstructionOpcode::FPToUI, &float, &int_type)?;
+
+        Ok(self.build_float_to_unsigned_int(float, int_type, name))
+    }
+
+    /// Builds a floating point to signed integer conversion, checking that vector lengths match.
+    pub fn try_build_float_to_signed_int<T: FloatMathValue<'ctx>>(
+        &self,
+        float: T,
+        int_type: <T::BaseType as FloatMathType<'ctx>>::MathConvType,
+        name: &str,
+    ) -> Result<<<T::BaseType as FloatMathType<'ctx>>::MathConvType as IntMathType<'ctx>>::ValueType, BuilderError> {
+        self.check_position()?;
+        check_value_cast(InstructionOpcode::FPToSI, &float, &int_type)?;
+
+        Ok(self.build_float_to_signed_int(float, int_type, name))
+    }
+
+    /// Builds an unsigned integer to floating point conversion, checking that vector lengths match.
+    pub fn try_build_unsigned_int_to_float<T: IntMathValue<'ctx>>(
+        &self,
+        int: T,
+        float_type: <T::BaseType as IntMathType<'ctx>>::MathConvType,
+        name: &str,
+    ) -> Result<<<T::BaseType as IntMathType<'ctx>>::MathConvType as FloatMathType<'ctx>>::ValueType, BuilderError> {
+        self.check_position()?;
+        check_value_cast(InstructionOpcode::UIToFP, &int, &float_type)?;
+
+        Ok(self.build_unsigned_int_to_float(int, float_type, name))
+    }
+
+    /// Builds a signed integer to floating point conversion, checking that vector lengths match.
+    pub fn try_build_signed_int_to_float<T: IntMathValue<'ctx>>(
+        &self,
+        int: T,
+        float_type: <T::BaseType as IntMathType<'ctx>>::MathConvType,
+        name: &str,
+    ) -> Result<<<T::BaseType as IntMathType<'ctx>>::MathConvType as FloatMathType<'ctx>>::ValueType, BuilderError> {
+        self.check_position()?;
+        check_value_cast(InstructionOpcode::SIToFP, &int, &float_type)?;
+
+        Ok(self.build_signed_int_to_float(int, float_type, name))
+    }
+
+    /// Builds an integer to pointer conversion, checking that vector lengths match.
+    pub fn try_build_int_to_ptr<T: IntMathValue<'ctx>>(
+        &self,
+        int: T,
+        ptr_type: <T::BaseType as IntMathType<'ctx>>::PtrConvType,
+        name: &str,
+    ) -> Result<<<T::BaseType as IntMathType<'ctx>>::PtrConvType as PointerMathType<'ctx>>::ValueType, BuilderError> {
+        self.check_position()?;
+        check_value_cast(InstructionOpcode::IntToPtr, &int, &ptr_type)?;
+
+        Ok(self.build_int_to_ptr(int, ptr_type, name))
+    }
+
+    /// Builds a pointer to integer conversion, checking that vector lengths match.
+    pub fn try_build_ptr_to_int<T: PointerMathValue<'ctx>>(
+        &self,
+        ptr: T,
+        int_type: <T::BaseType as PointerMathType<'ctx>>::PtrConvType,
+        name: &str,
+    ) -> Result<<<T::BaseType as PointerMathType<'ctx>>::PtrConvType as IntMathType<'ctx>>::ValueType, BuilderError> {
+        self.check_position()?;
+        check_value_cast(InstructionOpcode::PtrToInt, &ptr, &int_type)?;
+
+        Ok(self.build_ptr_to_int(ptr, int_type, name))
+    }
+
+    /// Builds a null pointer check, checking the builder's position.
+    pub fn try_build_is_null<T: PointerMathValue<'ctx>>(
+        &self,
+        ptr: T,
+        name: &str,
+    ) -> Result<<<T::BaseType as PointerMathType<'ctx>>::PtrConvType as IntMathType<'ctx>>::ValueType, BuilderError> {
+        self.check_position()?;
+
+        Ok(self.build_is_null(ptr, name))
+    }
+
+    /// Builds a non-null pointer check, checking the builder's position.
+    pub fn try_build_is_not_null<T: PointerMathValue<'ctx>>(
+        &self,
+        ptr: T,
+        name: &str,
+    ) -> Result<<<T::BaseType as PointerMathType<'ctx>>::PtrConvType as IntMathType<'ctx>>::ValueType, BuilderError> {
+        self.check_position()?;
+
+        Ok(self.build_is_not_null(ptr, name))
+    }
+
+    /// Builds an unconditional branch, checking the builder's position.
+    pub fn try_build_unconditional_branch(&self, destination_block: BasicBlock<'ctx>) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        Ok(self.build_unconditional_branch(destination_block))
+    }
+
+    /// Builds an indirect branch, checking that the address is a pointer.
+    pub fn try_build_indirect_branch<BV: BasicValue<'ctx>>(
+        &self,
+        address: BV,
+        destinations: &[BasicBlock<'ctx>],
+    ) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        if !address.as_basic_value_enum().is_pointer_value() {
+            return Err(BuilderError::NotAPointer);
+        }
+
+        Ok(self.build_indirect_branch(address, destinations))
+    }
+
+    /// Builds a switch, checking that each case value is a constant of the switched on type.
+    pub fn try_build_switch(
+        &self,
+        value: IntValue<'ctx>,
+        else_block: BasicBlock<'ctx>,
+        cases: &[(IntValue<'ctx>, BasicBlock<'ctx>)],
+    ) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        for (position, (case, _)) in cases.iter().enumerate() {
+            if case.get_type() != value.get_type() {
+                return Err(BuilderError::CaseTypeMismatch { position });
+            }
+
+            if !case.is_constant_int() {
+                return Err(BuilderError::NonConstantCase { position });
+            }
+        }
+
+        Ok(self.build_switch(value, else_block, cases))
+    }
+
+    /// Builds an unreachable instruction, checking the builder's position.
+    pub fn try_build_unreachable(&self) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        Ok(self.build_unreachable())
+    }
+
+    /// Builds an extract value instruction, checking that the index is in bounds.
+    pub fn try_build_extract_value<AV: AggregateValue<'ctx>>(&self, agg: AV, index: u32, name: &str) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+        self.check_position()?;
+        aggregate_field_type(agg.as_aggregate_value_enum(), index)?;
+
+        Ok(self.build_extract_value(agg, index, name).expect("index to have been validated"))
+    }
+
+    /// Builds an insert value instruction, checking that the index is in bounds and that the
+    /// value matches the type of the field it replaces.
+    pub fn try_build_insert_value<AV, BV>(&self, agg: AV, value: BV, index: u32, name: &str) -> Result<AggregateValueEnum<'ctx>, BuilderError>
+    where
+        AV: AggregateValue<'ctx>,
+        BV: BasicValue<'ctx>,
+    {
+        self.check_position()?;
+
+        if aggregate_field_type(agg.as_aggregate_value_enum(), index)? != value.as_basic_value_enum().get_type() {
+            return Err(BuilderError::ElementTypeMismatch);
+        }
+
+        Ok(self.build_insert_value(agg, value, index, name).expect("index to have been validated"))
+    }
+
+    /// Builds an extract element instruction, checking the builder's position.
+    pub fn try_build_extract_element(&self, vector: VectorValue<'ctx>, index: IntValue<'ctx>, name: &str) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        Ok(self.build_extract_element(vector, index, name))
+    }
+
+    /// Builds an insert element instruction, checking that the element matches the vector's element type.
+    pub fn try_build_insert_element<V: BasicValue<'ctx>>(
+        &self,
+        vector: VectorValue<'ctx>,
+        element: V,
+        index: IntValue<'ctx>,
+        name: &str,
+    ) -> Result<VectorValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        if vector.get_type().get_element_type() != element.as_basic_value_enum().get_type() {
+            return Err(BuilderError::ElementTypeMismatch);
+        }
+
+        Ok(self.build_insert_element(vector, element, index, name))
+    }
+
+    /// Builds a shuffle vector instruction, checking that both vectors have the same type and
+    /// that the mask is a constant vector of `i32`s.
+    pub fn try_build_shuffle_vector(&self, left: VectorValue<'ctx>, right: VectorValue<'ctx>, mask: VectorValue<'ctx>, name: &str) -> Result<VectorValue<'ctx>, BuilderError> {
+        self.check_position()?;
+        check_same_type(&left, &right)?;
+
+        let is_i32_mask = match mask.get_type().get_element_type() {
+            BasicTypeEnum::IntType(int_type) => int_type.get_bit_width() == 32,
+            _ => false,
+        };
+
+        if !is_i32_mask || !mask.is_const() {
+            return Err(BuilderError::InvalidShuffleMask);
+        }
+
+        Ok(self.build_shuffle_vector(left, right, mask, name))
+    }
+
+    /// Builds a global string and a pointer to it, checking the builder's position, which
+    /// LLVM uses to find the module to add the global to.
+    pub fn try_build_global_string_ptr(&self, value: &str, name: &str) -> Result<GlobalValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        Ok(self.build_global_string_ptr(value, name))
+    }
+
+    /// Builds a va_arg instruction, checking the builder's position.
+    pub fn try_build_va_arg<BT: BasicType<'ctx>>(&self, list: PointerValue<'ctx>, type_: BT, name: &str) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        Ok(self.build_va_arg(list, type_, name))
+    }
+
+    /// Builds a fence, checking that the ordering is at least `Acquire`.
+    pub fn try_build_fence(&self, atomic_ordering: AtomicOrdering, num: i32, name: &str) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        if atomic_ordering < AtomicOrdering::Acquire {
+            return Err(BuilderError::InvalidAtomicOrdering);
+        }
+
+        Ok(self.build_fence(atomic_ordering, num, name))
+    }
+
+    /// Builds an atomicrmw instruction, checking the value's type against the pointee type and
+    /// that the ordering is at least `Monotonic`.
+    pub fn try_build_atomicrmw(
+        &self,
+        op: AtomicRMWBinOp,
+        ptr: PointerValue<'ctx>,
+        value: IntValue<'ctx>,
+        ordering: AtomicOrdering,
+    ) -> Result<IntValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        let bit_width = value.get_type().get_bit_width();
+
+        if bit_width < 8 || !bit_width.is_power_of_two() {
+            return Err(BuilderError::InvalidAtomicOperand);
+        }
+
+        if ptr.get_type().get_element_type() != value.get_type().into() {
+            return Err(BuilderError::StoreTypeMismatch);
+        }
+
+        if ordering < AtomicOrdering::Monotonic {
+            return Err(BuilderError::InvalidAtomicOrdering);
+        }
+
+        Ok(self.build_atomicrmw(op, ptr, value, ordering).expect("atomicrmw operands to have been validated"))
+    }
+
+    /// Builds a cmpxchg instruction, checking the values' types against the pointee type and
+    /// the orderings against the rules of `build_cmpxchg`.
+    #[llvm_versions(3.9..=latest)]
+    pub fn try_build_cmpxchg<V: BasicValue<'ctx>>(
+        &self,
+        ptr: PointerValue<'ctx>,
+        cmp: V,
+        new: V,
+        success: AtomicOrdering,
+        failure: AtomicOrdering,
+    ) -> Result<StructValue<'ctx>, BuilderError> {
+        self.check_position()?;
+        check_same_type(&cmp, &new)?;
+
+        let cmp_type = cmp.as_basic_value_enum().get_type();
+
+        if !cmp_type.is_int_type() && !cmp_type.is_pointer_type() {
+            return Err(BuilderError::InvalidAtomicOperand);
+        }
+
+        if ptr.get_type().get_element_type().to_basic_type_enum() != cmp_type {
+            return Err(BuilderError::StoreTypeMismatch);
+        }
+
+        let orderings_ok = success >= AtomicOrdering::Monotonic
+            && failure >= AtomicOrdering::Monotonic
+            && failure <= success
+            && failure != AtomicOrdering::Release
+            && failure != AtomicOrdering::AcquireRelease;
+
+        if !orderings_ok {
+            return Err(BuilderError::InvalidAtomicOrdering);
+        }
+
+        Ok(self.build_cmpxchg(ptr, cmp, new, success, failure).expect("cmpxchg operands to have been validated"))
+    }
+
+    /// Builds a conditional branch, checking that the condition is an `i1`.
+    pub fn try_build_conditional_branch(
+        &self,
+        comparison: IntValue<'ctx>,
+        then_block: BasicBlock<'ctx>,
+        else_block: BasicBlock<'ctx>,
+    ) -> Result<InstructionValue<'ctx>, BuilderError> {
+        self.check_position()?;
+
+        if comparison.get_type().get_bit_width() != 1 {
+            return Err(BuilderError::NonBooleanCondition);
+        }
+
+        Ok(self.build_conditional_branch(comparison, then_block, else_block))
+    }
+
+    /// Builds a select instruction, checking that both branches have the same type and that the
+    /// condition is an `i1`, or a vector of `i1`s as long as the selected vectors.
+    pub fn try_build_select<BV: BasicValue<'ctx>, IMV: IntMathValue<'ctx>>(&self, condition: IMV, then: BV, else_: BV, name: &str) -> Result<BasicValueEnum<'ctx>, BuilderError> {
+        self.check_position()?;
+        check_same_type(&then, &else_)?;
+
+        let (condition_len, condition_scalar) = split_vector_type(condition.as_basic_value_enum().get_type());
+        let (selected_len, _) = split_vector_type(then.as_basic_value_enum().get_type());
+        let is_bool = match condition_scalar {
+            BasicTypeEnum::IntType(int_type) => int_type.get_bit_width() == 1,
+            _ => false,
+        };
+
+        if !is_bool || (condition_len.is_some() && condition_len != selected_len) {
+            return Err(BuilderError::NonBooleanCondition);
+        }
+
+        Ok(self.build_select(condition, then, else_, name))
+    }
+
+    /// Builds an integer comparison, checking that the operands have the same type.
+    pub fn try_build_int_compare<T: IntMathValue<'ctx>>(&self, op: IntPredicate, lhs: T, rhs: T, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_same_type(&lhs, &rhs)?;
+
+        Ok(self.build_int_compare(op, lhs, rhs, name))
+    }
+
+    /// Builds a floating point comparison, checking that the operands have the same type.
+    pub fn try_build_float_compare<T: FloatMathValue<'ctx>>(
+        &self,
+        op: FloatPredicate,
+        lhs: T,
+        rhs: T,
+        name: &str,
+    ) -> Result<<<T::BaseType as FloatMathType<'ctx>>::MathConvType as IntMathType<'ctx>>::ValueType, BuilderError> {
+        self.check_position()?;
+        check_same_type(&lhs, &rhs)?;
+
+        Ok(self.build_float_compare(op, lhs, rhs, name))
+    }
+
+    /// Builds a right shift, checking that the operands have the same type.
+    pub fn try_build_right_shift<T: IntMathValue<'ctx>>(&self, lhs: T, rhs: T, sign_extend: bool, name: &str) -> Result<T, BuilderError> {
+        self.check_position()?;
+        check_same_type(&lhs, &rhs)?;
+
+        Ok(self.build_right_shift(lhs, rhs, sign_extend, name))
+    }
+
+    try_build_unary_op! {
+        'ctx;
+        try_build_int_neg => build_int_neg: IntMathValue,
+        try_build_int_nsw_neg => build_int_nsw_neg: IntMathValue,
+        try_build_int_nuw_neg => build_int_nuw_neg: IntMathValue,
+        try_build_float_neg => build_float_neg: FloatMathValue,
+        try_build_not => build_not: IntMathValue,
+    }
+
+    try_build_binary_op! {
+        'ctx;
+        try_build_int_add => build_int_add: IntMathValue,
+        try_build_int_nsw_add => build_int_nsw_add: IntMathValue,
+        try_build_int_nuw_add => build_int_nuw_add: IntMathValue,
+        try_build_int_sub => build_int_sub: IntMathValue,
+        try_build_int_nsw_sub => build_int_nsw_sub: IntMathValue,
+        try_build_int_nuw_sub => build_int_nuw_sub: IntMathValue,
+        try_build_int_mul => build_int_mul: IntMathValue,
+        try_build_int_nsw_mul => build_int_nsw_mul: IntMathValue,
+        try_build_int_nuw_mul => build_int_nuw_mul: IntMathValue,
+        try_build_int_unsigned_div => build_int_unsigned_div: IntMathValue,
+        try_build_int_signed_div => build_int_signed_div: IntMathValue,
+        try_build_int_exact_signed_div => build_int_exact_signed_div: IntMathValue,
+        try_build_int_unsigned_rem => build_int_unsigned_rem: IntMathValue,
+        try_build_int_signed_rem => build_int_signed_rem: IntMathValue,
+        try_build_and => build_and: IntMathValue,
+        try_build_or => build_or: IntMathValue,
+        try_build_xor => build_xor: IntMathValue,
+        try_build_left_shift => build_left_shift: IntMathValue,
+        try_build_float_add => build_float_add: FloatMathValue,
+        try_build_float_sub => build_float_sub: FloatMathValue,
+        try_build_float_mul => build_float_mul: FloatMathValue,
+        try_build_float_div => build_float_div: FloatMathValue,
+        try_build_float_rem => build_float_rem: FloatMathValue,
+    }
+}
+
+/// Used by try_build_call and try_build_invoke to get the callee's type without panicking.
+fn callee_fn_type<'ctx>(function: FunctionOrPointerValue<'ctx>) -> Result<FunctionType<'ctx>, BuilderError> {
+    match function {
+        Left(val) => Ok(val.get_type()),
+        Right(val) => {
+            let pointee_type = val.get_type().get_element_type();
+
+            if !pointee_type.is_function_type() {
+                return Err(BuilderError::NotAFunctionPointer);
+            }
+
+            Ok(pointee_type.into_function_type())
+        },
+    }
+}
+
+/// Checks call arguments against the parameters of the callee's type. Variadic functions
+/// may take more arguments than they have parameters.
+fn check_call_args<'ctx>(fn_type: FunctionType<'ctx>, args: &[BasicValueEnum<'ctx>]) -> Result<(), BuilderError> {
+    let param_types = fn_type.get_param_types();
+    let is_var_args = fn_type.is_var_arg();
+
+    if args.len() < param_types.len() || (!is_var_args && args.len() > param_types.len()) {
+        return Err(BuilderError::WrongArgumentCount {
+            expected: param_types.len() as u32,
+            found: args.len(),
+            is_var_args,
+        });
+    }
+
+    for (position, (arg, param_type)) in args.iter().zip(param_types.iter()).enumerate() {
+        if arg.get_type() != *param_type {
+            return Err(BuilderError::ArgumentTypeMismatch { position });
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks that the two operands of a binary instruction have the same type.
+fn check_same_type<'ctx, V: BasicValue<'ctx>>(lhs: &V, rhs: &V) -> Result<(), BuilderError> {
+    let (lhs_type, rhs_type) = unsafe {
+        (LLVMTypeOf(lhs.as_value_ref()), LLVMTypeOf(rhs.as_value_ref()))
+    };
+
+    if lhs_type != rhs_type {
+        return Err(BuilderError::OperandTypeMismatch);
+    }
+
+    Ok(())
+}
+
+/// Checks that an exception handling pad operand is an instruction of the expected kind.
+#[llvm_versions(8.0..=latest)]
+fn check_pad(pad: InstructionValue<'_>, opcode: InstructionOpcode) -> Result<(), BuilderError> {
+    if pad.get_opcode() != opcode {
+        return Err(BuilderError::InvalidPad);
+    }
+
+    Ok(())
+}
+
+/// Checks that the parent of a catchswitch or cleanuppad, if any, is itself a funclet pad.
+#[llvm_versions(8.0..=latest)]
+fn check_parent_pad(parent_pad: Option<InstructionValue<'_>>) -> Result<(), BuilderError> {
+    match parent_pad.map(|pad| pad.get_opcode()) {
+        None | Some(InstructionOpcode::CatchPad) | Some(InstructionOpcode::CleanupPad) => Ok(()),
+        Some(_) => Err(BuilderError::InvalidPad),
+    }
+}
+
+/// Gets the type of the field at `index` of a struct or array value.
+fn aggregate_field_type<'ctx>(agg: AggregateValueEnum<'ctx>, index: u32) -> Result<BasicTypeEnum<'ctx>, BuilderError> {
+    let (len, field_type) = match agg {
+        AggregateValueEnum::ArrayValue(av) => (av.get_type().len(), Some(av.get_type().get_element_type())),
+        AggregateValueEnum::StructValue(sv) => (sv.get_type().count_fields(), sv.get_type().get_field_type_at_index(index)),
+    };
+
+    match field_type {
+        Some(field_type) if index < len => Ok(field_type),
+        _ => Err(BuilderError::AggregateIndexOutOfBounds { index, len }),
+    }
+}
+
+/// Splits a type into its length, if it is a vector, and its scalar (element) type.
+fn split_vector_type(ty: BasicTypeEnum<'_>) -> (Option<u32>, BasicTypeEnum<'_>) {
+    match ty {
+        BasicTypeEnum::VectorType(vector_type) => (Some(vector_type.get_size()), vector_type.get_element_type()),
+        _ => (None, ty),
+    }
+}
+
+/// The bit width of an integer or floating point scalar type, or 0 for any other type.
+fn scalar_bit_width(ty: BasicTypeEnum<'_>) -> u32 {
+    match ty {
+        BasicTypeEnum::IntType(int_type) => int_type.get_bit_width(),
+        BasicTypeEnum::FloatType(float_type) => match unsafe { LLVMGetTypeKind(float_type.as_type_ref()) } {
+            LLVMTypeKind::LLVMFloatTypeKind => 32,
+            LLVMTypeKind::LLVMDoubleTypeKind => 64,
+            LLVMTypeKind::LLVMX86_FP80TypeKind => 80,
+            LLVMTypeKind::LLVMFP128TypeKind | LLVMTypeKind::LLVMPPC_FP128TypeKind => 128,
+            // half, as well as bfloat from LLVM 11
+            _ => 16,
+        },
+        _ => 0,
+    }
+}
+
+/// The address space of a pointer or vector of pointers type.
+fn address_space_of(ty: BasicTypeEnum<'_>) -> Option<u32> {
+    match split_vector_type(ty).1 {
+        BasicTypeEnum::PointerType(ptr_type) => Some(unsafe { LLVMGetPointerAddressSpace(ptr_type.as_type_ref()) }),
+        _ => None,
+    }
+}
+
+/// Mirrors LLVM's `CastInst::castIsValid`, which is not exposed by the C API, so that an
+/// invalid cast is reported rather than tripping an assertion inside of LLVM.
+fn check_cast(op: InstructionOpcode, from: BasicTypeEnum<'_>, to: BasicTypeEnum<'_>) -> Result<(), BuilderError> {
+    if from.is_array_type() || from.is_struct_type() || to.is_array_type() || to.is_struct_type() {
+        return Err(BuilderError::InvalidCast);
+    }
+
+    let (from_len, from_scalar) = split_vector_type(from);
+    let (to_len, to_scalar) = split_vector_type(to);
+    let (from_bits, to_bits) = (scalar_bit_width(from_scalar), scalar_bit_width(to_scalar));
+    let same_len = from_len == to_len;
+    let ints = from_scalar.is_int_type() && to_scalar.is_int_type();
+    let floats = from_scalar.is_float_type() && to_scalar.is_float_type();
+
+    let is_valid = match op {
+        InstructionOpcode::Trunc => ints && same_len && from_bits > to_bits,
+        InstructionOpcode::ZExt | InstructionOpcode::SExt => ints && same_len && from_bits < to_bits,
+        InstructionOpcode::FPTrunc => floats && same_len && from_bits > to_bits,
+        InstructionOpcode::FPExt => floats && same_len && from_bits < to_bits,
+        InstructionOpcode::UIToFP | InstructionOpcode::SIToFP => from_scalar.is_int_type() && to_scalar.is_float_type() && same_len,
+        InstructionOpcode::FPToUI | InstructionOpcode::FPToSI => from_scalar.is_float_type() && to_scalar.is_int_type() && same_len,
+        InstructionOpcode::PtrToInt => from_scalar.is_pointer_type() && to_scalar.is_int_type() && same_len,
+        InstructionOpcode::IntToPtr => from_scalar.is_int_type() && to_scalar.is_pointer_type() && same_len,
+        InstructionOpcode::BitCast => match (address_space_of(from), address_space_of(to)) {
+            // A vector of pointers may only be cast to a lone pointer if it has a single element
+            (Some(from_space), Some(to_space)) => from_space == to_space && from_len.unwrap_or(1) == to_len.unwrap_or(1),
+            (None, None) => from_bits * from_len.unwrap_or(1) == to_bits * to_len.unwrap_or(1),
+            _ => false,
+        },
+        InstructionOpcode::AddrSpaceCast => match (address_space_of(from), address_space_of(to)) {
+            (Some(from_space), Some(to_space)) => from_space != to_space && same_len,
+            _ => false,
+        },
+        _ => false,
+    };
+
+    if !is_valid {
+        return Err(BuilderError::InvalidCast);
+    }
+
+    Ok(())
+}
+
+/// Checks a cast of `op` from a value to a type.
+fn check_value_cast<'ctx, V: BasicValue<'ctx>, T: BasicType<'ctx>>(op: InstructionOpcode, value: &V, ty: &T) -> Result<(), BuilderError> {
+    check_cast(op, value.as_basic_value_enum().get_type(), ty.as_basic_type_enum())
+}
+
+/// Checks the cast LLVM's resizing cast helpers (ie `CreateIntCast`) pick between a value and a
+/// type: a bitcast between scalars of the same width, otherwise `narrowing` or `widening`.
+fn check_resizing_cast<'ctx, V: BasicValue<'ctx>, T: BasicType<'ctx>>(
+    value: &V,
+    ty: &T,
+    narrowing: InstructionOpcode,
+    widening: InstructionOpcode,
+) -> Result<(), BuilderError> {
+    let from = value.as_basic_value_enum().get_type();
+    let to = ty.as_basic_type_enum();
+    let op = match scalar_bit_width(split_vector_type(from).1).cmp(&scalar_bit_width(split_vector_type(to).1)) {
+        Ordering::Greater => narrowing,
+        Ordering::Less => widening,
+        Ordering::Equal => InstructionOpcode::BitCast,
+    };
+
+    check_cast(op, from, to)
+}
+
+/// Used by build_call and build_invoke to get the callee, validating that a `PointerValue` is a function pointer.
+fn callee_value_ref(function: FunctionOrPointerValue<'_>, builder_fn: &str) -> LLVMValueRef {
+    match function {
+        Left(val) => val.as_value_ref(),
+        Right(val) => {
+            // If using a pointer value, we must validate it's a valid function ptr
+            let value_ref = val.as_value_ref();
+            let ty_kind = unsafe { LLVMGetTypeKind(LLVMGetElementType(LLVMTypeOf(value_ref))) };
+            let is_a_fn_ptr = match ty_kind {
+                LLVMTypeKind::LLVMFunctionTypeKind => true,
+                _ => false,
+            };
+
+            // REVIEW: We should probably turn this into a Result?
+            assert!(is_a_fn_ptr, "{} called with a pointer which is not a function pointer", builder_fn);
+
+            value_ref
+        },
+    }
+}
+
+/// Used by build_call and build_invoke since LLVM gets upset when void return calls are named
+/// because they don't return anything.
+fn callee_value_name(fn_val_ref: LLVMValueRef, name: &str) -> &str {
+    unsafe {
+        match LLVMGetTypeKind(LLVMGetReturnType(LLVMGetElementType(LLVMTypeOf(fn_val_ref)))) {
+            LLVMTypeKind::LLVMVoidTypeKind => "",
+            _ => name,
+        }
+    }
+}
+
 /// Used by build_memcpy and build_memmove
 #[llvm_versions(8.0..=latest)]
 fn is_alignment_ok(align: u32) -> bool {
@@ -1,8 +1,9 @@
 //! A `Context` is an opaque owner and manager of core global data.
 
-use llvm_sys::core::{LLVMAppendBasicBlockInContext, LLVMContextCreate, LLVMContextDispose, LLVMCreateBuilderInContext, LLVMDoubleTypeInContext, LLVMFloatTypeInContext, LLVMFP128TypeInContext, LLVMInsertBasicBlockInContext, LLVMInt16TypeInContext, LLVMInt1TypeInContext, LLVMInt32TypeInContext, LLVMInt64TypeInContext, LLVMInt8TypeInContext, LLVMIntTypeInContext, LLVMModuleCreateWithNameInContext, LLVMStructCreateNamed, LLVMStructTypeInContext, LLVMVoidTypeInContext, LLVMHalfTypeInContext, LLVMGetGlobalContext, LLVMPPCFP128TypeInContext, LLVMConstStructInContext, LLVMMDNodeInContext, LLVMMDStringInContext, LLVMGetMDKindIDInContext, LLVMX86FP80TypeInContext, LLVMConstStringInContext, LLVMContextSetDiagnosticHandler};
+use llvm_sys::core::{LLVMAppendBasicBlockInContext, LLVMContextCreate, LLVMContextDispose, LLVMCreateBuilderInContext, LLVMDoubleTypeInContext, LLVMFloatTypeInContext, LLVMFP128TypeInContext, LLVMInsertBasicBlockInContext, LLVMInt16TypeInContext, LLVMInt1TypeInContext, LLVMInt32TypeInContext, LLVMInt64TypeInContext, LLVMInt8TypeInContext, LLVMIntTypeInContext, LLVMModuleCreateWithNameInContext, LLVMStructCreateNamed, LLVMStructTypeInContext, LLVMVoidTypeInContext, LLVMHalfTypeInContext, LLVMGetGlobalContext, LLVMPPCFP128TypeInContext, LLVMConstStructInContext, LLVMMDNodeInContext, LLVMMDStringInContext, LLVMGetMDKindIDInContext, LLVMX86FP80TypeInContext, LLVMConstStringInContext, LLVMContextSetDiagnosticHandler, LLVMContextSetYieldCallback};
 #[llvm_versions(3.9..=latest)]
 use llvm_sys::core::{LLVMCreateEnumAttribute, LLVMCreateStringAttribute};
+use llvm_sys::core::{LLVMGetNumOperands, LLVMGetOperand, LLVMIsAGlobalValue};
 #[llvm_versions(3.6..7.0)]
 use llvm_sys::core::{LLVMConstInlineAsm};
 #[llvm_versions(7.0..=latest)]
@@ -22,12 +23,17 @@ use crate::attributes::Attribute;
 use crate::basic_block::BasicBlock;
 use crate::builder::Builder;
 use crate::memory_buffer::MemoryBuffer;
-use crate::module::Module;
+use crate::module::{IrParseError, Module};
 use crate::support::{to_c_str, LLVMString};
+use crate::support::error_handling::{diagnostic_handler_shim, yield_callback_shim, DiagnosticHandler, DiagnosticInfo, YieldCallback};
 use crate::targets::TargetData;
 use crate::types::{BasicTypeEnum, FloatType, IntType, StructType, VoidType, AsTypeRef, FunctionType};
 use crate::values::{AsValueRef, BasicMetadataValueEnum, BasicValueEnum, FunctionValue, StructValue, MetadataValue, VectorValue, PointerValue};
 
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::convert::TryInto;
+use std::fmt::{self, Debug, Formatter};
 use std::marker::PhantomData;
 use std::mem::{forget, ManuallyDrop};
 use std::ops::Deref;
@@ -59,9 +65,10 @@ thread_local! {
 ///
 /// A `Context` is not thread safe and cannot be shared across threads. Multiple `Context`s
 /// can, however, execute on different threads simultaneously according to the LLVM docs.
-#[derive(Debug, PartialEq, Eq)]
 pub struct Context {
     pub(crate) context: LLVMContextRef,
+    diagnostic_handler: RefCell<Option<Box<DiagnosticHandler>>>,
+    yield_callback: RefCell<Option<Box<YieldCallback>>>,
 }
 
 unsafe impl Send for Context {}
@@ -72,6 +79,8 @@ impl Context {
 
         Context {
             context,
+            diagnostic_handler: RefCell::new(None),
+            yield_callback: RefCell::new(None),
         }
     }
 
@@ -197,6 +206,74 @@ impl Context {
         Err(LLVMString::new(err_str))
     }
 
+    /// Parses a type written in textual LLVM IR, such as `{ i32, [4 x i8*] }`, into a
+    /// `BasicTypeEnum` of this `Context`.
+    ///
+    /// Named struct types such as `%struct.foo` cannot be referenced, since they would have to
+    /// be defined by the snippet being parsed; spell them out as literal struct types instead.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i32_type = context.i32_type();
+    /// let struct_type = context.parse_type("{ i32, [4 x i32] }").unwrap();
+    ///
+    /// assert_eq!(struct_type, context.struct_type(&[i32_type.into(), i32_type.array_type(4).into()], false).into());
+    /// assert!(context.parse_type("{ i32, i33* ").is_err());
+    /// ```
+    pub fn parse_type(&self, ty: &str) -> Result<BasicTypeEnum, IrParseError> {
+        let module = self.parse_ir_snippet("@0 = external global", ty)?;
+        let global = module.get_first_global().expect("Snippet module always has a global");
+
+        match global.as_pointer_value().get_type().get_element_type().try_into() {
+            Ok(basic_type) => Ok(basic_type),
+            Err(()) => Err(IrParseError::with_message("type is not a basic type")),
+        }
+    }
+
+    /// Parses a constant written in textual LLVM IR along with its type, such as
+    /// `{ i32, float } { i32 1, float 2.0 }` or `i64 ptrtoint (i8* null to i64)`, into a
+    /// `BasicValueEnum` of this `Context`.
+    ///
+    /// The constant cannot refer to global values, since they would belong to the `Module` the
+    /// snippet is parsed into rather than to any `Module` of the caller.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let constant = context.parse_constant("[2 x i8] c\"hi\"").unwrap();
+    ///
+    /// assert_eq!(constant.into_array_value().get_type(), context.i8_type().array_type(2));
+    /// assert!(context.parse_constant("i32 @missing").is_err());
+    /// ```
+    pub fn parse_constant(&self, constant: &str) -> Result<BasicValueEnum, IrParseError> {
+        let module = self.parse_ir_snippet("@0 = private constant", constant)?;
+        let global = module.get_first_global().expect("Snippet module always has a global");
+        let initializer = global.get_initializer().expect("Snippet global always has an initializer");
+
+        // The snippet may span several lines and define further global values for the constant
+        // to refer to, all of which would dangle once the module is dropped.
+        if refers_to_global_value(initializer.as_value_ref()) {
+            return Err(IrParseError::with_message("constant refers to a global value"));
+        }
+
+        Ok(initializer)
+    }
+
+    // Parses a snippet of IR on its own lines after a generated header, so that errors are
+    // reported relative to the snippet.
+    fn parse_ir_snippet(&self, header: &str, snippet: &str) -> Result<Module, IrParseError> {
+        let ir = format!("{}\n{}\n", header, snippet);
+
+        Module::parse_ir_str(self, &ir, "").map_err(|error| error.offset_lines(1))
+    }
+
     /// Creates a inline asm function pointer.
     ///
     /// # Example
@@ -880,16 +957,78 @@ impl Context {
         }
     }
 
-    // LLVM 3.9+
-    // pub fn get_diagnostic_handler(&self) -> DiagnosticHandler {
-    //     let handler = unsafe {
-    //         LLVMContextGetDiagnosticHandler(self.context)
-    //     };
+    /// Sets a handler which is called with every diagnostic LLVM emits in this `Context`,
+    /// such as optimization remarks, backend warnings and inline assembly errors. While a
+    /// handler is set, LLVM no longer prints diagnostics to stderr nor exits on errors.
+    ///
+    /// A panic in the handler cannot unwind through LLVM, so it is caught and the diagnostic
+    /// being handled is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::support::error_handling::DiagnosticSeverity;
+    ///
+    /// let context = Context::create();
+    ///
+    /// context.set_diagnostic_handler(|diagnostic| {
+    ///     if diagnostic.get_severity() == DiagnosticSeverity::Warning {
+    ///         println!("warning: {}", diagnostic.get_description());
+    ///     }
+    /// });
+    /// ```
+    pub fn set_diagnostic_handler<F: FnMut(&DiagnosticInfo) + Send + 'static>(&self, handler: F) {
+        let mut handler: Box<DiagnosticHandler> = Box::new(Box::new(handler));
+        let handler_ptr = &mut *handler as *mut DiagnosticHandler as *mut c_void;
+
+        self.set_raw_diagnostic_handler(diagnostic_handler_shim, handler_ptr);
+
+        // The previous handler is only dropped once LLVM no longer refers to it
+        *self.diagnostic_handler.borrow_mut() = Some(handler);
+    }
+
+    /// Removes the handler set by `set_diagnostic_handler`, so that LLVM reports diagnostics
+    /// to stderr again.
+    pub fn clear_diagnostic_handler(&self) {
+        unsafe {
+            LLVMContextSetDiagnosticHandler(self.context, None, ptr::null_mut());
+        }
+
+        *self.diagnostic_handler.borrow_mut() = None;
+    }
+
+    /// Sets a callback which LLVM calls periodically during long running operations, such as
+    /// between functions when running a `PassManager`, to give the caller a chance to yield.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    ///
+    /// context.set_yield_callback(|| std::thread::yield_now());
+    /// ```
+    pub fn set_yield_callback<F: FnMut() + Send + 'static>(&self, callback: F) {
+        let mut callback: Box<YieldCallback> = Box::new(Box::new(callback));
+        let callback_ptr = &mut *callback as *mut YieldCallback as *mut c_void;
 
-    //     // REVIEW: Can this be null?
+        unsafe {
+            LLVMContextSetYieldCallback(self.context, Some(yield_callback_shim), callback_ptr);
+        }
 
-    //     DiagnosticHandler::new(handler)
-    // }
+        *self.yield_callback.borrow_mut() = Some(callback);
+    }
+
+    /// Removes the callback set by `set_yield_callback`.
+    pub fn clear_yield_callback(&self) {
+        unsafe {
+            LLVMContextSetYieldCallback(self.context, None, ptr::null_mut());
+        }
+
+        *self.yield_callback.borrow_mut() = None;
+    }
 
     /// Creates an enum `Attribute` in this `Context`.
     ///
@@ -955,13 +1094,56 @@ impl Context {
         VectorValue::new(ptr)
     }
 
-    pub(crate) fn set_diagnostic_handler(&self, handler: extern "C" fn (LLVMDiagnosticInfoRef, *mut c_void), void_ptr: *mut c_void) {
+    pub(crate) fn set_raw_diagnostic_handler(&self, handler: extern "C" fn (LLVMDiagnosticInfoRef, *mut c_void), void_ptr: *mut c_void) {
         unsafe {
             LLVMContextSetDiagnosticHandler(self.context, Some(handler), void_ptr)
         }
     }
 }
 
+impl Debug for Context {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Context")
+            .field("context", &self.context)
+            .field("has_diagnostic_handler", &self.diagnostic_handler.borrow().is_some())
+            .field("has_yield_callback", &self.yield_callback.borrow().is_some())
+            .finish()
+    }
+}
+
+impl PartialEq for Context {
+    fn eq(&self, other: &Context) -> bool {
+        self.context == other.context
+    }
+}
+
+impl Eq for Context {}
+
+// Determines whether a constant is, or has among its operands, a global value. Operands shared
+// between constant expressions are only visited once.
+fn refers_to_global_value(constant: LLVMValueRef) -> bool {
+    let mut pending = vec![constant];
+    let mut visited = HashSet::new();
+
+    while let Some(value) = pending.pop() {
+        if !visited.insert(value) {
+            continue;
+        }
+
+        unsafe {
+            if !LLVMIsAGlobalValue(value).is_null() {
+                return true;
+            }
+
+            for index in 0..LLVMGetNumOperands(value) {
+                pending.push(LLVMGetOperand(value, index as u32));
+            }
+        }
+    }
+
+    false
+}
+
 impl Drop for Context {
     fn drop(&mut self) {
         unsafe {
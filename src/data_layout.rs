@@ -1,7 +1,12 @@
+use std::cmp::max;
+use std::error::Error;
 use std::ffi::CStr;
 use std::fmt;
+use std::str::FromStr;
 
-use crate::support::{LLVMString, LLVMStringOrRaw};
+use crate::support::{to_c_str, LLVMString, LLVMStringOrRaw};
+use crate::targets::{ByteOrdering, TargetData};
+use crate::types::{BasicType, BasicTypeEnum, StructType};
 
 #[derive(Eq)]
 pub struct DataLayout {
@@ -25,6 +30,78 @@ impl DataLayout {
         }
     }
 
+    /// Creates a `DataLayout` from its string representation. The string is not validated
+    /// until it is used, see `DataLayout::parse`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::data_layout::DataLayout;
+    ///
+    /// let data_layout = DataLayout::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
+    ///
+    /// assert_eq!(data_layout.as_str().to_str(), Ok("e-m:e-i64:64-f80:128-n8:16:32:64-S128"));
+    /// ```
+    pub fn create(data_layout: &str) -> DataLayout {
+        let c_string = to_c_str(data_layout);
+
+        DataLayout {
+            data_layout: LLVMStringOrRaw::Owned(LLVMString::create_from_c_str(&c_string)),
+        }
+    }
+
+    /// Parses the string representation of this `DataLayout` into a `DataLayoutSpec`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::data_layout::{DataLayout, Mangling};
+    /// use inkwell::targets::ByteOrdering;
+    ///
+    /// let data_layout = DataLayout::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
+    /// let spec = data_layout.parse().unwrap();
+    ///
+    /// assert_eq!(spec.get_byte_ordering(), ByteOrdering::LittleEndian);
+    /// assert_eq!(spec.get_mangling(), Some(Mangling::ELF));
+    /// assert_eq!(spec.get_native_integer_widths(), &[8, 16, 32, 64]);
+    /// ```
+    pub fn parse(&self) -> Result<DataLayoutSpec, DataLayoutParseError> {
+        let data_layout = self.as_str().to_str().map_err(|_| DataLayoutParseError::InvalidUtf8)?;
+
+        data_layout.parse()
+    }
+
+    /// Computes the `StructLayout` of a `StructType` under this `DataLayout`, without
+    /// requiring a `TargetMachine`. The string representation is parsed first, so an invalid
+    /// one is reported as an error rather than aborting inside LLVM.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `StructType` is opaque or contains an opaque type.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::data_layout::DataLayout;
+    ///
+    /// let context = Context::create();
+    /// let struct_type = context.struct_type(&[context.i8_type().into(), context.i64_type().into()], false);
+    /// let data_layout = DataLayout::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
+    /// let struct_layout = data_layout.get_struct_layout(&struct_type).unwrap();
+    ///
+    /// assert_eq!(struct_layout.get_size(), 16);
+    /// assert_eq!(struct_layout.get_fields()[1].offset, 8);
+    /// assert_eq!(struct_layout.get_padding()[0].size, 7);
+    /// ```
+    pub fn get_struct_layout<'ctx>(&self, struct_type: &StructType<'ctx>) -> Result<StructLayout<'ctx>, DataLayoutParseError> {
+        let data_layout = self.as_str().to_str().map_err(|_| DataLayoutParseError::InvalidUtf8)?;
+
+        data_layout.parse::<DataLayoutSpec>()?;
+
+        Ok(TargetData::create(data_layout).get_struct_layout(struct_type))
+    }
+
     pub fn as_str(&self) -> &CStr {
         self.data_layout.as_str()
     }
@@ -51,3 +128,547 @@ impl fmt::Debug for DataLayout {
             .finish()
     }
 }
+
+/// Errors which can occur while parsing a `DataLayout`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DataLayoutParseError {
+    /// The data layout was not valid UTF-8.
+    InvalidUtf8,
+    /// A `-` separated component of the data layout was malformed.
+    InvalidComponent(String),
+}
+
+impl Error for DataLayoutParseError {}
+
+impl fmt::Display for DataLayoutParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataLayoutParseError::InvalidUtf8 => write!(f, "DataLayoutParseError(Data layout is not valid UTF-8)"),
+            DataLayoutParseError::InvalidComponent(component) => write!(f, "DataLayoutParseError(Invalid component {:?})", component),
+        }
+    }
+}
+
+/// How symbol names are mangled, the `m:` component of a `DataLayout`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Mangling {
+    /// `m:e`, private symbols get a `.L` prefix.
+    ELF,
+    /// `m:l`, private symbols get a `@` prefix.
+    GOFF,
+    /// `m:m`, private symbols get a `$` prefix.
+    MIPS,
+    /// `m:o`, private symbols get a `L` prefix and other symbols a `_` prefix.
+    MachO,
+    /// `m:w`, private symbols get a `.L` prefix.
+    WindowsCOFF,
+    /// `m:x`, private symbols get a `L` prefix and other symbols a `_` prefix, while
+    /// `__stdcall`, `__fastcall` and `__vectorcall` functions are suffixed with `@N`.
+    WindowsX86COFF,
+    /// `m:a`, private symbols get a `L..` prefix.
+    XCOFF,
+}
+
+impl Mangling {
+    /// Gets the prefix added to the name of every global symbol, if any.
+    pub fn get_global_prefix(self) -> Option<char> {
+        match self {
+            Mangling::MachO | Mangling::WindowsX86COFF => Some('_'),
+            _ => None,
+        }
+    }
+
+    /// Gets the prefix added to the name of private symbols.
+    pub fn get_private_prefix(self) -> &'static str {
+        match self {
+            Mangling::ELF | Mangling::WindowsCOFF => ".L",
+            Mangling::GOFF => "@",
+            Mangling::MIPS => "$",
+            Mangling::MachO | Mangling::WindowsX86COFF => "L",
+            Mangling::XCOFF => "L..",
+        }
+    }
+}
+
+/// The size and alignment of pointers in an address space, from a `p` component of a
+/// `DataLayout`. All values are in bits.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct PointerSpec {
+    /// The address space the pointers are in.
+    pub address_space: u32,
+    /// The size of a pointer.
+    pub bit_width: u32,
+    /// The ABI alignment of a pointer.
+    pub abi_alignment: u32,
+    /// The preferred alignment of a pointer.
+    pub preferred_alignment: u32,
+    /// The size of the indices used in address calculations.
+    pub index_bit_width: u32,
+}
+
+/// The alignment of an integer, vector or floating point type of a given size, from an `i`,
+/// `v` or `f` component of a `DataLayout`. All values are in bits.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct AlignSpec {
+    /// The size of the type.
+    pub bit_width: u32,
+    /// The ABI alignment of the type.
+    pub abi_alignment: u32,
+    /// The preferred alignment of the type.
+    pub preferred_alignment: u32,
+}
+
+/// How the alignment of function pointers is determined, from the `F` component of a
+/// `DataLayout`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum FunctionPtrAlignType {
+    /// `Fi`, function pointers are aligned independently of the functions they point to.
+    Independent,
+    /// `Fn`, function pointers are aligned to a multiple of the alignment of the functions
+    /// they point to.
+    MultipleOfFunctionAlign,
+}
+
+/// A parsed `DataLayout`. Anything the string representation leaves out has LLVM's default
+/// value, so this describes the layout that is actually in effect.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DataLayoutSpec {
+    byte_ordering: ByteOrdering,
+    stack_alignment: Option<u32>,
+    program_address_space: u32,
+    alloca_address_space: u32,
+    globals_address_space: u32,
+    pointers: Vec<PointerSpec>,
+    integers: Vec<AlignSpec>,
+    vectors: Vec<AlignSpec>,
+    floats: Vec<AlignSpec>,
+    aggregate_alignment: (u32, u32),
+    function_pointer_alignment: Option<(FunctionPtrAlignType, u32)>,
+    native_integer_widths: Vec<u32>,
+    non_integral_address_spaces: Vec<u32>,
+    mangling: Option<Mangling>,
+}
+
+impl DataLayoutSpec {
+    /// Gets the byte ordering, from the `e` or `E` component.
+    pub fn get_byte_ordering(&self) -> ByteOrdering {
+        self.byte_ordering
+    }
+
+    /// Gets the natural alignment of the stack in bits, from the `S` component.
+    pub fn get_stack_alignment(&self) -> Option<u32> {
+        self.stack_alignment
+    }
+
+    /// Gets the address space functions are placed in, from the `P` component.
+    pub fn get_program_address_space(&self) -> u32 {
+        self.program_address_space
+    }
+
+    /// Gets the address space of `alloca`s, from the `A` component.
+    pub fn get_alloca_address_space(&self) -> u32 {
+        self.alloca_address_space
+    }
+
+    /// Gets the default address space of global variables, from the `G` component.
+    pub fn get_globals_address_space(&self) -> u32 {
+        self.globals_address_space
+    }
+
+    /// Gets the pointer specifications of every address space that has one.
+    pub fn get_pointer_specs(&self) -> &[PointerSpec] {
+        &self.pointers
+    }
+
+    /// Gets the pointer specification of an address space. Like in LLVM, address spaces
+    /// without one use that of address space 0.
+    pub fn get_pointer_spec(&self, address_space: u32) -> PointerSpec {
+        let find = |address_space| self.pointers.iter().find(|spec| spec.address_space == address_space);
+
+        *find(address_space).or_else(|| find(0)).expect("Address space 0 always has a pointer specification")
+    }
+
+    /// Gets the alignments of the integer types with an explicit specification.
+    pub fn get_integer_specs(&self) -> &[AlignSpec] {
+        &self.integers
+    }
+
+    /// Gets the alignment of an integer type of the given width. Like in LLVM, widths without
+    /// an explicit specification use that of the next larger width, or of the largest one.
+    pub fn get_integer_alignment(&self, bit_width: u32) -> AlignSpec {
+        let larger = self.integers.iter().filter(|spec| spec.bit_width >= bit_width).min_by_key(|spec| spec.bit_width);
+        let largest = self.integers.iter().max_by_key(|spec| spec.bit_width);
+        let spec = larger.or(largest).expect("Integer specifications are never empty");
+
+        AlignSpec { bit_width, ..*spec }
+    }
+
+    /// Gets the alignments of the vector types with an explicit specification.
+    pub fn get_vector_specs(&self) -> &[AlignSpec] {
+        &self.vectors
+    }
+
+    /// Gets the alignment of a vector type of the given size. Like in LLVM, sizes without an
+    /// explicit specification are naturally aligned, rounded up to a power of two.
+    pub fn get_vector_alignment(&self, bit_width: u32) -> AlignSpec {
+        match self.vectors.iter().find(|spec| spec.bit_width == bit_width) {
+            Some(spec) => *spec,
+            None => {
+                let alignment = max(bit_width, 8).next_power_of_two();
+
+                AlignSpec { bit_width, abi_alignment: alignment, preferred_alignment: alignment }
+            },
+        }
+    }
+
+    /// Gets the alignments of the floating point types with an explicit specification.
+    pub fn get_float_specs(&self) -> &[AlignSpec] {
+        &self.floats
+    }
+
+    /// Gets the ABI and preferred alignments of aggregates in bits, from the `a` component.
+    pub fn get_aggregate_alignment(&self) -> (u32, u32) {
+        self.aggregate_alignment
+    }
+
+    /// Gets how function pointers are aligned and their alignment in bits, from the `F`
+    /// component.
+    pub fn get_function_pointer_alignment(&self) -> Option<(FunctionPtrAlignType, u32)> {
+        self.function_pointer_alignment
+    }
+
+    /// Gets the integer widths natively supported by the target's registers, from the `n`
+    /// component.
+    pub fn get_native_integer_widths(&self) -> &[u32] {
+        &self.native_integer_widths
+    }
+
+    /// Determines whether an integer of the given width is natively supported.
+    pub fn is_native_integer_width(&self, bit_width: u32) -> bool {
+        self.native_integer_widths.contains(&bit_width)
+    }
+
+    /// Gets the address spaces whose pointers have no integral representation, from the `ni`
+    /// component.
+    pub fn get_non_integral_address_spaces(&self) -> &[u32] {
+        &self.non_integral_address_spaces
+    }
+
+    /// Gets how symbol names are mangled, from the `m` component.
+    pub fn get_mangling(&self) -> Option<Mangling> {
+        self.mangling
+    }
+}
+
+impl Default for DataLayoutSpec {
+    /// The layout LLVM uses for an empty data layout string.
+    fn default() -> Self {
+        let align = |bit_width, abi_alignment, preferred_alignment| AlignSpec { bit_width, abi_alignment, preferred_alignment };
+
+        DataLayoutSpec {
+            byte_ordering: ByteOrdering::LittleEndian,
+            stack_alignment: None,
+            program_address_space: 0,
+            alloca_address_space: 0,
+            globals_address_space: 0,
+            pointers: vec![PointerSpec {
+                address_space: 0,
+                bit_width: 64,
+                abi_alignment: 64,
+                preferred_alignment: 64,
+                index_bit_width: 64,
+            }],
+            integers: vec![align(1, 8, 8), align(8, 8, 8), align(16, 16, 16), align(32, 32, 32), align(64, 32, 64)],
+            vectors: vec![align(64, 64, 64), align(128, 128, 128)],
+            floats: vec![align(16, 16, 16), align(32, 32, 32), align(64, 64, 64), align(128, 128, 128)],
+            aggregate_alignment: (0, 64),
+            function_pointer_alignment: None,
+            native_integer_widths: Vec::new(),
+            non_integral_address_spaces: Vec::new(),
+            mangling: None,
+        }
+    }
+}
+
+impl FromStr for DataLayoutSpec {
+    type Err = DataLayoutParseError;
+
+    fn from_str(data_layout: &str) -> Result<Self, Self::Err> {
+        let mut spec = DataLayoutSpec::default();
+
+        if data_layout.is_empty() {
+            return Ok(spec);
+        }
+
+        for component in data_layout.split('-') {
+            let invalid = || DataLayoutParseError::InvalidComponent(component.to_string());
+            let number = |field: Option<&str>| field.and_then(|field| field.parse::<u32>().ok()).ok_or_else(invalid);
+            // Like LLVM, alignments must be a power of two number of bytes, or zero where that means unspecified
+            let alignment = |field: Option<&str>, allow_zero: bool| -> Result<u32, DataLayoutParseError> {
+                let bits = number(field)?;
+
+                if (bits % 8 == 0 && (bits / 8).is_power_of_two()) || (allow_zero && bits == 0) {
+                    Ok(bits)
+                } else {
+                    Err(invalid())
+                }
+            };
+            let address_space = |field: Option<&str>| number(field).and_then(|address_space| match address_space {
+                0..=0xFF_FFFF => Ok(address_space),
+                _ => Err(invalid()),
+            });
+            let kind = component.chars().next().ok_or_else(invalid)?;
+            let rest = &component[kind.len_utf8()..];
+
+            match kind {
+                'e' | 'E' if rest.is_empty() => {
+                    spec.byte_ordering = if kind == 'e' { ByteOrdering::LittleEndian } else { ByteOrdering::BigEndian };
+                },
+                'S' => spec.stack_alignment = Some(alignment(Some(rest), true)?).filter(|&alignment| alignment != 0),
+                'P' => spec.program_address_space = address_space(Some(rest))?,
+                'A' => spec.alloca_address_space = address_space(Some(rest))?,
+                'G' => spec.globals_address_space = address_space(Some(rest))?,
+                'p' => {
+                    let mut fields = rest.split(':');
+                    let address_space = match fields.next() {
+                        Some("") => 0,
+                        field => address_space(field)?,
+                    };
+                    let bit_width = number(fields.next())?;
+                    let abi_alignment = alignment(fields.next(), false)?;
+                    let preferred_alignment = fields.next().map_or(Ok(abi_alignment), |field| alignment(Some(field), false))?;
+                    let index_bit_width = fields.next().map_or(Ok(bit_width), |field| number(Some(field)))?;
+                    let is_byte_width = |bits: u32| bits != 0 && bits % 8 == 0;
+
+                    if !is_byte_width(bit_width)
+                        || !is_byte_width(index_bit_width)
+                        || index_bit_width > bit_width
+                        || preferred_alignment < abi_alignment
+                        || fields.next().is_some()
+                    {
+                        return Err(invalid());
+                    }
+
+                    let pointer = PointerSpec { address_space, bit_width, abi_alignment, preferred_alignment, index_bit_width };
+
+                    match spec.pointers.iter_mut().find(|spec| spec.address_space == address_space) {
+                        Some(existing) => *existing = pointer,
+                        None => spec.pointers.push(pointer),
+                    }
+                },
+                'i' | 'v' | 'f' | 'a' => {
+                    let mut fields = rest.split(':');
+                    let bit_width = match (kind, fields.next()) {
+                        ('a', Some("")) => 0,
+                        (_, field) => number(field)?,
+                    };
+                    // Only aggregates may leave their ABI alignment unspecified
+                    let abi_alignment = alignment(fields.next(), kind == 'a')?;
+                    let preferred_alignment = fields.next().map_or(Ok(abi_alignment), |field| alignment(Some(field), true))?;
+
+                    // LLVM rejects sized aggregates such as a64:64, and requires i8 to be byte aligned
+                    if (kind == 'a' && bit_width != 0)
+                        || (kind == 'i' && bit_width == 8 && abi_alignment != 8)
+                        || bit_width >= 1 << 24
+                        || preferred_alignment < abi_alignment
+                        || fields.next().is_some()
+                    {
+                        return Err(invalid());
+                    }
+
+                    let align_spec = AlignSpec { bit_width, abi_alignment, preferred_alignment };
+                    let specs = match kind {
+                        'i' => &mut spec.integers,
+                        'v' => &mut spec.vectors,
+                        'f' => &mut spec.floats,
+                        _ => {
+                            spec.aggregate_alignment = (abi_alignment, preferred_alignment);
+
+                            continue;
+                        },
+                    };
+
+                    match specs.iter_mut().find(|spec| spec.bit_width == bit_width) {
+                        Some(existing) => *existing = align_spec,
+                        None => specs.push(align_spec),
+                    }
+                },
+                'F' => {
+                    let align_type = match rest.chars().next() {
+                        Some('i') => FunctionPtrAlignType::Independent,
+                        Some('n') => FunctionPtrAlignType::MultipleOfFunctionAlign,
+                        _ => return Err(invalid()),
+                    };
+
+                    spec.function_pointer_alignment = Some((align_type, alignment(Some(&rest[1..]), true)?));
+                },
+                'm' => {
+                    spec.mangling = Some(match rest {
+                        ":e" => Mangling::ELF,
+                        ":l" => Mangling::GOFF,
+                        ":m" => Mangling::MIPS,
+                        ":o" => Mangling::MachO,
+                        ":w" => Mangling::WindowsCOFF,
+                        ":x" => Mangling::WindowsX86COFF,
+                        ":a" => Mangling::XCOFF,
+                        _ => return Err(invalid()),
+                    });
+                },
+                'n' if rest.starts_with("i:") => {
+                    spec.non_integral_address_spaces = rest[2..].split(':').map(|field| address_space(Some(field))).collect::<Result<_, _>>()?;
+
+                    if spec.non_integral_address_spaces.contains(&0) {
+                        return Err(invalid());
+                    }
+                },
+                'n' => {
+                    spec.native_integer_widths = rest.split(':').map(|field| number(Some(field))).collect::<Result<_, _>>()?;
+
+                    if spec.native_integer_widths.contains(&0) {
+                        return Err(invalid());
+                    }
+                },
+                _ => return Err(invalid()),
+            }
+        }
+
+        Ok(spec)
+    }
+}
+
+/// The placement of a field within a `StructLayout`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct FieldLayout<'ctx> {
+    /// The index of the field within the struct.
+    pub index: u32,
+    /// The type of the field.
+    pub ty: BasicTypeEnum<'ctx>,
+    /// The offset of the field from the start of the struct, in bytes.
+    pub offset: u64,
+    /// The number of bytes the field occupies, including the tail padding of its type.
+    pub size: u64,
+    /// The ABI alignment of the field's type, in bytes.
+    pub alignment: u32,
+}
+
+/// A run of padding bytes within a `StructLayout`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Padding {
+    /// The offset of the first padding byte from the start of the struct.
+    pub offset: u64,
+    /// The number of padding bytes.
+    pub size: u64,
+}
+
+/// The complete memory layout of a `StructType` under a particular data layout: the offset
+/// of every field, the padding between and after them, and the total size and alignment.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::Context;
+/// use inkwell::data_layout::Padding;
+/// use inkwell::targets::TargetData;
+///
+/// let context = Context::create();
+/// let i8_type = context.i8_type();
+/// let i32_type = context.i32_type();
+/// let struct_type = context.struct_type(&[i8_type.into(), i32_type.into(), i8_type.into()], false);
+/// let target_data = TargetData::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
+/// let struct_layout = target_data.get_struct_layout(&struct_type);
+///
+/// assert_eq!(struct_layout.get_size(), 12);
+/// assert_eq!(struct_layout.get_alignment(), 4);
+/// assert_eq!(struct_layout.get_padding(), &[Padding { offset: 1, size: 3 }, Padding { offset: 9, size: 3 }]);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StructLayout<'ctx> {
+    struct_type: StructType<'ctx>,
+    size: u64,
+    alignment: u32,
+    fields: Vec<FieldLayout<'ctx>>,
+    padding: Vec<Padding>,
+}
+
+impl<'ctx> StructLayout<'ctx> {
+    pub(crate) fn new(target_data: &TargetData, struct_type: StructType<'ctx>) -> Self {
+        assert!(struct_type.is_sized(), "Cannot compute the layout of unsized struct {:?}", struct_type);
+
+        let size = target_data.get_abi_size(&struct_type);
+        let mut fields = Vec::new();
+        let mut padding = Vec::new();
+        let mut end = 0;
+
+        for (index, ty) in struct_type.get_field_types().into_iter().enumerate() {
+            let index = index as u32;
+            let offset = target_data.offset_of_element(&struct_type, index).expect("Field index is in bounds");
+
+            if offset > end {
+                padding.push(Padding { offset: end, size: offset - end });
+            }
+
+            let field = FieldLayout {
+                index,
+                ty,
+                offset,
+                size: target_data.get_abi_size(&ty),
+                alignment: target_data.get_abi_alignment(&ty),
+            };
+
+            end = max(end, field.offset + field.size);
+            fields.push(field);
+        }
+
+        if size > end {
+            padding.push(Padding { offset: end, size: size - end });
+        }
+
+        StructLayout {
+            struct_type,
+            size,
+            alignment: target_data.get_abi_alignment(&struct_type),
+            fields,
+            padding,
+        }
+    }
+
+    /// Gets the `StructType` this layout describes.
+    pub fn get_struct_type(&self) -> StructType<'ctx> {
+        self.struct_type
+    }
+
+    /// Gets the total size of the struct in bytes, including tail padding.
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+
+    /// Gets the ABI alignment of the struct in bytes.
+    pub fn get_alignment(&self) -> u32 {
+        self.alignment
+    }
+
+    /// Gets the layout of every field, in order.
+    pub fn get_fields(&self) -> &[FieldLayout<'ctx>] {
+        &self.fields
+    }
+
+    /// Gets the layout of the field at an index, if it exists.
+    pub fn get_field(&self, index: u32) -> Option<&FieldLayout<'ctx>> {
+        self.fields.get(index as usize)
+    }
+
+    /// Gets the field that occupies the byte at an offset, if it isn't padding.
+    pub fn get_field_at_offset(&self, offset: u64) -> Option<&FieldLayout<'ctx>> {
+        self.fields.iter().find(|field| offset >= field.offset && offset < field.offset + field.size)
+    }
+
+    /// Gets every run of padding bytes, including the tail padding, in order.
+    pub fn get_padding(&self) -> &[Padding] {
+        &self.padding
+    }
+
+    /// Gets the total number of padding bytes.
+    pub fn get_padding_size(&self) -> u64 {
+        self.padding.iter().map(|padding| padding.size).sum()
+    }
+}
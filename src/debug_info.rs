@@ -93,6 +93,19 @@
 //!
 //! ```
 //!
+//! ## Sum types
+//! DWARF variant parts (`DW_TAG_variant_part`) cannot be created, since LLVM's C API has no
+//! way to build a composite type with a discriminator or a member with a discriminant value.
+//! Sum types can instead be described as a union of one struct per variant, each starting
+//! with the discriminant as a member:
+//! ```ignore
+//! let tag = dibuilder.create_member_type(scope, "tag", file, 1, 8, 8, 0, DIFlags::PUBLIC, u8_type);
+//! let some_value = dibuilder.create_member_type(scope, "value", file, 1, 32, 32, 32, DIFlags::PUBLIC, i32_type);
+//! let some = dibuilder.create_struct_type(scope, "Some", file, 1, 64, 32, DIFlags::PUBLIC, None, &[tag.as_type(), some_value.as_type()], 0, None, "");
+//! let none = dibuilder.create_struct_type(scope, "None", file, 1, 64, 32, DIFlags::PUBLIC, None, &[tag.as_type()], 0, None, "");
+//! let option = dibuilder.create_union_type(scope, "Option", file, 1, 64, 32, DIFlags::PUBLIC, &[some.as_type(), none.as_type()], 0, "");
+//! ```
+//!
 //! ## Finalize debug info
 //! Before any kind of code generation (including verification passes; they generate code and
 //! validate debug info), do:
@@ -104,10 +117,25 @@ use crate::basic_block::BasicBlock;
 use crate::context::Context;
 pub use crate::debug_info::flags::{DIFlags, DIFlagsConstants};
 use crate::module::Module;
+use crate::AddressSpace;
 use crate::values::{AsValueRef, BasicValueEnum, InstructionValue, PointerValue, MetadataValue};
 
 #[llvm_versions(8.0..=latest)]
 use llvm_sys::debuginfo::LLVMDIBuilderCreateTypedef;
+#[llvm_versions(8.0..=latest)]
+use llvm_sys::debuginfo::LLVMDIBuilderCreateEnumerator;
+#[llvm_versions(8.0..=latest)]
+use llvm_sys::debuginfo::{
+    LLVMDIBuilderCreateImportedDeclaration, LLVMDIBuilderCreateImportedModuleFromAlias,
+    LLVMDIBuilderCreateImportedModuleFromModule, LLVMDIBuilderCreateImportedModuleFromNamespace,
+};
+#[llvm_versions(11.0..=latest)]
+use llvm_sys::debuginfo::{LLVMDIBuilderCreateMacro, LLVMDIBuilderCreateTempMacroFile};
+#[llvm_versions(9.0..=latest)]
+use llvm_sys::debuginfo::{
+    LLVMDIFileGetDirectory, LLVMDIFileGetFilename, LLVMDILocationGetInlinedAt, LLVMDIScopeGetFile,
+    LLVMDISubprogramGetLine, LLVMDIVariableGetFile, LLVMDIVariableGetLine, LLVMDIVariableGetScope,
+};
 pub use llvm_sys::debuginfo::LLVMDWARFTypeEncoding;
 use llvm_sys::debuginfo::LLVMDebugMetadataVersion;
 use llvm_sys::debuginfo::LLVMDisposeDIBuilder;
@@ -115,11 +143,17 @@ use llvm_sys::debuginfo::LLVMMetadataReplaceAllUsesWith;
 use llvm_sys::debuginfo::LLVMTemporaryMDNode;
 use llvm_sys::debuginfo::{LLVMCreateDIBuilder, LLVMCreateDIBuilderDisallowUnresolved};
 use llvm_sys::debuginfo::{
-    LLVMDIBuilderCreateAutoVariable, LLVMDIBuilderCreateBasicType, LLVMDIBuilderCreateCompileUnit,
-    LLVMDIBuilderCreateDebugLocation, LLVMDIBuilderCreateExpression, LLVMDIBuilderCreateFile,
-    LLVMDIBuilderCreateFunction, LLVMDIBuilderCreateLexicalBlock, LLVMDIBuilderCreateMemberType,
-    LLVMDIBuilderCreateNameSpace, LLVMDIBuilderCreateParameterVariable,
-    LLVMDIBuilderCreateStructType, LLVMDIBuilderCreateSubroutineType, LLVMDIBuilderCreateUnionType,
+    LLVMDIBuilderCreateArrayType, LLVMDIBuilderCreateAutoVariable, LLVMDIBuilderCreateBasicType,
+    LLVMDIBuilderCreateBitFieldMemberType, LLVMDIBuilderCreateCompileUnit,
+    LLVMDIBuilderCreateDebugLocation, LLVMDIBuilderCreateEnumerationType,
+    LLVMDIBuilderCreateExpression, LLVMDIBuilderCreateFile, LLVMDIBuilderCreateForwardDecl,
+    LLVMDIBuilderCreateFunction, LLVMDIBuilderCreateInheritance, LLVMDIBuilderCreateLexicalBlock,
+    LLVMDIBuilderCreateMemberType, LLVMDIBuilderCreateNameSpace,
+    LLVMDIBuilderCreateParameterVariable, LLVMDIBuilderCreatePointerType,
+    LLVMDIBuilderCreateQualifiedType, LLVMDIBuilderCreateReferenceType,
+    LLVMDIBuilderCreateReplaceableCompositeType, LLVMDIBuilderCreateStructType,
+    LLVMDIBuilderCreateSubroutineType, LLVMDIBuilderCreateUnionType,
+    LLVMDIBuilderCreateVectorType, LLVMDIBuilderGetOrCreateSubrange,
     LLVMDIBuilderFinalize, LLVMDIBuilderInsertDbgValueBefore, LLVMDIBuilderInsertDeclareAtEnd,
     LLVMDIBuilderInsertDeclareBefore, LLVMDILocationGetColumn, LLVMDILocationGetLine,
     LLVMDILocationGetScope, LLVMDITypeGetAlignInBits, LLVMDITypeGetOffsetInBits,
@@ -129,6 +163,10 @@ use llvm_sys::debuginfo::{
 use llvm_sys::debuginfo::{LLVMDIBuilderCreateGlobalVariableExpression,LLVMDIBuilderCreateConstantValueExpression};
 use llvm_sys::prelude::{LLVMDIBuilderRef, LLVMMetadataRef};
 use llvm_sys::core::LLVMMetadataAsValue;
+#[llvm_versions(9.0..=latest)]
+use llvm_sys::core::{LLVMGetMDNodeNumOperands, LLVMGetMDNodeOperands, LLVMGetMDString};
+#[llvm_versions(9.0..=latest)]
+use std::ffi::CStr;
 use std::convert::TryInto;
 use std::marker::PhantomData;
 
@@ -155,6 +193,14 @@ pub struct DIScope<'ctx> {
     _marker: PhantomData<&'ctx Context>,
 }
 
+impl<'ctx> DIScope<'ctx> {
+    /// Gets the file this scope is in, if any.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_file(&self) -> Option<DIFile<'ctx>> {
+        get_scope_file(self.metadata_ref)
+    }
+}
+
 /// Specific scopes (i.e. `DILexicalBlock`) can be turned into a `DIScope` with the
 /// `AsDIScope::as_debug_info_scope` trait method.
 pub trait AsDIScope<'ctx> {
@@ -637,6 +683,312 @@ impl<'ctx> DebugInfoBuilder<'ctx> {
         }
     }
 
+    /// Create a pointer type pointing to `pointee`.
+    pub fn create_pointer_type(
+        &self,
+        name: &str,
+        pointee: DIType<'ctx>,
+        size_in_bits: u64,
+        align_in_bits: u32,
+        address_space: AddressSpace,
+    ) -> DIDerivedType<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreatePointerType(
+                self.builder,
+                pointee.metadata_ref,
+                size_in_bits,
+                align_in_bits,
+                address_space as libc::c_uint,
+                name.as_ptr() as _,
+                name.len(),
+            )
+        };
+        DIDerivedType {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a reference type to `ty`. `tag` is a `DW_TAG_*` constant, either
+    /// `DW_TAG_reference_type` (0x10) or `DW_TAG_rvalue_reference_type` (0x42).
+    pub fn create_reference_type(&self, ty: DIType<'ctx>, tag: u32) -> DIDerivedType<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateReferenceType(self.builder, tag, ty.metadata_ref)
+        };
+        DIDerivedType {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a qualified version of `ty`. `tag` is a `DW_TAG_*` constant such as
+    /// `DW_TAG_const_type` (0x26) or `DW_TAG_volatile_type` (0x35).
+    pub fn create_qualified_type(&self, ty: DIType<'ctx>, tag: u32) -> DIDerivedType<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateQualifiedType(self.builder, tag, ty.metadata_ref)
+        };
+        DIDerivedType {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a subrange of `count` elements starting at `lower_bound`, describing one
+    /// dimension of an array or vector type.
+    pub fn get_or_create_subrange(&self, lower_bound: i64, count: i64) -> DISubrange<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderGetOrCreateSubrange(self.builder, lower_bound, count)
+        };
+        DISubrange {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create an array type of `element_type` with one subrange per dimension.
+    pub fn create_array_type(
+        &self,
+        element_type: DIType<'ctx>,
+        size_in_bits: u64,
+        align_in_bits: u32,
+        subscripts: &[DISubrange<'ctx>],
+    ) -> DICompositeType<'ctx> {
+        let mut subscripts: Vec<LLVMMetadataRef> =
+            subscripts.iter().map(|range| range.metadata_ref).collect();
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateArrayType(
+                self.builder,
+                size_in_bits,
+                align_in_bits,
+                element_type.metadata_ref,
+                subscripts.as_mut_ptr(),
+                subscripts.len().try_into().unwrap(),
+            )
+        };
+        DICompositeType {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a vector type of `element_type` with one subrange per dimension.
+    pub fn create_vector_type(
+        &self,
+        element_type: DIType<'ctx>,
+        size_in_bits: u64,
+        align_in_bits: u32,
+        subscripts: &[DISubrange<'ctx>],
+    ) -> DICompositeType<'ctx> {
+        let mut subscripts: Vec<LLVMMetadataRef> =
+            subscripts.iter().map(|range| range.metadata_ref).collect();
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateVectorType(
+                self.builder,
+                size_in_bits,
+                align_in_bits,
+                element_type.metadata_ref,
+                subscripts.as_mut_ptr(),
+                subscripts.len().try_into().unwrap(),
+            )
+        };
+        DICompositeType {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a single enumerator of an enumeration type.
+    #[llvm_versions(8.0..=latest)]
+    pub fn create_enumerator(&self, name: &str, value: i64, is_unsigned: bool) -> DIEnumerator<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateEnumerator(
+                self.builder,
+                name.as_ptr() as _,
+                name.len(),
+                value,
+                is_unsigned as _,
+            )
+        };
+        DIEnumerator {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create an enumeration type with the given enumerators. `underlying_type` is the
+    /// integer type the enumeration is stored as, if any.
+    #[llvm_versions(8.0..=latest)]
+    pub fn create_enumeration_type(
+        &self,
+        scope: DIScope<'ctx>,
+        name: &str,
+        file: DIFile<'ctx>,
+        line_no: u32,
+        size_in_bits: u64,
+        align_in_bits: u32,
+        elements: &[DIEnumerator<'ctx>],
+        underlying_type: Option<DIType<'ctx>>,
+    ) -> DICompositeType<'ctx> {
+        let mut elements: Vec<LLVMMetadataRef> =
+            elements.iter().map(|enumerator| enumerator.metadata_ref).collect();
+        let underlying_type = underlying_type.map_or(std::ptr::null_mut(), |dt| dt.metadata_ref);
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateEnumerationType(
+                self.builder,
+                scope.metadata_ref,
+                name.as_ptr() as _,
+                name.len(),
+                file.metadata_ref,
+                line_no,
+                size_in_bits,
+                align_in_bits,
+                elements.as_mut_ptr(),
+                elements.len().try_into().unwrap(),
+                underlying_type,
+            )
+        };
+        DICompositeType {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a type for a bit field member. `offset_in_bits` is the offset of the bit field
+    /// itself and `storage_offset_in_bits` the offset of the storage unit containing it.
+    pub fn create_bit_field_member_type(
+        &self,
+        scope: DIScope<'ctx>,
+        name: &str,
+        file: DIFile<'ctx>,
+        line_no: u32,
+        size_in_bits: u64,
+        offset_in_bits: u64,
+        storage_offset_in_bits: u64,
+        flags: DIFlags,
+        ty: DIType<'ctx>,
+    ) -> DIDerivedType<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateBitFieldMemberType(
+                self.builder,
+                scope.metadata_ref,
+                name.as_ptr() as _,
+                name.len(),
+                file.metadata_ref,
+                line_no,
+                size_in_bits,
+                offset_in_bits,
+                storage_offset_in_bits,
+                flags,
+                ty.metadata_ref,
+            )
+        };
+        DIDerivedType {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create an inheritance relationship, making `base_type` a base of `ty` at
+    /// `base_offset` bits.
+    pub fn create_inheritance(
+        &self,
+        ty: DIType<'ctx>,
+        base_type: DIType<'ctx>,
+        base_offset: u64,
+        vbptr_offset: u32,
+        flags: DIFlags,
+    ) -> DIDerivedType<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateInheritance(
+                self.builder,
+                ty.metadata_ref,
+                base_type.metadata_ref,
+                base_offset,
+                vbptr_offset,
+                flags,
+            )
+        };
+        DIDerivedType {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a forward declaration of a composite type whose definition is not emitted.
+    /// `tag` is a `DW_TAG_*` constant such as `DW_TAG_structure_type` (0x13).
+    pub fn create_forward_decl(
+        &self,
+        tag: u32,
+        name: &str,
+        scope: DIScope<'ctx>,
+        file: DIFile<'ctx>,
+        line_no: u32,
+        runtime_language: u32,
+        size_in_bits: u64,
+        align_in_bits: u32,
+        unique_id: &str,
+    ) -> DICompositeType<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateForwardDecl(
+                self.builder,
+                tag,
+                name.as_ptr() as _,
+                name.len(),
+                scope.metadata_ref,
+                file.metadata_ref,
+                line_no,
+                runtime_language,
+                size_in_bits,
+                align_in_bits,
+                unique_id.as_ptr() as _,
+                unique_id.len(),
+            )
+        };
+        DICompositeType {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a temporary composite type, which can be referred to before its members are
+    /// known and must then be replaced by `replace_composite_type`. `tag` is a `DW_TAG_*`
+    /// constant such as `DW_TAG_structure_type` (0x13).
+    pub fn create_replaceable_composite_type(
+        &self,
+        tag: u32,
+        name: &str,
+        scope: DIScope<'ctx>,
+        file: DIFile<'ctx>,
+        line_no: u32,
+        runtime_language: u32,
+        size_in_bits: u64,
+        align_in_bits: u32,
+        flags: DIFlags,
+        unique_id: &str,
+    ) -> DICompositeType<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateReplaceableCompositeType(
+                self.builder,
+                tag,
+                name.as_ptr() as _,
+                name.len(),
+                scope.metadata_ref,
+                file.metadata_ref,
+                line_no,
+                runtime_language,
+                size_in_bits,
+                align_in_bits,
+                flags,
+                unique_id.as_ptr() as _,
+                unique_id.len(),
+            )
+        };
+        DICompositeType {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
     #[llvm_versions(8.0..=latest)]
     pub fn create_global_variable_expression(
         &self,
@@ -779,6 +1131,161 @@ impl<'ctx> DebugInfoBuilder<'ctx> {
         }
     }
 
+    /// Create a descriptor for importing all declarations of a namespace into `scope`, like
+    /// C++'s `using namespace`.
+    #[llvm_versions(8.0..=latest)]
+    pub fn create_imported_module_from_namespace(
+        &self,
+        scope: DIScope<'ctx>,
+        namespace: DINamespace<'ctx>,
+        file: DIFile<'ctx>,
+        line_no: u32,
+    ) -> DIImportedEntity<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateImportedModuleFromNamespace(
+                self.builder,
+                scope.metadata_ref,
+                namespace.metadata_ref,
+                file.metadata_ref,
+                line_no,
+            )
+        };
+        DIImportedEntity {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a descriptor for importing a module through another, aliased import.
+    #[llvm_versions(8.0..=latest)]
+    pub fn create_imported_module_from_alias(
+        &self,
+        scope: DIScope<'ctx>,
+        imported_entity: DIImportedEntity<'ctx>,
+        file: DIFile<'ctx>,
+        line_no: u32,
+    ) -> DIImportedEntity<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateImportedModuleFromAlias(
+                self.builder,
+                scope.metadata_ref,
+                imported_entity.metadata_ref,
+                file.metadata_ref,
+                line_no,
+            )
+        };
+        DIImportedEntity {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a descriptor for importing a module (a `DW_TAG_module` scope) into `scope`.
+    #[llvm_versions(8.0..=latest)]
+    pub fn create_imported_module_from_module(
+        &self,
+        scope: DIScope<'ctx>,
+        module: DIScope<'ctx>,
+        file: DIFile<'ctx>,
+        line_no: u32,
+    ) -> DIImportedEntity<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateImportedModuleFromModule(
+                self.builder,
+                scope.metadata_ref,
+                module.metadata_ref,
+                file.metadata_ref,
+                line_no,
+            )
+        };
+        DIImportedEntity {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a descriptor for importing a single declaration, such as a function or type,
+    /// into `scope` under `name`, like C++'s `using foo::bar`.
+    #[llvm_versions(8.0..=latest)]
+    pub fn create_imported_declaration(
+        &self,
+        scope: DIScope<'ctx>,
+        declaration: DIScope<'ctx>,
+        file: DIFile<'ctx>,
+        line_no: u32,
+        name: &str,
+    ) -> DIImportedEntity<'ctx> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateImportedDeclaration(
+                self.builder,
+                scope.metadata_ref,
+                declaration.metadata_ref,
+                file.metadata_ref,
+                line_no,
+                name.as_ptr() as _,
+                name.len(),
+            )
+        };
+        DIImportedEntity {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a macro definition or undefinition. Macros without a parent macro file
+    /// belong to the compile unit.
+    #[llvm_versions(11.0..=latest)]
+    pub fn create_macro(
+        &self,
+        parent_macro_file: Option<DIMacroFile<'ctx>>,
+        line_no: u32,
+        record_type: DWARFMacinfoRecordType,
+        name: &str,
+        value: &str,
+    ) -> DIMacro<'ctx> {
+        let parent_macro_file = parent_macro_file.map_or(std::ptr::null_mut(), |file| file.metadata_ref);
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateMacro(
+                self.builder,
+                parent_macro_file,
+                line_no,
+                record_type.into(),
+                name.as_ptr() as _,
+                name.len(),
+                value.as_ptr() as _,
+                value.len(),
+            )
+        };
+        DIMacro {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Create a macro file, recording that `file` was included at `line_no`. The macros
+    /// created with it as their parent are collected into it by `finalize`.
+    #[llvm_versions(11.0..=latest)]
+    pub fn create_temp_macro_file(
+        &self,
+        parent_macro_file: Option<DIMacroFile<'ctx>>,
+        line_no: u32,
+        file: DIFile<'ctx>,
+    ) -> DIMacroFile<'ctx> {
+        let parent_macro_file = parent_macro_file.map_or(std::ptr::null_mut(), |file| file.metadata_ref);
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateTempMacroFile(
+                self.builder,
+                parent_macro_file,
+                line_no,
+                file.metadata_ref,
+            )
+        };
+        DIMacroFile {
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
     /// Insert a variable declaration (`llvm.dbg.declare`) before a specified instruction.
     pub fn insert_declare_before_instruction(
         &self,
@@ -888,6 +1395,20 @@ impl<'ctx> DebugInfoBuilder<'ctx> {
         LLVMMetadataReplaceAllUsesWith(placeholder.metadata_ref, other.metadata_ref);
     }
 
+    /// Deletes a type created by `create_replaceable_composite_type`, replacing all uses of
+    /// it with its complete definition.
+    ///
+    /// # Safety:
+    /// This and any other copies of the replaceable type made by Copy or Clone
+    /// become dangling pointers after calling this method.
+    pub unsafe fn replace_composite_type(
+        &self,
+        replaceable: DICompositeType<'ctx>,
+        other: DICompositeType<'ctx>,
+    ) {
+        LLVMMetadataReplaceAllUsesWith(replaceable.metadata_ref, other.metadata_ref);
+    }
+
     /// Construct any deferred debug info descriptors. May generate invalid metadata if debug info
     /// is incomplete. Module/function verification can then fail.
     ///
@@ -911,6 +1432,24 @@ pub struct DIFile<'ctx> {
     _marker: PhantomData<&'ctx Context>,
 }
 
+impl<'ctx> DIFile<'ctx> {
+    /// Gets the name of this file.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_filename(&self) -> &CStr {
+        let mut len = 0;
+
+        unsafe { CStr::from_ptr(LLVMDIFileGetFilename(self.metadata_ref, &mut len)) }
+    }
+
+    /// Gets the directory of this file.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_directory(&self) -> &CStr {
+        let mut len = 0;
+
+        unsafe { CStr::from_ptr(LLVMDIFileGetDirectory(self.metadata_ref, &mut len)) }
+    }
+}
+
 impl<'ctx> AsDIScope<'ctx> for DIFile<'ctx> {
     fn as_debug_info_scope(self) -> DIScope<'ctx> {
         DIScope {
@@ -929,6 +1468,17 @@ pub struct DICompileUnit<'ctx> {
 }
 
 impl<'ctx> DICompileUnit<'ctx> {
+    #[llvm_versions(9.0..=latest)]
+    pub(crate) fn new(metadata_ref: LLVMMetadataRef) -> Self {
+        let file = get_scope_file(metadata_ref).expect("compile units always have a file");
+
+        DICompileUnit {
+            file,
+            metadata_ref,
+            _marker: PhantomData,
+        }
+    }
+
     pub fn get_file(&self) -> DIFile<'ctx> {
         self.file
     }
@@ -966,6 +1516,35 @@ pub struct DISubprogram<'ctx> {
     pub(crate) _marker: PhantomData<&'ctx Context>,
 }
 
+impl<'ctx> DISubprogram<'ctx> {
+    /// Gets the source name of this subprogram. The name is read from the subprogram's
+    /// operands, which requires the `Context` it was created in.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_name(&self, context: &Context) -> Option<&CStr> {
+        // Operands of a DISubprogram are its file, scope, name, linkage name, ...
+        get_string_operand(context, self.metadata_ref, 2)
+    }
+
+    /// Gets the linkage (mangled) name of this subprogram, if it has one distinct from its
+    /// name.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_linkage_name(&self, context: &Context) -> Option<&CStr> {
+        get_string_operand(context, self.metadata_ref, 3)
+    }
+
+    /// Gets the file this subprogram is defined in.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_file(&self) -> Option<DIFile<'ctx>> {
+        get_scope_file(self.metadata_ref)
+    }
+
+    /// Gets the line this subprogram is defined on.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_line(&self) -> u32 {
+        unsafe { LLVMDISubprogramGetLine(self.metadata_ref) }
+    }
+}
+
 impl<'ctx> AsDIScope<'ctx> for DISubprogram<'ctx> {
     fn as_debug_info_scope(self) -> DIScope<'ctx> {
         DIScope {
@@ -1079,6 +1658,20 @@ impl<'ctx> AsDIScope<'ctx> for DICompositeType<'ctx> {
     }
 }
 
+/// One dimension of an array or vector type, created by `get_or_create_subrange`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DISubrange<'ctx> {
+    pub(crate) metadata_ref: LLVMMetadataRef,
+    _marker: PhantomData<&'ctx Context>,
+}
+
+/// A named value of an enumeration type, created by `create_enumerator`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DIEnumerator<'ctx> {
+    pub(crate) metadata_ref: LLVMMetadataRef,
+    _marker: PhantomData<&'ctx Context>,
+}
+
 /// Metadata representing the type of a function
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct DISubroutineType<'ctx> {
@@ -1131,13 +1724,90 @@ impl<'ctx> DILocation<'ctx> {
             _marker: PhantomData,
         }
     }
+
+    /// Gets the location this location was inlined at, if it was inlined.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_inlined_at(&self) -> Option<DILocation<'ctx>> {
+        let metadata_ref = unsafe { LLVMDILocationGetInlinedAt(self.metadata_ref) };
+
+        if metadata_ref.is_null() {
+            return None;
+        }
+
+        Some(DILocation {
+            metadata_ref,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// An imported module or declaration, created by the `create_imported_*` methods of
+/// `DebugInfoBuilder`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DIImportedEntity<'ctx> {
+    pub(crate) metadata_ref: LLVMMetadataRef,
+    _marker: PhantomData<&'ctx Context>,
+}
+
+/// A macro definition or undefinition, created by `create_macro`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DIMacro<'ctx> {
+    pub(crate) metadata_ref: LLVMMetadataRef,
+    _marker: PhantomData<&'ctx Context>,
+}
+
+/// The macros of an included file, created by `create_temp_macro_file`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DIMacroFile<'ctx> {
+    pub(crate) metadata_ref: LLVMMetadataRef,
+    _marker: PhantomData<&'ctx Context>,
 }
 
 /// Metadata representing a variable inside a scope
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct DILocalVariable<'ctx> {
     pub(crate) metadata_ref: LLVMMetadataRef,
-    _marker: PhantomData<&'ctx Context>,
+    pub(crate) _marker: PhantomData<&'ctx Context>,
+}
+
+impl<'ctx> DILocalVariable<'ctx> {
+    /// Gets the source name of this variable. The name is read from the variable's
+    /// operands, which requires the `Context` it was created in.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_name(&self, context: &Context) -> Option<&CStr> {
+        // Operands of a DILocalVariable are its scope, name, file, type, ...
+        get_string_operand(context, self.metadata_ref, 1)
+    }
+
+    /// Gets the scope this variable is declared in.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_scope(&self) -> DIScope<'ctx> {
+        DIScope {
+            metadata_ref: unsafe { LLVMDIVariableGetScope(self.metadata_ref) },
+            _marker: PhantomData,
+        }
+    }
+
+    /// Gets the file this variable is declared in.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_file(&self) -> Option<DIFile<'ctx>> {
+        let metadata_ref = unsafe { LLVMDIVariableGetFile(self.metadata_ref) };
+
+        if metadata_ref.is_null() {
+            return None;
+        }
+
+        Some(DIFile {
+            metadata_ref,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Gets the line this variable is declared on.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_line(&self) -> u32 {
+        unsafe { LLVMDIVariableGetLine(self.metadata_ref) }
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -1162,9 +1832,55 @@ pub struct DIExpression<'ctx> {
     _marker: PhantomData<&'ctx Context>,
 }
 
+#[llvm_versions(9.0..=latest)]
+fn get_scope_file<'ctx>(scope: LLVMMetadataRef) -> Option<DIFile<'ctx>> {
+    let metadata_ref = unsafe { LLVMDIScopeGetFile(scope) };
+
+    if metadata_ref.is_null() {
+        return None;
+    }
+
+    Some(DIFile {
+        metadata_ref,
+        _marker: PhantomData,
+    })
+}
+
+// The C API has no getters for the names of most debug info nodes, but they can still be
+// read through the generic node operands of their metadata value.
+#[llvm_versions(9.0..=latest)]
+fn get_string_operand<'a>(context: &Context, metadata_ref: LLVMMetadataRef, index: usize) -> Option<&'a CStr> {
+    unsafe {
+        let node = LLVMMetadataAsValue(context.context, metadata_ref);
+        let count = LLVMGetMDNodeNumOperands(node) as usize;
+        let mut operands = Vec::with_capacity(count);
+
+        LLVMGetMDNodeOperands(node, operands.as_mut_ptr());
+        operands.set_len(count);
+
+        let operand = *operands.get(index)?;
+
+        if operand.is_null() {
+            return None;
+        }
+
+        let mut len = 0;
+        let string = LLVMGetMDString(operand, &mut len);
+
+        if string.is_null() {
+            return None;
+        }
+
+        // MDStrings are always stored null terminated
+        Some(CStr::from_ptr(string))
+    }
+}
+
 pub use flags::*;
 mod flags {
     use llvm_sys::debuginfo::{LLVMDWARFEmissionKind, LLVMDWARFSourceLanguage};
+    #[llvm_versions(11.0..=latest)]
+    use llvm_sys::debuginfo::LLVMDWARFMacinfoRecordType;
     pub use llvm_sys::debuginfo::LLVMDIFlags as DIFlags;
 
     pub trait DIFlagsConstants {
@@ -1274,6 +1990,23 @@ mod flags {
         LineTablesOnly,
     }
 
+    /// The kinds of macro information records. Corresponds to `LLVMDWARFMacinfoRecordType` enum from LLVM.
+    #[llvm_versions(11.0..=latest)]
+    #[llvm_enum(LLVMDWARFMacinfoRecordType)]
+    #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+    pub enum DWARFMacinfoRecordType {
+        #[llvm_variant(LLVMDWARFMacinfoRecordTypeDefine)]
+        Define,
+        #[llvm_variant(LLVMDWARFMacinfoRecordTypeMacro)]
+        Undef,
+        #[llvm_variant(LLVMDWARFMacinfoRecordTypeStartFile)]
+        StartFile,
+        #[llvm_variant(LLVMDWARFMacinfoRecordTypeEndFile)]
+        EndFile,
+        #[llvm_variant(LLVMDWARFMacinfoRecordTypeVendorExt)]
+        VendorExt,
+    }
+
     /// Source languages known by DWARF. Corresponds to `LLVMDWARFSourceLanguage` enum from LLVM.
     #[llvm_enum(LLVMDWARFSourceLanguage)]
     #[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
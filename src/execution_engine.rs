@@ -1,4 +1,8 @@
 use libc::c_int;
+#[llvm_versions(8.0..=latest)]
+use llvm_sys::execution_engine::{LLVMCreateGDBRegistrationListener, LLVMCreatePerfJITEventListener};
+#[llvm_versions(8.0..=latest)]
+use llvm_sys::prelude::LLVMJITEventListenerRef;
 use llvm_sys::execution_engine::{LLVMGetExecutionEngineTargetData, LLVMExecutionEngineRef, LLVMRunFunction, LLVMRunFunctionAsMain, LLVMDisposeExecutionEngine, LLVMGetFunctionAddress, LLVMAddModule, LLVMFindFunction, LLVMLinkInMCJIT, LLVMLinkInInterpreter, LLVMRemoveModule, LLVMGenericValueRef, LLVMFreeMachineCodeForFunction, LLVMAddGlobalMapping, LLVMRunStaticConstructors, LLVMRunStaticDestructors};
 
 use crate::context::Context;
@@ -312,7 +316,7 @@ impl<'ctx> ExecutionEngine<'ctx> {
         let execution_engine = self.execution_engine.as_ref().expect(EE_INNER_PANIC);
 
         Ok(JitFunction {
-            _execution_engine: execution_engine.clone(),
+            _owner: JitFunctionOwner::ExecutionEngine(execution_engine.clone()),
             inner: transmute_copy(&address),
         })
     }
@@ -467,11 +471,66 @@ impl Deref for ExecEngineInner<'_> {
     }
 }
 
+/// A listener which LLVM notifies whenever a JIT emits or frees an object, used to make
+/// JIT compiled code visible to debuggers and profilers.
+///
+/// Listeners can be registered with an `experimental::Orc` JIT. The C API offers no way to
+/// register them with an MCJIT `ExecutionEngine`, however MCJIT always registers the GDB
+/// listener itself, so its code is already visible to debuggers supporting the GDB JIT interface.
+///
+/// LLVM owns every listener for the lifetime of the process, so they never need to be freed.
+#[llvm_versions(8.0..=latest)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JITEventListener {
+    pub(crate) listener: LLVMJITEventListenerRef,
+}
+
+#[llvm_versions(8.0..=latest)]
+impl JITEventListener {
+    /// Gets the listener which registers emitted objects with debuggers through the GDB JIT
+    /// interface (`__jit_debug_register_code`), which both gdb and lldb understand.
+    pub fn create_gdb_registration_listener() -> Self {
+        let listener = unsafe {
+            LLVMCreateGDBRegistrationListener()
+        };
+
+        assert!(!listener.is_null());
+
+        JITEventListener { listener }
+    }
+
+    /// Gets the listener which writes a `jit-<pid>.dump` file for `perf inject --jit`.
+    ///
+    /// Returns `None` if LLVM was built without perf support (`LLVM_USE_PERF`).
+    pub fn create_perf_listener() -> Option<Self> {
+        let listener = unsafe {
+            LLVMCreatePerfJITEventListener()
+        };
+
+        if listener.is_null() {
+            return None;
+        }
+
+        Some(JITEventListener { listener })
+    }
+}
+
+/// Keeps whichever JIT a `JitFunction` was looked up in alive.
+// The fields are only held to keep the JIT alive, never read
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+enum JitFunctionOwner<'ctx> {
+    ExecutionEngine(ExecEngineInner<'ctx>),
+    #[cfg(all(feature = "experimental", not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9",
+                                                feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0", feature = "llvm7-0"))))]
+    Orc(Rc<experimental::OrcInner<'ctx>>),
+}
+
 /// A wrapper around a function pointer which ensures the function being pointed
 /// to doesn't accidentally outlive its execution engine.
 #[derive(Clone)]
 pub struct JitFunction<'ctx, F> {
-    _execution_engine: ExecEngineInner<'ctx>,
+    _owner: JitFunctionOwner<'ctx>,
     inner: F,
 }
 
@@ -523,18 +582,31 @@ macro_rules! impl_unsafe_fn {
 
 impl_unsafe_fn!(A, B, C, D, E, F, G, H, I, J, K, L, M);
 
-#[cfg(all(feature = "experimental", not(any(feature = "llvm3-6", feature = "llvm3-7"))))]
+// The ORC C bindings only report errors through LLVMErrorRef from LLVM 8 onwards.
+#[cfg(all(feature = "experimental", not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9",
+                                            feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0", feature = "llvm7-0"))))]
 pub mod experimental {
-    use llvm_sys::error::{LLVMErrorRef, LLVMGetErrorTypeId, LLVMConsumeError, LLVMGetErrorMessage, LLVMErrorTypeId};
-    use llvm_sys::orc::{LLVMOrcCreateInstance, LLVMOrcDisposeInstance, LLVMOrcJITStackRef, LLVMOrcAddEagerlyCompiledIR, LLVMOrcAddLazilyCompiledIR, LLVMOrcGetErrorMsg, LLVMOrcGetMangledSymbol, LLVMOrcDisposeMangledSymbol};
+    use libc::{c_char, c_void};
+    use llvm_sys::error::{LLVMErrorRef, LLVMGetErrorMessage, LLVMDisposeErrorMessage};
+    use llvm_sys::orc::{LLVMOrcRegisterJITEventListener, LLVMOrcUnregisterJITEventListener, LLVMOrcCreateInstance, LLVMOrcDisposeInstance, LLVMOrcJITStackRef, LLVMOrcAddEagerlyCompiledIR, LLVMOrcAddLazilyCompiledIR, LLVMOrcGetErrorMsg, LLVMOrcGetMangledSymbol, LLVMOrcDisposeMangledSymbol, LLVMOrcModuleHandle, LLVMOrcRemoveModule, LLVMOrcGetSymbolAddress, LLVMOrcTargetAddress};
+    use llvm_sys::support::{LLVMLoadLibraryPermanently, LLVMSearchForAddressOfSymbol};
 
+    use crate::context::Context;
+    use crate::execution_engine::{FunctionLookupError, JITEventListener, JitFunction, JitFunctionOwner, UnsafeFunctionPointer};
     use crate::module::Module;
     use crate::support::to_c_str;
     use crate::targets::TargetMachine;
 
-    use std::mem::MaybeUninit;
+    use std::cell::Cell;
+    use std::error::Error;
+    use std::fmt::{self, Debug, Display, Formatter};
+    use std::marker::PhantomData;
+    use std::mem::{forget, size_of, transmute_copy, MaybeUninit};
     use std::ffi::{CStr, CString};
     use std::ops::Deref;
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::ptr;
+    use std::rc::Rc;
 
     #[derive(Debug)]
     pub struct MangledSymbol(*mut libc::c_char);
@@ -557,14 +629,25 @@ pub mod experimental {
         }
     }
 
-    #[derive(Debug)]
-    pub struct LLVMError(LLVMErrorRef);
+    /// An error reported by the ORC JIT. The message is copied out of the `LLVMErrorRef`
+    /// as soon as it is received, since reading it consumes the underlying error.
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct LLVMError(CString);
 
     impl LLVMError {
-        // Null type id == success
-        pub fn get_type_id(&self) -> LLVMErrorTypeId { // FIXME: Don't expose LLVMErrorTypeId
+        /// Consumes `error`, returning `Ok(())` for a null (success) `LLVMErrorRef`.
+        fn check(error: LLVMErrorRef) -> Result<(), LLVMError> {
+            if error.is_null() {
+                return Ok(());
+            }
+
             unsafe {
-                LLVMGetErrorTypeId(self.0)
+                let message = LLVMGetErrorMessage(error);
+                let owned = CStr::from_ptr(message).to_owned();
+
+                LLVMDisposeErrorMessage(message);
+
+                Err(LLVMError(owned))
             }
         }
     }
@@ -573,79 +656,273 @@ pub mod experimental {
         type Target = CStr;
 
         fn deref(&self) -> &CStr {
-            unsafe {
-                CStr::from_ptr(LLVMGetErrorMessage(self.0)) // FIXME: LLVMGetErrorMessage consumes the error, needs LLVMDisposeErrorMessage after
-            }
+            &self.0
+        }
+    }
+
+    impl Display for LLVMError {
+        fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+            write!(f, "LLVMError({})", self.0.to_string_lossy())
+        }
+    }
+
+    impl Error for LLVMError {}
+
+    /// A Rust callback used by the ORC JIT to find the address of symbols which are not
+    /// defined by any of the modules added to it, such as functions from the host process.
+    ///
+    /// It is handed mangled symbol names (see `Orc::get_mangled_symbol`) and should return
+    /// `None` if it cannot resolve a symbol.
+    pub type SymbolResolver = Box<dyn Fn(&str) -> Option<usize>>;
+
+    /// A handle to a `Module` which has been added to an `Orc` JIT. It can be passed to
+    /// `Orc::remove_module` to discard the module and its machine code.
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct OrcModuleHandle(LLVMOrcModuleHandle);
+
+    /// The ORC JIT stack shared between an `Orc` and the `JitFunction`s looked up in it.
+    pub(crate) struct OrcInner<'ctx> {
+        jit_stack: LLVMOrcJITStackRef,
+        // The JIT stack only borrows the target machine, so it must be dropped after the stack is disposed of
+        _target_machine: TargetMachine,
+        // Double boxed so that the pointer handed to LLVM as the resolver context stays put
+        symbol_resolver: Box<SymbolResolver>,
+        // Invariant so that the JIT can never outlive the `Context` its modules belong to
+        _marker: PhantomData<Cell<&'ctx Context>>,
+    }
+
+    impl Debug for OrcInner<'_> {
+        fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+            f.debug_struct("OrcInner")
+                .field("jit_stack", &self.jit_stack)
+                .finish()
         }
     }
 
-    impl Drop for LLVMError {
+    impl Drop for OrcInner<'_> {
         fn drop(&mut self) {
-            unsafe {
-                LLVMConsumeError(self.0)
-            }
+            // REVIEW: Not much we can do with an error here other than ignore it.
+            let _ = LLVMError::check(unsafe {
+                LLVMOrcDisposeInstance(self.jit_stack)
+            });
         }
     }
 
-    // TODO
+    /// An ORC JIT which can compile `Module`s either eagerly or lazily (function by function, the
+    /// first time each one is called) and discard them again, making it suitable for incremental
+    /// compilation such as in a REPL.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::context::Context;
+    /// use inkwell::execution_engine::experimental::Orc;
+    /// use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetMachine};
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// let triple = TargetMachine::get_default_triple();
+    /// let target = Target::from_triple(&triple).unwrap();
+    /// let target_machine = target.create_target_machine(&triple, "", "", OptimizationLevel::None, RelocMode::Default, CodeModel::Default).unwrap();
+    /// let context = Context::create();
+    /// let orc = Orc::create(&context, target_machine);
+    /// let module = context.create_module("repl_line_1");
+    /// let builder = context.create_builder();
+    /// let i64_type = context.i64_type();
+    /// let function = module.add_function("answer", i64_type.fn_type(&[], false), None);
+    ///
+    /// builder.position_at_end(context.append_basic_block(function, "entry"));
+    /// builder.build_return(Some(&i64_type.const_int(42, false)));
+    ///
+    /// let handle = orc.add_compiled_ir(module, true).unwrap();
+    ///
+    /// unsafe {
+    ///     let answer = orc.get_function::<unsafe extern "C" fn() -> u64>("answer").unwrap();
+    ///
+    ///     assert_eq!(answer.call(), 42);
+    /// }
+    ///
+    /// orc.remove_module(handle).unwrap();
+    /// ```
     #[derive(Debug)]
-    pub struct Orc(LLVMOrcJITStackRef);
+    pub struct Orc<'ctx>(Rc<OrcInner<'ctx>>);
 
-    impl Orc {
-        pub fn create(target_machine: TargetMachine) -> Self {
-            let stack_ref = unsafe {
+    impl<'ctx> Orc<'ctx> {
+        /// Creates an ORC JIT for modules of `context` which resolves external symbols against
+        /// the current process.
+        pub fn create(context: &'ctx Context, target_machine: TargetMachine) -> Self {
+            // Passing null makes the symbols of the current process searchable
+            unsafe {
+                LLVMLoadLibraryPermanently(ptr::null());
+            }
+
+            Orc::create_with_symbol_resolver(context, target_machine, Box::new(search_process_symbols))
+        }
+
+        /// Creates an ORC JIT for modules of `context` which resolves external symbols through
+        /// `symbol_resolver`. Symbols defined by modules in the JIT are always found without consulting it.
+        pub fn create_with_symbol_resolver(_context: &'ctx Context, target_machine: TargetMachine, symbol_resolver: SymbolResolver) -> Self {
+            let jit_stack = unsafe {
                 LLVMOrcCreateInstance(target_machine.target_machine)
             };
 
-            Orc(stack_ref)
+            Orc(Rc::new(OrcInner {
+                jit_stack,
+                _target_machine: target_machine,
+                symbol_resolver: Box::new(symbol_resolver),
+                _marker: PhantomData,
+            }))
         }
 
-        pub fn add_compiled_ir<'ctx>(&self, module: &Module<'ctx>, lazily: bool) -> Result<(), ()> {
-            // let handle = MaybeUninit::uninit();
-            // let _err =  if lazily {
-            //     unsafe { LLVMOrcAddLazilyCompiledIR(self.0, handle.as_mut_ptr(), module.module.get(), sym_resolve, sym_resolve_ctx) }
-            // } else {
-            //     unsafe { LLVMOrcAddEagerlyCompiledIR(self.0, handle.as_mut_ptr(), module.module.get(), sym_resolve, sym_resolve_ctx) }
-            // };
+        /// Adds a `Module` to the JIT, taking ownership of it. If `lazily` is true each function
+        /// is only compiled the first time it is called, otherwise the whole module is compiled
+        /// right away.
+        ///
+        /// Returns `Err` if the module is already owned by an `ExecutionEngine` or compilation fails.
+        pub fn add_compiled_ir(&self, module: Module<'ctx>, lazily: bool) -> Result<OrcModuleHandle, LLVMError> {
+            if module.owned_by_ee.borrow().is_some() {
+                return Err(LLVMError(CString::new("Module is already owned by an ExecutionEngine").unwrap()));
+            }
+
+            let module_ref = module.module.get();
+            let resolver_ctx = &*self.0.symbol_resolver as *const SymbolResolver as *mut c_void;
+            let mut handle = MaybeUninit::uninit();
+
+            // The JIT stack takes ownership of the module, so it must not be disposed of on drop
+            drop(module.data_layout.borrow_mut().take());
+            forget(module);
+
+            let err = unsafe {
+                if lazily {
+                    LLVMOrcAddLazilyCompiledIR(self.0.jit_stack, handle.as_mut_ptr(), module_ref, Some(symbol_resolver_shim), resolver_ctx)
+                } else {
+                    LLVMOrcAddEagerlyCompiledIR(self.0.jit_stack, handle.as_mut_ptr(), module_ref, Some(symbol_resolver_shim), resolver_ctx)
+                }
+            };
+
+            LLVMError::check(err)?;
 
-            Ok(())
+            Ok(OrcModuleHandle(unsafe { handle.assume_init() }))
         }
 
-        /// Obtains an error message owned by the ORC JIT stack.
-        pub fn get_error(&self) -> &CStr {
-            let err_str = unsafe { LLVMOrcGetErrorMsg(self.0) };
+        /// Removes a module previously added with `add_compiled_ir`, freeing its machine code.
+        /// Any `JitFunction` looked up from that module must no longer be called afterwards.
+        pub fn remove_module(&self, handle: OrcModuleHandle) -> Result<(), LLVMError> {
+            LLVMError::check(unsafe {
+                LLVMOrcRemoveModule(self.0.jit_stack, handle.0)
+            })
+        }
 
-            if err_str.is_null() {
-                panic!("Needs to be optional")
+        /// Looks up the address of a symbol by its unmangled name, compiling it first if it
+        /// was added lazily.
+        pub fn get_symbol_address(&self, name: &str) -> Result<usize, FunctionLookupError> {
+            let mangled_name = self.get_mangled_symbol(name);
+            let mut address: LLVMOrcTargetAddress = 0;
+
+            let err = unsafe {
+                LLVMOrcGetSymbolAddress(self.0.jit_stack, &mut address, mangled_name.as_ptr())
+            };
+
+            if LLVMError::check(err).is_err() || address == 0 {
+                return Err(FunctionLookupError::FunctionNotFound);
+            }
+
+            Ok(address as usize)
+        }
+
+        /// Looks up a function by its unmangled name, compiling it first if it was added lazily.
+        ///
+        /// # Safety
+        ///
+        /// It is the caller's responsibility to ensure they call the function with
+        /// the correct signature and calling convention, and that the module defining
+        /// it has not been removed.
+        pub unsafe fn get_function<F>(&self, fn_name: &str) -> Result<JitFunction<'ctx, F>, FunctionLookupError>
+        where
+            F: UnsafeFunctionPointer,
+        {
+            let address = self.get_symbol_address(fn_name)?;
+
+            assert_eq!(size_of::<F>(), size_of::<usize>(),
+                "The type `F` must have the same size as a function pointer");
+
+            Ok(JitFunction {
+                _owner: JitFunctionOwner::Orc(self.0.clone()),
+                inner: transmute_copy(&address),
+            })
+        }
+
+        /// Registers a `JITEventListener` which will be notified of every object this JIT emits
+        /// from now on, such as to make its code visible to gdb or perf.
+        pub fn register_jit_event_listener(&self, listener: JITEventListener) {
+            unsafe {
+                LLVMOrcRegisterJITEventListener(self.0.jit_stack, listener.listener)
             }
+        }
 
+        /// Stops notifying a previously registered `JITEventListener`.
+        pub fn unregister_jit_event_listener(&self, listener: JITEventListener) {
             unsafe {
+                LLVMOrcUnregisterJITEventListener(self.0.jit_stack, listener.listener)
+            }
+        }
+
+        /// Obtains the last error message owned by the ORC JIT stack, if any.
+        pub fn get_error(&self) -> Option<&CStr> {
+            let err_str = unsafe { LLVMOrcGetErrorMsg(self.0.jit_stack) };
+
+            if err_str.is_null() {
+                return None;
+            }
+
+            let err_str = unsafe {
                 CStr::from_ptr(err_str)
+            };
+
+            if err_str.to_bytes().is_empty() {
+                return None;
             }
+
+            Some(err_str)
         }
 
         pub fn get_mangled_symbol(&self, symbol: &str) -> MangledSymbol {
             let mut mangled_symbol = MaybeUninit::uninit();
             let c_symbol = to_c_str(symbol);
 
-            unsafe { LLVMOrcGetMangledSymbol(self.0, mangled_symbol.as_mut_ptr(), c_symbol.as_ptr()) };
+            unsafe { LLVMOrcGetMangledSymbol(self.0.jit_stack, mangled_symbol.as_mut_ptr(), c_symbol.as_ptr()) };
 
             MangledSymbol(unsafe { mangled_symbol.assume_init() })
         }
     }
 
-    impl Drop for Orc {
-        fn drop(&mut self) {
-            // REVIEW: This returns an LLVMErrorRef, not sure what we can do with it...
-            // print to stderr maybe?
-            LLVMError(unsafe {
-                LLVMOrcDisposeInstance(self.0)
-            });
-        }
+    extern "C" fn symbol_resolver_shim(name: *const c_char, ctx: *mut c_void) -> u64 {
+        let resolver = unsafe { &*(ctx as *const SymbolResolver) };
+        let name = unsafe { CStr::from_ptr(name) };
+
+        // Unwinding into LLVM is UB, so a panicking resolver simply fails to find the symbol
+        let address = catch_unwind(AssertUnwindSafe(|| name.to_str().ok().and_then(|name| resolver(name))));
+
+        address.ok().flatten().unwrap_or(0) as u64
     }
 
-    #[test]
-    fn test_mangled_str() {
+    fn search_process_symbols(mangled_name: &str) -> Option<usize> {
+        let search = |name: &str| {
+            let c_name = to_c_str(name);
+            let address = unsafe { LLVMSearchForAddressOfSymbol(c_name.as_ptr()) };
+
+            if address.is_null() { None } else { Some(address as usize) }
+        };
+
+        // Symbol names are mangled with a global prefix on some platforms (ie MachO's "_")
+        search(mangled_name).or_else(|| {
+            if mangled_name.starts_with('_') { search(&mangled_name[1..]) } else { None }
+        })
+    }
+
+    #[cfg(test)]
+    fn create_native_orc<'ctx>(context: &'ctx Context) -> Orc<'ctx> {
         use crate::OptimizationLevel;
         use crate::targets::{CodeModel, InitializationConfig, RelocMode, Target};
 
@@ -661,15 +938,134 @@ pub mod experimental {
             RelocMode::Default,
             CodeModel::Default,
         ).unwrap();
-        let orc = Orc::create(target_machine);
 
-        assert_eq!(orc.get_error().to_str().unwrap(), "");
+        Orc::create(context, target_machine)
+    }
+
+    #[test]
+    fn test_mangled_str() {
+        let context = Context::create();
+        let orc = create_native_orc(&context);
+
+        assert_eq!(orc.get_error(), None);
 
         let mangled_symbol = orc.get_mangled_symbol("MyStructName");
 
-        assert_eq!(orc.get_error().to_str().unwrap(), "");
+        assert_eq!(orc.get_error(), None);
 
         // REVIEW: This doesn't seem very mangled...
         assert_eq!(mangled_symbol.to_str().unwrap(), "MyStructName");
     }
+
+    #[test]
+    fn test_add_and_remove_compiled_ir() {
+        let context = Context::create();
+        let orc = create_native_orc(&context);
+        let builder = context.create_builder();
+        let i64_type = context.i64_type();
+        let fn_type = i64_type.fn_type(&[], false);
+
+        for &lazily in &[false, true] {
+            let module = context.create_module("orc");
+            let function = module.add_function("answer", fn_type, None);
+
+            builder.position_at_end(context.append_basic_block(function, "entry"));
+            builder.build_return(Some(&i64_type.const_int(42, false)));
+
+            let handle = orc.add_compiled_ir(module, lazily).unwrap();
+
+            unsafe {
+                let answer = orc.get_function::<unsafe extern "C" fn() -> u64>("answer").unwrap();
+
+                assert_eq!(answer.call(), 42);
+            }
+
+            orc.remove_module(handle).unwrap();
+
+            unsafe {
+                assert_eq!(orc.get_function::<unsafe extern "C" fn() -> u64>("answer").unwrap_err(),
+                    FunctionLookupError::FunctionNotFound);
+            }
+        }
+    }
+
+    #[test]
+    fn test_jit_event_listeners() {
+        let context = Context::create();
+        let orc = create_native_orc(&context);
+        let builder = context.create_builder();
+        let i64_type = context.i64_type();
+        let module = context.create_module("orc");
+        let function = module.add_function("answer", i64_type.fn_type(&[], false), None);
+        let gdb_listener = JITEventListener::create_gdb_registration_listener();
+
+        assert_eq!(gdb_listener, JITEventListener::create_gdb_registration_listener());
+
+        orc.register_jit_event_listener(gdb_listener);
+
+        if let Some(perf_listener) = JITEventListener::create_perf_listener() {
+            orc.register_jit_event_listener(perf_listener);
+            orc.unregister_jit_event_listener(perf_listener);
+        }
+
+        builder.position_at_end(context.append_basic_block(function, "entry"));
+        builder.build_return(Some(&i64_type.const_int(42, false)));
+
+        orc.add_compiled_ir(module, false).unwrap();
+
+        unsafe {
+            let answer = orc.get_function::<unsafe extern "C" fn() -> u64>("answer").unwrap();
+
+            assert_eq!(answer.call(), 42);
+        }
+
+        orc.unregister_jit_event_listener(gdb_listener);
+    }
+
+    #[test]
+    fn test_symbol_resolver() {
+        use crate::OptimizationLevel;
+        use crate::targets::{CodeModel, InitializationConfig, RelocMode, Target};
+
+        extern "C" fn host_seven() -> u64 {
+            7
+        }
+
+        Target::initialize_native(&InitializationConfig::default()).unwrap();
+
+        let target_triple = TargetMachine::get_default_triple();
+        let target = Target::from_triple(&target_triple).unwrap();
+        let target_machine = target.create_target_machine(
+            &target_triple,
+            &"",
+            &"",
+            OptimizationLevel::None,
+            RelocMode::Default,
+            CodeModel::Default,
+        ).unwrap();
+        let context = Context::create();
+        let orc = Orc::create_with_symbol_resolver(&context, target_machine, Box::new(|name| {
+            if name.ends_with("host_seven") { Some(host_seven as usize) } else { None }
+        }));
+        let module = context.create_module("orc");
+        let builder = context.create_builder();
+        let i64_type = context.i64_type();
+        let fn_type = i64_type.fn_type(&[], false);
+        let host_fn = module.add_function("host_seven", fn_type, None);
+        let function = module.add_function("call_host", fn_type, None);
+
+        builder.position_at_end(context.append_basic_block(function, "entry"));
+
+        let seven = builder.build_call(host_fn, &[], "seven").try_as_basic_value().left().unwrap();
+
+        builder.build_return(Some(&seven));
+
+        orc.add_compiled_ir(module, false).unwrap();
+
+        unsafe {
+            let call_host = orc.get_function::<unsafe extern "C" fn() -> u64>("call_host").unwrap();
+
+            assert_eq!(call_host.call(), 7);
+        }
+    }
 }
@@ -0,0 +1,109 @@
+//! An `Intrinsic` is a function known to LLVM, such as `llvm.memset` or `llvm.sadd.with.overflow`,
+//! which may be overloaded on the types it operates on.
+
+use llvm_sys::core::{LLVMGetIntrinsicDeclaration, LLVMIntrinsicIsOverloaded, LLVMLookupIntrinsicID};
+use llvm_sys::prelude::LLVMTypeRef;
+
+use crate::module::Module;
+use crate::types::{AsTypeRef, BasicTypeEnum};
+use crate::values::FunctionValue;
+
+/// An LLVM intrinsic, identified by its intrinsic ID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Intrinsic {
+    id: u32,
+}
+
+impl Intrinsic {
+    /// Creates an `Intrinsic` from an ID, such as one returned by `FunctionValue::get_intrinsic_id`.
+    ///
+    /// # Safety
+    ///
+    /// The ID must be a valid, non zero intrinsic ID for the LLVM version in use.
+    pub unsafe fn new(id: u32) -> Self {
+        Intrinsic { id }
+    }
+
+    /// Finds an intrinsic by its name. Overloaded intrinsics may be looked up either by their
+    /// base name (`llvm.ctpop`) or by a mangled name (`llvm.ctpop.v4i32`).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::intrinsics::Intrinsic;
+    ///
+    /// assert!(Intrinsic::find("llvm.trap").is_some());
+    /// assert!(Intrinsic::find("llvm.not_an_intrinsic").is_none());
+    /// ```
+    pub fn find(name: &str) -> Option<Self> {
+        let id = unsafe {
+            LLVMLookupIntrinsicID(name.as_ptr() as *const ::libc::c_char, name.len())
+        };
+
+        if id == 0 {
+            return None;
+        }
+
+        Some(Intrinsic { id })
+    }
+
+    /// Gets the ID of this intrinsic.
+    pub fn get_id(self) -> u32 {
+        self.id
+    }
+
+    /// Determines whether this intrinsic is overloaded, meaning that its declaration
+    /// depends on the types it is instantiated with.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::intrinsics::Intrinsic;
+    ///
+    /// assert!(!Intrinsic::find("llvm.trap").unwrap().is_overloaded());
+    /// assert!(Intrinsic::find("llvm.ctpop").unwrap().is_overloaded());
+    /// ```
+    pub fn is_overloaded(self) -> bool {
+        unsafe {
+            LLVMIntrinsicIsOverloaded(self.id) != 0
+        }
+    }
+
+    /// Gets or inserts the declaration of this intrinsic into a `Module`. Overloaded intrinsics
+    /// must be given the types they are overloaded on, in the order LLVM expects them (for
+    /// `llvm.memset.p0i8.i64` that is the pointer type followed by the length type), while
+    /// other intrinsics must be given none.
+    ///
+    /// Returns `None` if types are given for an intrinsic which isn't overloaded or none are
+    /// given for one which is. LLVM cannot verify the number of overloaded types, so giving
+    /// the wrong number may still abort.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::intrinsics::Intrinsic;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("intrinsics");
+    /// let i32_type = context.i32_type();
+    /// let sadd = Intrinsic::find("llvm.sadd.with.overflow").unwrap();
+    /// let declaration = sadd.get_declaration(&module, &[i32_type.into()]).unwrap();
+    ///
+    /// assert_eq!(declaration.get_name().to_str(), Ok("llvm.sadd.with.overflow.i32"));
+    /// ```
+    pub fn get_declaration<'ctx>(self, module: &Module<'ctx>, param_types: &[BasicTypeEnum<'ctx>]) -> Option<FunctionValue<'ctx>> {
+        if self.is_overloaded() == param_types.is_empty() {
+            return None;
+        }
+
+        let mut param_types: Vec<LLVMTypeRef> = param_types.iter()
+                                                           .map(|ty| ty.as_type_ref())
+                                                           .collect();
+        let function = unsafe {
+            LLVMGetIntrinsicDeclaration(module.module.get(), self.id, param_types.as_mut_ptr(), param_types.len())
+        };
+
+        FunctionValue::new(function)
+    }
+}
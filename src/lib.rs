@@ -17,6 +17,9 @@ extern crate inkwell_internals;
 #[macro_use]
 pub mod support;
 #[deny(missing_docs)]
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+pub mod abi;
+#[deny(missing_docs)]
 pub mod attributes;
 #[deny(missing_docs)]
 #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9",
@@ -31,6 +34,12 @@ pub mod data_layout;
 #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
 pub mod debug_info;
 pub mod execution_engine;
+#[deny(missing_docs)]
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9",
+              feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0", feature = "llvm7-0", feature = "llvm8-0")))]
+pub mod intrinsics;
+#[deny(missing_docs)]
+pub mod lto;
 pub mod memory_buffer;
 #[deny(missing_docs)]
 pub mod module;
@@ -0,0 +1,402 @@
+//! Link time optimization over several modules, either by merging them into a single `Module`
+//! (full LTO) or through LLVM's ThinLTO code generator.
+
+#[llvm_versions(4.0..=latest)]
+#[cfg(feature = "thinlto")]
+use llvm_sys::lto::{
+    lto_codegen_model, lto_get_error_message, lto_get_version, thinlto_code_gen_t, thinlto_codegen_add_cross_referenced_symbol,
+    thinlto_codegen_add_module, thinlto_codegen_add_must_preserve_symbol, thinlto_codegen_dispose,
+    thinlto_codegen_process, thinlto_codegen_set_cache_dir, thinlto_codegen_set_cpu, thinlto_codegen_set_pic_model,
+    thinlto_create_codegen, thinlto_module_get_num_objects, thinlto_module_get_object,
+};
+
+use crate::context::Context;
+use crate::memory_buffer::MemoryBuffer;
+use crate::module::{Linkage, Module};
+use crate::passes::{PassManager, PassManagerBuilder};
+use crate::support::LLVMString;
+use crate::targets::{FileType, TargetMachine};
+use crate::values::GlobalValue;
+use crate::{GlobalVisibility, OptimizationLevel};
+#[llvm_versions(4.0..=latest)]
+#[cfg(feature = "thinlto")]
+use crate::support::to_c_str;
+#[llvm_versions(4.0..=latest)]
+#[cfg(feature = "thinlto")]
+use crate::targets::RelocMode;
+
+use std::collections::HashSet;
+#[llvm_versions(4.0..=latest)]
+#[cfg(feature = "thinlto")]
+use std::ffi::{CStr, CString};
+#[llvm_versions(4.0..=latest)]
+#[cfg(feature = "thinlto")]
+use std::path::Path;
+
+/// Merges several modules into one, which is then internalized, optimized and compiled
+/// as a whole.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::OptimizationLevel;
+/// use inkwell::context::Context;
+/// use inkwell::lto::FullLTO;
+/// use inkwell::memory_buffer::MemoryBuffer;
+/// use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetMachine};
+/// use std::path::Path;
+///
+/// Target::initialize_native(&InitializationConfig::default()).unwrap();
+///
+/// let triple = TargetMachine::get_default_triple();
+/// let target = Target::from_triple(&triple).unwrap();
+/// let target_machine = target.create_target_machine(
+///     &triple,
+///     "generic",
+///     "",
+///     OptimizationLevel::Default,
+///     RelocMode::Default,
+///     CodeModel::Default,
+/// ).unwrap();
+///
+/// let context = Context::create();
+/// let mut lto = FullLTO::new(&context, "merged");
+///
+/// for path in &["main.bc", "runtime.bc"] {
+///     let buffer = MemoryBuffer::create_from_file(Path::new(path)).unwrap();
+///
+///     lto.add_bitcode(&buffer).unwrap();
+/// }
+///
+/// lto.add_preserved_symbol("main");
+///
+/// let object = lto.run(&target_machine, OptimizationLevel::Aggressive).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct FullLTO<'ctx> {
+    context: &'ctx Context,
+    module: Module<'ctx>,
+    preserved_symbols: HashSet<String>,
+}
+
+impl<'ctx> FullLTO<'ctx> {
+    /// Creates a `FullLTO` whose inputs are merged into an empty `Module` named `name`.
+    pub fn new(context: &'ctx Context, name: &str) -> Self {
+        FullLTO {
+            context,
+            module: context.create_module(name),
+            preserved_symbols: HashSet::new(),
+        }
+    }
+
+    /// Links a `Module` into the merged `Module`.
+    pub fn add_module(&self, module: Module<'ctx>) -> Result<(), LLVMString> {
+        self.module.link_in_module(module)
+    }
+
+    /// Parses a bitcode `MemoryBuffer` and links it into the merged `Module`.
+    pub fn add_bitcode(&self, buffer: &MemoryBuffer) -> Result<(), LLVMString> {
+        let module = Module::parse_bitcode_from_buffer(buffer, self.context)?;
+
+        self.add_module(module)
+    }
+
+    /// Keeps the symbol `name` visible outside of the merged `Module` when it is internalized.
+    pub fn add_preserved_symbol(&mut self, name: &str) {
+        self.preserved_symbols.insert(name.to_string());
+    }
+
+    /// Gets the merged `Module`.
+    pub fn get_module(&self) -> &Module<'ctx> {
+        &self.module
+    }
+
+    /// Gives internal linkage to every function and global variable defined in the merged
+    /// `Module` which isn't a preserved symbol, so they can be inlined and removed once unused.
+    pub fn internalize(&self) {
+        let functions = self.module.get_functions().map(|function| function.as_global_value());
+
+        for global in functions.chain(self.module.get_globals()) {
+            if self.should_internalize(global) {
+                global.set_linkage(Linkage::Internal);
+                global.set_visibility(GlobalVisibility::Default);
+            }
+        }
+    }
+
+    fn should_internalize(&self, global: GlobalValue<'ctx>) -> bool {
+        if global.is_declaration() {
+            return false;
+        }
+
+        match global.get_linkage() {
+            Linkage::Internal | Linkage::Private | Linkage::Appending | Linkage::AvailableExternally => return false,
+            _ => {},
+        }
+
+        let name = global.get_name().to_string_lossy();
+
+        // Intrinsic globals such as llvm.used and llvm.global_ctors must keep their linkage
+        !name.starts_with("llvm.") && !self.preserved_symbols.contains(&*name)
+    }
+
+    /// Runs LLVM's link time optimization pipeline on the merged `Module` for the given
+    /// `TargetMachine`.
+    pub fn optimize(&self, target_machine: &TargetMachine, opt_level: OptimizationLevel) {
+        self.module.set_triple(&target_machine.get_triple());
+        self.module.set_data_layout(&target_machine.get_target_data().get_data_layout());
+
+        let pass_manager_builder = PassManagerBuilder::create();
+        let pass_manager = PassManager::create(());
+
+        pass_manager_builder.set_optimization_level(opt_level);
+        target_machine.add_analysis_passes(&pass_manager);
+        // Internalization is done by FullLTO::internalize so the preserved symbols are respected
+        pass_manager_builder.populate_lto_pass_manager(&pass_manager, false, true);
+        pass_manager.run_on(&self.module);
+    }
+
+    /// Compiles the merged `Module` into an object file.
+    pub fn codegen(&self, target_machine: &TargetMachine) -> Result<MemoryBuffer, LLVMString> {
+        target_machine.write_to_memory_buffer(&self.module, FileType::Object)
+    }
+
+    /// Internalizes, optimizes and compiles the merged `Module` into an object file.
+    pub fn run(self, target_machine: &TargetMachine, opt_level: OptimizationLevel) -> Result<MemoryBuffer, LLVMString> {
+        self.internalize();
+        self.optimize(target_machine, opt_level);
+        self.codegen(target_machine)
+    }
+}
+
+/// LLVM's `LTOObjectBuffer` doesn't expose its fields, so it is read through this struct
+/// which has the same layout.
+#[llvm_versions(4.0..=latest)]
+#[cfg(feature = "thinlto")]
+#[repr(C)]
+struct ObjectBuffer {
+    buffer: *const ::libc::c_char,
+    size: ::libc::size_t,
+}
+
+/// Drives LLVM's ThinLTO code generator, which optimizes each module separately while
+/// importing the functions it needs from the other modules, and produces an object file
+/// per module.
+///
+/// ThinLTO lives in LLVM's libLTO rather than the core libraries, so it requires the
+/// `thinlto` feature which links against it. All modules need the same target triple, and
+/// LLVM aborts if they are malformed.
+///
+/// libLTO is a shared library which usually has LLVM linked into it, so the process then holds
+/// a second copy of LLVM besides the one inkwell links against. The two never share a
+/// `Context`, since bitcode is passed between them, but they must be the same LLVM version: `create` fails if libLTO's version differs from the one
+/// selected by the `llvmX-Y` feature. Each copy also has its own global state, such as the
+/// options set with `support::parse_command_line_options`, which do not affect libLTO.
+///
+/// Cross module importing relies on the module summaries in the bitcode, such as those emitted
+/// by `clang -flto=thin` or `opt -module-summary`. Inkwell cannot write summaries, so a `Module`
+/// added with `add_module` has none: it is optimized and compiled on its own, and never imports
+/// from or exports to the other modules. Only bitcode with summaries, added with `add_bitcode`,
+/// gets cross module importing.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::lto::ThinLTO;
+/// use inkwell::memory_buffer::MemoryBuffer;
+/// use std::path::Path;
+///
+/// let mut thin_lto = ThinLTO::create().unwrap();
+///
+/// for path in &["main.bc", "runtime.bc"] {
+///     let buffer = MemoryBuffer::create_from_file(Path::new(path)).unwrap();
+///
+///     thin_lto.add_bitcode(path, buffer);
+/// }
+///
+/// thin_lto.add_must_preserve_symbol("main");
+///
+/// let objects = thin_lto.run();
+/// ```
+#[llvm_versions(4.0..=latest)]
+#[cfg(feature = "thinlto")]
+#[derive(Debug)]
+pub struct ThinLTO {
+    code_gen: thinlto_code_gen_t,
+    // LLVM refers to the identifiers and bitcode without copying them
+    inputs: Vec<(CString, MemoryBuffer)>,
+}
+
+#[llvm_versions(4.0..=latest)]
+#[cfg(feature = "thinlto")]
+impl ThinLTO {
+    /// Creates a ThinLTO code generator. Fails if the libLTO found at runtime is a different
+    /// LLVM version than the one inkwell was built for.
+    pub fn create() -> Result<Self, LLVMString> {
+        check_lto_version()?;
+
+        let code_gen = unsafe { thinlto_create_codegen() };
+
+        if code_gen.is_null() {
+            let message = unsafe { CStr::from_ptr(lto_get_error_message()) };
+
+            return Err(LLVMString::create_from_c_str(message));
+        }
+
+        Ok(ThinLTO {
+            code_gen,
+            inputs: Vec::new(),
+        })
+    }
+
+    /// Adds a `Module` as bitcode, identified by its name. The bitcode has no module summary,
+    /// so this `Module` takes no part in cross module importing.
+    pub fn add_module(&mut self, module: &Module) {
+        let identifier = module.get_name().to_string_lossy().into_owned();
+
+        self.add_bitcode(&identifier, module.write_bitcode_to_memory());
+    }
+
+    /// Adds a bitcode `MemoryBuffer`. The identifier must be unique among all inputs.
+    pub fn add_bitcode(&mut self, identifier: &str, buffer: MemoryBuffer) {
+        let identifier = to_c_str(identifier).into_owned();
+        let data = buffer.as_slice();
+
+        unsafe {
+            thinlto_codegen_add_module(
+                self.code_gen,
+                identifier.as_ptr(),
+                data.as_ptr() as *const ::libc::c_char,
+                data.len() as ::libc::c_int,
+            );
+        }
+
+        self.inputs.push((identifier, buffer));
+    }
+
+    /// Keeps the symbol `name` visible outside of the object files. Other symbols may be
+    /// internalized, inlined and removed.
+    pub fn add_must_preserve_symbol(&self, name: &str) {
+        unsafe {
+            thinlto_codegen_add_must_preserve_symbol(
+                self.code_gen,
+                name.as_ptr() as *const ::libc::c_char,
+                name.len() as ::libc::c_int,
+            );
+        }
+    }
+
+    /// Marks the symbol `name` as referenced across the ThinLTO modules.
+    pub fn add_cross_referenced_symbol(&self, name: &str) {
+        unsafe {
+            thinlto_codegen_add_cross_referenced_symbol(
+                self.code_gen,
+                name.as_ptr() as *const ::libc::c_char,
+                name.len() as ::libc::c_int,
+            );
+        }
+    }
+
+    /// Sets the CPU to generate code for.
+    pub fn set_cpu(&self, cpu: &str) {
+        let c_string = to_c_str(cpu);
+
+        unsafe {
+            thinlto_codegen_set_cpu(self.code_gen, c_string.as_ptr());
+        }
+    }
+
+    /// Sets the relocation model to generate code with.
+    pub fn set_reloc_mode(&self, reloc_mode: RelocMode) {
+        let model = match reloc_mode {
+            RelocMode::Default => lto_codegen_model::LTO_CODEGEN_PIC_MODEL_DEFAULT,
+            RelocMode::Static => lto_codegen_model::LTO_CODEGEN_PIC_MODEL_STATIC,
+            RelocMode::PIC => lto_codegen_model::LTO_CODEGEN_PIC_MODEL_DYNAMIC,
+            RelocMode::DynamicNoPic => lto_codegen_model::LTO_CODEGEN_PIC_MODEL_DYNAMIC_NO_PIC,
+        };
+
+        unsafe {
+            thinlto_codegen_set_pic_model(self.code_gen, model);
+        }
+    }
+
+    /// Caches the generated object files in `path` for incremental builds.
+    pub fn set_cache_dir(&self, path: &Path) {
+        let path_str = path.to_str().expect("Did not find a valid Unicode path string");
+        let c_string = to_c_str(path_str);
+
+        unsafe {
+            thinlto_codegen_set_cache_dir(self.code_gen, c_string.as_ptr());
+        }
+    }
+
+    /// Optimizes and compiles all modules, returning the object files. There is usually
+    /// one object file per module, but this isn't guaranteed.
+    pub fn run(self) -> Vec<MemoryBuffer> {
+        unsafe {
+            thinlto_codegen_process(self.code_gen);
+        }
+
+        let num_objects = unsafe { thinlto_module_get_num_objects(self.code_gen) };
+
+        (0..num_objects as u32).map(|index| {
+            let object: ObjectBuffer = unsafe {
+                std::mem::transmute(thinlto_module_get_object(self.code_gen, index))
+            };
+            let data = unsafe { std::slice::from_raw_parts(object.buffer as *const u8, object.size) };
+
+            // The objects are owned by the code generator, which is disposed of on return
+            MemoryBuffer::create_from_memory_range_copy(data, &format!("thinlto.{}.o", index))
+        }).collect()
+    }
+}
+
+// Checks that libLTO, which is loaded separately from the LLVM libraries inkwell links
+// against, is the version the `llvmX-Y` feature selected.
+#[llvm_versions(4.0..=latest)]
+#[cfg(feature = "thinlto")]
+fn check_lto_version() -> Result<(), LLVMString> {
+    #[cfg(feature = "llvm4-0")]
+    const EXPECTED: &str = "4.0";
+    #[cfg(feature = "llvm5-0")]
+    const EXPECTED: &str = "5.0";
+    #[cfg(feature = "llvm6-0")]
+    const EXPECTED: &str = "6.0";
+    #[cfg(feature = "llvm7-0")]
+    const EXPECTED: &str = "7.0";
+    #[cfg(feature = "llvm8-0")]
+    const EXPECTED: &str = "8.0";
+    #[cfg(feature = "llvm9-0")]
+    const EXPECTED: &str = "9.0";
+    #[cfg(feature = "llvm10-0")]
+    const EXPECTED: &str = "10.0";
+    #[cfg(feature = "llvm11-0")]
+    const EXPECTED: &str = "11.0";
+
+    // Such as "LLVM version 11.0.1", which may be followed by ", " and vendor information
+    let version = unsafe { CStr::from_ptr(lto_get_version()) }.to_string_lossy();
+    let number = version
+        .split("version ")
+        .nth(1)
+        .and_then(|rest| rest.split(|c: char| c == ',' || c.is_whitespace()).next())
+        .unwrap_or("");
+    let major_minor: Vec<_> = number.split('.').take(2).collect();
+
+    if major_minor.join(".") == EXPECTED {
+        return Ok(());
+    }
+
+    let message = format!("libLTO is {:?}, but inkwell was built for LLVM {}\0", version, EXPECTED);
+
+    Err(LLVMString::create_from_str(&message))
+}
+
+#[llvm_versions(4.0..=latest)]
+#[cfg(feature = "thinlto")]
+impl Drop for ThinLTO {
+    fn drop(&mut self) {
+        unsafe {
+            thinlto_codegen_dispose(self.code_gen);
+        }
+    }
+}
@@ -110,13 +110,18 @@ impl MemoryBuffer {
             LLVMCreateObjectFile(self.memory_buffer)
         };
 
-        forget(self);
-
         if object_file.is_null() {
+            forget(self);
+
             return Err(());
         }
 
-        Ok(ObjectFile::new(object_file))
+        // The ObjectFile now owns the buffer, so the contents stay valid until it is dropped
+        let object_file = ObjectFile::new(object_file, self.as_slice());
+
+        forget(self);
+
+        Ok(object_file)
     }
 }
 
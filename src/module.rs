@@ -16,7 +16,9 @@ use llvm_sys::LLVMLinkage;
 use llvm_sys::LLVMModuleFlagBehavior;
 
 use std::cell::{Cell, RefCell, Ref};
+use std::error::Error;
 use std::ffi::CStr;
+use std::fmt;
 use std::fs::File;
 use std::marker::PhantomData;
 use std::mem::{forget, MaybeUninit};
@@ -33,6 +35,7 @@ use crate::data_layout::DataLayout;
 use crate::debug_info::{DebugInfoBuilder, DICompileUnit, DWARFEmissionKind, DWARFSourceLanguage};
 use crate::execution_engine::ExecutionEngine;
 use crate::memory_buffer::MemoryBuffer;
+use crate::passes::PassManager;
 use crate::support::{to_c_str, LLVMString};
 use crate::targets::{InitializationConfig, Target, TargetTriple};
 use crate::types::{AsTypeRef, BasicType, FunctionType, StructType};
@@ -149,7 +152,7 @@ pub enum Linkage {
 /// The underlying module will be disposed when dropping this object.
 #[derive(Debug, PartialEq, Eq)]
 pub struct Module<'ctx> {
-    data_layout: RefCell<Option<DataLayout>>,
+    pub(crate) data_layout: RefCell<Option<DataLayout>>,
     pub(crate) module: Cell<LLVMModuleRef>,
     pub(crate) owned_by_ee: RefCell<Option<ExecutionEngine<'ctx>>>,
     _marker: PhantomData<&'ctx Context>,
@@ -276,6 +279,30 @@ impl<'ctx> Module<'ctx> {
         FunctionValue::new(function)
     }
 
+    /// Iterates over the `FunctionValue`s defined in this `Module`, from either end. The current
+    /// function may be deleted while iterating.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    /// let fn_value1 = module.add_function("my_fn1", fn_type, None);
+    /// let fn_value2 = module.add_function("my_fn2", fn_type, None);
+    ///
+    /// assert_eq!(module.get_functions().collect::<Vec<_>>(), [fn_value1, fn_value2]);
+    /// assert_eq!(module.get_functions().rev().collect::<Vec<_>>(), [fn_value2, fn_value1]);
+    /// ```
+    pub fn get_functions(&self) -> FunctionIter<'ctx> {
+        FunctionIter {
+            front: self.get_first_function(),
+            back: self.get_last_function(),
+        }
+    }
+
     /// Gets a `FunctionValue` defined in this `Module` by its name.
     ///
     /// # Example
@@ -1003,6 +1030,29 @@ impl<'ctx> Module<'ctx> {
         Some(GlobalValue::new(value))
     }
 
+    /// Iterates over the `GlobalValue`s in a module, from either end. The current global may
+    /// be deleted while iterating.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("mod");
+    /// let i8_type = context.i8_type();
+    /// let global1 = module.add_global(i8_type, None, "my_global1");
+    /// let global2 = module.add_global(i8_type, None, "my_global2");
+    ///
+    /// assert_eq!(module.get_globals().collect::<Vec<_>>(), [global1, global2]);
+    /// ```
+    pub fn get_globals(&self) -> GlobalIter<'ctx> {
+        GlobalIter {
+            front: self.get_first_global(),
+            back: self.get_last_global(),
+        }
+    }
+
     /// Gets a named `GlobalValue` in a module.
     ///
     /// # Example
@@ -1098,6 +1148,201 @@ impl<'ctx> Module<'ctx> {
         Self::parse_bitcode_from_buffer(&buffer, &context)
     }
 
+    /// Lazily parses a `Module` from bitcode. Only the declarations, global variables and
+    /// metadata are read up front, function bodies are read from the buffer when they are
+    /// materialized with `materialize_function` or `materialize_all`. Until then, functions
+    /// with a body in the bitcode have no basic blocks but aren't declarations either.
+    ///
+    /// The `Module` takes ownership of the `MemoryBuffer`, since it reads function bodies
+    /// from it on demand.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::memory_buffer::MemoryBuffer;
+    /// use inkwell::module::Module;
+    /// use std::path::Path;
+    ///
+    /// let context = Context::create();
+    /// let buffer = MemoryBuffer::create_from_file(Path::new("foo/bar.bc")).unwrap();
+    /// let module = Module::parse_bitcode_lazily_from_buffer(buffer, &context).unwrap();
+    /// let function = module.get_function("bar").unwrap();
+    ///
+    /// assert_eq!(function.count_basic_blocks(), 0);
+    ///
+    /// module.materialize_function(function);
+    ///
+    /// assert!(function.count_basic_blocks() > 0);
+    /// ```
+    #[llvm_versions(3.9..=latest)]
+    pub fn parse_bitcode_lazily_from_buffer(buffer: MemoryBuffer, context: &'ctx Context) -> Result<Self, LLVMString> {
+        use crate::support::error_handling::get_error_str_diagnostic_handler;
+        use llvm_sys::bit_reader::LLVMGetBitcodeModuleInContext2;
+        use llvm_sys::core::{LLVMContextGetDiagnosticContext, LLVMContextGetDiagnosticHandler, LLVMContextSetDiagnosticHandler};
+        use libc::c_void;
+
+        let mut module = ptr::null_mut();
+        let mut char_ptr: *mut ::libc::c_char = ptr::null_mut();
+        let char_ptr_ptr = &mut char_ptr as *mut *mut ::libc::c_char as *mut *mut c_void as *mut c_void;
+
+        // Unlike the deprecated LLVMGetBitcodeModuleInContext, errors are only reported through
+        // the context's diagnostic handler, which would exit the process if none was set.
+        let (prev_handler, prev_handler_ctx) = unsafe {
+            (LLVMContextGetDiagnosticHandler(context.context), LLVMContextGetDiagnosticContext(context.context))
+        };
+
+        context.set_raw_diagnostic_handler(get_error_str_diagnostic_handler, char_ptr_ptr);
+
+        let code = unsafe {
+            LLVMGetBitcodeModuleInContext2(context.context, buffer.memory_buffer, &mut module)
+        };
+
+        unsafe {
+            LLVMContextSetDiagnosticHandler(context.context, prev_handler, prev_handler_ctx);
+        }
+
+        // LLVM has taken ownership of the buffer, even if parsing failed
+        forget(buffer);
+
+        if code == 1 {
+            if char_ptr.is_null() {
+                return Err(LLVMString::create_from_str("Failed to parse bitcode\0"));
+            }
+
+            return Err(LLVMString::new(char_ptr));
+        }
+
+        Ok(Module::new(module))
+    }
+
+    /// A convenience function for lazily parsing a `Module` from a bitcode file, see
+    /// `parse_bitcode_lazily_from_buffer`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::module::Module;
+    /// use std::path::Path;
+    ///
+    /// let context = Context::create();
+    /// let module = Module::parse_bitcode_lazily_from_path(Path::new("foo/bar.bc"), &context).unwrap();
+    ///
+    /// module.materialize_all();
+    /// ```
+    #[llvm_versions(3.9..=latest)]
+    pub fn parse_bitcode_lazily_from_path<P: AsRef<Path>>(path: P, context: &'ctx Context) -> Result<Self, LLVMString> {
+        let buffer = MemoryBuffer::create_from_file(path.as_ref())?;
+
+        Self::parse_bitcode_lazily_from_buffer(buffer, context)
+    }
+
+    /// Reads the body of a function of a lazily parsed `Module` from its bitcode. This does
+    /// nothing for functions which are already materialized or were not lazily parsed.
+    ///
+    /// LLVM aborts if the function's bitcode turns out to be malformed.
+    pub fn materialize_function(&self, function: FunctionValue<'ctx>) {
+        // The C API has no direct way to materialize a function, but running a function pass
+        // manager materializes the function before running any passes on it.
+        let pass_manager = PassManager::<FunctionValue<'ctx>>::create(self);
+
+        pass_manager.initialize();
+        pass_manager.run_on(&function);
+        pass_manager.finalize();
+    }
+
+    /// Reads the bodies of all functions of a lazily parsed `Module` from its bitcode, see
+    /// `materialize_function`.
+    pub fn materialize_all(&self) {
+        let pass_manager = PassManager::<FunctionValue<'ctx>>::create(self);
+
+        pass_manager.initialize();
+
+        for function in self.get_functions() {
+            pass_manager.run_on(&function);
+        }
+
+        pass_manager.finalize();
+    }
+
+    /// Parses textual LLVM IR into a new `Module` in a `Context`. The `name` becomes the
+    /// `Module`'s identifier. Unlike `Context::create_module_from_ir`, a failure is reported
+    /// as an `IrParseError` with the location of the problem.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::module::Module;
+    ///
+    /// let context = Context::create();
+    /// let module = Module::parse_ir_str(&context, "define i32 @one() {\n  ret i32 1\n}\n", "golden").unwrap();
+    ///
+    /// assert!(module.get_function("one").is_some());
+    ///
+    /// let error = Module::parse_ir_str(&context, "define i32 @one() {\n  ret i64 1\n}\n", "golden").unwrap_err();
+    ///
+    /// assert_eq!(error.get_line(), Some(2));
+    /// assert_eq!(error.get_column(), Some(7));
+    /// assert_eq!(error.get_source_line(), Some("  ret i64 1"));
+    /// ```
+    pub fn parse_ir_str(context: &'ctx Context, ir: &str, name: &str) -> Result<Self, IrParseError> {
+        // LLVM leaves the location out of diagnostics for unnamed buffers, so those get a placeholder name
+        let buffer_name = if name.is_empty() { "<inkwell>" } else { name };
+        let memory_buffer = MemoryBuffer::create_from_memory_range_copy(ir.as_bytes(), buffer_name);
+        let module = context.create_module_from_ir(memory_buffer)
+            .map_err(|diagnostic| IrParseError::new(&diagnostic.to_string(), buffer_name))?;
+
+        if name.is_empty() {
+            #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+            module.set_name(name);
+            #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+            module.set_source_file_name(name);
+        }
+
+        Ok(module)
+    }
+
+    /// Creates a copy of this `Module` owned by another `Context` by round tripping it
+    /// through bitcode. Unlike `clone`, which always stays in this `Module`'s `Context`,
+    /// this allows a `Module` to be handed over to a `Context` living on another thread.
+    ///
+    /// The copy does not borrow from this `Module` or its `Context`, so either may be
+    /// dropped while the copy is still in use.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let other_context = Context::create();
+    /// let other_module = module.clone_into_context(&other_context).unwrap();
+    ///
+    /// assert_eq!(*other_module.get_context(), other_context);
+    /// assert_eq!(module.print_to_string(), other_module.print_to_string());
+    /// ```
+    pub fn clone_into_context<'ctx2>(&self, context: &'ctx2 Context) -> Result<Module<'ctx2>, LLVMString> {
+        // Writing bitcode for an invalid module may crash, just like cloning one
+        self.verify()?;
+
+        let buffer = self.write_bitcode_to_memory();
+        let module = Module::parse_bitcode_from_buffer(&buffer, context)?;
+
+        // Bitcode doesn't record the module identifier, so it must be carried over manually
+        #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+        unsafe {
+            let mut length = 0;
+            let name = LLVMGetModuleIdentifier(self.module.get(), &mut length);
+
+            LLVMSetModuleIdentifier(module.module.get(), name, length);
+        }
+
+        Ok(module)
+    }
+
     /// Gets the name of this `Module`.
     ///
     /// # Example
@@ -1249,14 +1494,37 @@ impl<'ctx> Module<'ctx> {
             let mut char_ptr: *mut ::libc::c_char = ptr::null_mut();
             let char_ptr_ptr = &mut char_ptr as *mut *mut ::libc::c_char as *mut *mut c_void as *mut c_void;
 
+            // Keep track of any handler set via Context::set_diagnostic_handler so it can be restored
+            #[cfg(not(feature = "llvm3-8"))]
+            let (prev_handler, prev_handler_ctx) = unsafe {
+                use llvm_sys::core::{LLVMContextGetDiagnosticContext, LLVMContextGetDiagnosticHandler};
+
+                (LLVMContextGetDiagnosticHandler(context.context), LLVMContextGetDiagnosticContext(context.context))
+            };
+
             // Newer LLVM versions don't use an out ptr anymore which was really straightforward...
             // Here we assign an error handler to extract the error message, if any, for us.
-            context.set_diagnostic_handler(get_error_str_diagnostic_handler, char_ptr_ptr);
+            context.set_raw_diagnostic_handler(get_error_str_diagnostic_handler, char_ptr_ptr);
 
             let code = unsafe {
                 LLVMLinkModules2(self.module.get(), other.module.get())
             };
 
+            #[cfg(not(feature = "llvm3-8"))]
+            unsafe {
+                use llvm_sys::core::LLVMContextSetDiagnosticHandler;
+
+                LLVMContextSetDiagnosticHandler(context.context, prev_handler, prev_handler_ctx);
+            }
+            // LLVM 3.8 has no way to query the previous handler, so the best we can do is to
+            // not leave a handler pointing into this stack frame behind
+            #[cfg(feature = "llvm3-8")]
+            unsafe {
+                use llvm_sys::core::LLVMContextSetDiagnosticHandler;
+
+                LLVMContextSetDiagnosticHandler(context.context, None, ptr::null_mut());
+            }
+
             forget(other);
 
             if code == 1 {
@@ -1353,6 +1621,30 @@ impl<'ctx> Module<'ctx> {
         }
     }
 
+    /// Gets the debug info compile units of this `Module`, which are listed in its
+    /// `llvm.dbg.cu` named metadata.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::module::Module;
+    ///
+    /// let context = Context::create();
+    /// let module = Module::parse_bitcode_from_path("foo/bar.bc", &context).unwrap();
+    ///
+    /// for compile_unit in module.get_compile_units() {
+    ///     println!("{:?}", compile_unit.get_file().get_filename());
+    /// }
+    /// ```
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_compile_units(&self) -> Vec<DICompileUnit<'ctx>> {
+        self.get_global_metadata("llvm.dbg.cu")
+            .into_iter()
+            .map(|metadata| DICompileUnit::new(metadata.as_metadata_ref()))
+            .collect()
+    }
+
     /// Creates a `DebugInfoBuilder` for this `Module`.
     #[llvm_versions(7.0..=latest)]
     pub fn create_debug_info_builder(&self,
@@ -1386,6 +1678,84 @@ impl<'ctx> Module<'ctx> {
     }
 }
 
+/// A double ended iterator over the `FunctionValue`s of a `Module`, created by `Module::get_functions`.
+#[derive(Debug)]
+pub struct FunctionIter<'ctx> {
+    front: Option<FunctionValue<'ctx>>,
+    back: Option<FunctionValue<'ctx>>,
+}
+
+impl<'ctx> Iterator for FunctionIter<'ctx> {
+    type Item = FunctionValue<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let function = self.front?;
+
+        if self.back == Some(function) {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.front = function.get_next_function();
+        }
+
+        Some(function)
+    }
+}
+
+impl<'ctx> DoubleEndedIterator for FunctionIter<'ctx> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let function = self.back?;
+
+        if self.front == Some(function) {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.back = function.get_previous_function();
+        }
+
+        Some(function)
+    }
+}
+
+/// A double ended iterator over the `GlobalValue`s of a `Module`, created by `Module::get_globals`.
+#[derive(Debug)]
+pub struct GlobalIter<'ctx> {
+    front: Option<GlobalValue<'ctx>>,
+    back: Option<GlobalValue<'ctx>>,
+}
+
+impl<'ctx> Iterator for GlobalIter<'ctx> {
+    type Item = GlobalValue<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let global = self.front?;
+
+        if self.back == Some(global) {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.front = global.get_next_global();
+        }
+
+        Some(global)
+    }
+}
+
+impl<'ctx> DoubleEndedIterator for GlobalIter<'ctx> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let global = self.back?;
+
+        if self.front == Some(global) {
+            self.front = None;
+            self.back = None;
+        } else {
+            self.back = global.get_previous_global();
+        }
+
+        Some(global)
+    }
+}
+
 impl Clone for Module<'_> {
     fn clone(&self) -> Self {
         // REVIEW: Is this just a LLVM 6 bug? We could conditionally compile this assertion for affected versions
@@ -1401,6 +1771,101 @@ impl Clone for Module<'_> {
     }
 }
 
+/// An error produced while parsing textual LLVM IR, along with where it occurred.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IrParseError {
+    line: Option<u32>,
+    column: Option<u32>,
+    source_line: Option<String>,
+    message: String,
+}
+
+impl IrParseError {
+    // Splits up a diagnostic printed by LLVM, which looks like:
+    //
+    // name:2:7: error: message
+    //   source line
+    //       ^
+    pub(crate) fn new(diagnostic: &str, buffer_name: &str) -> Self {
+        let mut lines = diagnostic.lines();
+        let first_line = lines.next().unwrap_or("");
+        let name_prefix = format!("{}:", buffer_name);
+        let location_and_message = if first_line.starts_with(&name_prefix) {
+            &first_line[name_prefix.len()..]
+        } else {
+            first_line
+        };
+        let mut parts = location_and_message.splitn(3, ':');
+        let line = parts.next().and_then(|line| line.parse().ok());
+        let column = parts.next().and_then(|column| column.parse().ok());
+        let (line, column, message) = match (line, column, parts.next()) {
+            (Some(line), Some(column), Some(message)) => (Some(line), Some(column), message),
+            _ => (None, None, location_and_message),
+        };
+        let message = message.trim_start();
+        let message = if message.starts_with("error: ") { &message["error: ".len()..] } else { message };
+
+        IrParseError {
+            line,
+            column,
+            source_line: line.and_then(|_| lines.next()).map(|source_line| source_line.to_string()),
+            message: message.to_string(),
+        }
+    }
+
+    pub(crate) fn with_message(message: &str) -> Self {
+        IrParseError {
+            line: None,
+            column: None,
+            source_line: None,
+            message: message.to_string(),
+        }
+    }
+
+    // Moves the location up by some lines, for snippets parsed after a generated header.
+    pub(crate) fn offset_lines(mut self, lines: u32) -> Self {
+        self.line = self.line.map(|line| line.saturating_sub(lines));
+
+        self
+    }
+
+    /// Gets the 1-based line the error occurred on, if it is known.
+    pub fn get_line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// Gets the 1-based column the error occurred at, if it is known.
+    pub fn get_column(&self) -> Option<u32> {
+        self.column
+    }
+
+    /// Gets the line of source the error occurred on, if it is known.
+    pub fn get_source_line(&self) -> Option<&str> {
+        self.source_line.as_deref()
+    }
+
+    /// Gets the description of the error.
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Error for IrParseError {}
+
+impl fmt::Display for IrParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.line, self.column, &self.source_line) {
+            (Some(line), Some(column), Some(source_line)) => {
+                let caret_indent = " ".repeat(column.saturating_sub(1) as usize);
+
+                write!(f, "IrParseError({}:{}: {}\n{}\n{}^)", line, column, self.message, source_line, caret_indent)
+            },
+            (Some(line), Some(column), None) => write!(f, "IrParseError({}:{}: {})", line, column, self.message),
+            _ => write!(f, "IrParseError({})", self.message),
+        }
+    }
+}
+
 // Module owns the data layout string, so LLVMDisposeModule will deallocate it for us.
 // which is why DataLayout must be called with `new_borrowed`
 impl Drop for Module<'_> {
@@ -1,38 +1,133 @@
-use llvm_sys::object::{LLVMDisposeObjectFile, LLVMObjectFileRef, LLVMSectionIteratorRef, LLVMGetSections, LLVMDisposeSectionIterator, LLVMSymbolIteratorRef, LLVMIsSectionIteratorAtEnd, LLVMGetSectionName, LLVMDisposeRelocationIterator, LLVMRelocationIteratorRef, LLVMDisposeSymbolIterator, LLVMGetSectionContents, LLVMGetSectionSize, LLVMMoveToNextSection, LLVMGetSectionAddress, LLVMGetSymbolName, LLVMGetSymbolSize, LLVMGetRelocations, LLVMGetSymbolAddress, LLVMGetRelocationOffset, LLVMGetRelocationSymbol, LLVMGetRelocationType, LLVMGetRelocationTypeName, LLVMGetRelocationValueString, LLVMMoveToNextSymbol, LLVMMoveToNextRelocation, LLVMIsSymbolIteratorAtEnd, LLVMIsRelocationIteratorAtEnd, LLVMGetSymbols};
+//! An `ObjectFile` gives read access to the sections, symbols and relocations of a compiled object.
+
+use llvm_sys::object::{LLVMDisposeObjectFile, LLVMObjectFileRef, LLVMSectionIteratorRef, LLVMGetSections, LLVMDisposeSectionIterator, LLVMSymbolIteratorRef, LLVMIsSectionIteratorAtEnd, LLVMGetSectionName, LLVMDisposeRelocationIterator, LLVMRelocationIteratorRef, LLVMDisposeSymbolIterator, LLVMGetSectionContents, LLVMGetSectionSize, LLVMMoveToNextSection, LLVMGetSectionAddress, LLVMGetSectionContainsSymbol, LLVMGetSymbolName, LLVMGetSymbolSize, LLVMGetRelocations, LLVMGetSymbolAddress, LLVMGetRelocationOffset, LLVMGetRelocationSymbol, LLVMGetRelocationType, LLVMMoveToContainingSection, LLVMMoveToNextSymbol, LLVMMoveToNextRelocation, LLVMIsSymbolIteratorAtEnd, LLVMIsRelocationIteratorAtEnd, LLVMGetSymbols, LLVMGetRelocationValueString};
+
+use crate::memory_buffer::MemoryBuffer;
+use crate::support::LLVMString;
 
 use std::ffi::CStr;
+use std::path::Path;
+use std::rc::Rc;
+
+// LLVM's object iterators are cursors: advancing one changes what every handle to it refers to.
+// Iterators therefore copy what each Section, Symbol and Relocation needs out of their cursor and
+// advance it in place. The few methods which need an LLVM cursor of their own, such as
+// Section::get_relocations, position a new one when they are called.
+
+/// The architecture an `ObjectFile` was compiled for, as far as it matters to decode relocations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Machine {
+    ElfX86_64,
+    ElfAArch64,
+    Unknown,
+}
+
+impl Machine {
+    fn from_header(header: &[u8]) -> Self {
+        const EM_X86_64: u16 = 62;
+        const EM_AARCH64: u16 = 183;
+
+        if header.len() < 20 || header[..4] != *b"\x7fELF" {
+            return Machine::Unknown;
+        }
+
+        // e_ident[EI_DATA] tells us the endianness of e_machine
+        let e_machine = match header[5] {
+            1 => u16::from_le_bytes([header[18], header[19]]),
+            2 => u16::from_be_bytes([header[18], header[19]]),
+            _ => return Machine::Unknown,
+        };
 
-// REVIEW: Make sure SectionIterator's object_file ptr doesn't outlive ObjectFile
-// REVIEW: This module is very untested
-// TODO: More references to account for lifetimes
+        match e_machine {
+            EM_X86_64 => Machine::ElfX86_64,
+            EM_AARCH64 => Machine::ElfAArch64,
+            _ => Machine::Unknown,
+        }
+    }
+}
+
+/// An object file, such as one emitted by a `TargetMachine`.
 #[derive(Debug)]
 pub struct ObjectFile {
-    object_file: LLVMObjectFileRef
+    object_file: LLVMObjectFileRef,
+    machine: Machine,
+    // The MemoryBuffer owned by object_file
+    contents: *const u8,
+    size: usize,
 }
 
 impl ObjectFile {
-    pub(crate) fn new(object_file: LLVMObjectFileRef) -> Self {
+    // The contents must be those of the MemoryBuffer object_file was created from, which it now owns
+    pub(crate) fn new(object_file: LLVMObjectFileRef, contents: &[u8]) -> Self {
         assert!(!object_file.is_null());
 
         ObjectFile {
-            object_file
+            object_file,
+            machine: Machine::from_header(contents),
+            contents: contents.as_ptr(),
+            size: contents.len(),
         }
     }
 
-    pub fn get_sections(&self) -> SectionIterator {
-        let section_iterator = unsafe {
-            LLVMGetSections(self.object_file)
-        };
+    /// Parses an `ObjectFile` from a copy of the given bytes.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::object_file::ObjectFile;
+    ///
+    /// assert!(ObjectFile::from_bytes(b"not an object file").is_err());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, LLVMString> {
+        let memory_buffer = MemoryBuffer::create_from_memory_range_copy(bytes, "object_file");
+
+        memory_buffer.create_object_file()
+                     .map_err(|()| LLVMString::create_from_str("The given bytes are not a valid object file\0"))
+    }
 
-        SectionIterator::new(section_iterator, self.object_file)
+    /// Reads and parses an `ObjectFile` from a file on disk.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::object_file::ObjectFile;
+    ///
+    /// let object_file = ObjectFile::from_path("foo/bar.o").unwrap();
+    ///
+    /// for symbol in object_file.get_symbols() {
+    ///     println!("{:?}", symbol.get_name());
+    /// }
+    /// ```
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, LLVMString> {
+        let memory_buffer = MemoryBuffer::create_from_file(path.as_ref())?;
+
+        memory_buffer.create_object_file()
+                     .map_err(|()| LLVMString::create_from_str("The given file is not a valid object file\0"))
     }
 
-    pub fn get_symbols(&self) -> SymbolIterator {
-        let symbol_iterator = unsafe {
-            LLVMGetSymbols(self.object_file)
-        };
+    /// Gets the raw bytes of this `ObjectFile`.
+    pub fn as_slice(&self) -> &[u8] {
+        unsafe {
+            std::slice::from_raw_parts(self.contents, self.size)
+        }
+    }
 
-        SymbolIterator::new(symbol_iterator, self.object_file)
+    /// Iterates over the sections of this `ObjectFile`.
+    pub fn get_sections(&self) -> SectionIterator {
+        SectionIterator {
+            object_file: self,
+            cursor: SectionCursor::new(self),
+            index: 0,
+        }
+    }
+
+    /// Iterates over the symbols of this `ObjectFile`.
+    pub fn get_symbols(&self) -> SymbolIterator {
+        SymbolIterator {
+            object_file: self,
+            cursor: SymbolCursor::new(self),
+            index: 0,
+        }
     }
 }
 
@@ -45,305 +140,573 @@ impl Drop for ObjectFile {
 }
 
 #[derive(Debug)]
-pub struct SectionIterator {
-    section_iterator: LLVMSectionIteratorRef,
-    object_file: LLVMObjectFileRef,
-    before_first: bool,
-}
+struct SectionCursor(LLVMSectionIteratorRef);
+
+impl SectionCursor {
+    fn new(object_file: &ObjectFile) -> Self {
+        let section_iterator = unsafe {
+            LLVMGetSections(object_file.object_file)
+        };
 
-impl SectionIterator {
-    fn new(section_iterator: LLVMSectionIteratorRef, object_file: LLVMObjectFileRef) -> Self {
         assert!(!section_iterator.is_null());
 
-        SectionIterator {
-            section_iterator,
-            object_file,
-            before_first: true
-        }
+        SectionCursor(section_iterator)
     }
-}
 
-impl Iterator for SectionIterator {
-    type Item = Section;
+    fn at(object_file: &ObjectFile, index: usize) -> Self {
+        let cursor = SectionCursor::new(object_file);
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.before_first {
-            self.before_first = false;
-        } else {
+        for _ in 0..index {
             unsafe {
-                LLVMMoveToNextSection(self.section_iterator);
+                LLVMMoveToNextSection(cursor.0);
             }
         }
 
-        let at_end = unsafe {
-            LLVMIsSectionIteratorAtEnd(self.object_file, self.section_iterator) == 1
+        cursor
+    }
+
+    fn is_at_end(&self, object_file: &ObjectFile) -> bool {
+        unsafe {
+            LLVMIsSectionIteratorAtEnd(object_file.object_file, self.0) == 1
+        }
+    }
+}
+
+impl Drop for SectionCursor {
+    fn drop(&mut self) {
+        unsafe {
+            LLVMDisposeSectionIterator(self.0)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct SymbolCursor(LLVMSymbolIteratorRef);
+
+impl SymbolCursor {
+    fn new(object_file: &ObjectFile) -> Self {
+        let symbol_iterator = unsafe {
+            LLVMGetSymbols(object_file.object_file)
         };
 
-        if at_end {
-            return None;
+        assert!(!symbol_iterator.is_null());
+
+        SymbolCursor(symbol_iterator)
+    }
+
+    fn at(object_file: &ObjectFile, index: usize) -> Self {
+        let cursor = SymbolCursor::new(object_file);
+
+        for _ in 0..index {
+            unsafe {
+                LLVMMoveToNextSymbol(cursor.0);
+            }
         }
 
-        let section = Section::new(self.section_iterator, self.object_file);
+        cursor
+    }
 
-        Some(section)
+    fn is_at_end(&self, object_file: &ObjectFile) -> bool {
+        unsafe {
+            LLVMIsSymbolIteratorAtEnd(object_file.object_file, self.0) == 1
+        }
     }
 }
 
-impl Drop for SectionIterator {
+impl Drop for SymbolCursor {
     fn drop(&mut self) {
         unsafe {
-            LLVMDisposeSectionIterator(self.section_iterator)
+            LLVMDisposeSymbolIterator(self.0)
         }
     }
 }
 
 #[derive(Debug)]
-pub struct Section {
-    section: LLVMSectionIteratorRef,
-    object_file: LLVMObjectFileRef,
-}
+struct RelocationCursor(LLVMRelocationIteratorRef);
 
-impl Section {
-    fn new(section: LLVMSectionIteratorRef, object_file: LLVMObjectFileRef) -> Self {
-        assert!(!section.is_null());
+impl RelocationCursor {
+    fn at(section: &SectionCursor, index: usize) -> Self {
+        let relocation_iterator = unsafe {
+            LLVMGetRelocations(section.0)
+        };
 
-        Section {
-            section,
-            object_file,
+        assert!(!relocation_iterator.is_null());
+
+        for _ in 0..index {
+            unsafe {
+                LLVMMoveToNextRelocation(relocation_iterator);
+            }
         }
+
+        RelocationCursor(relocation_iterator)
     }
 
-    pub fn get_name(&self) -> Option<&CStr> {
-        let name = unsafe {
-            LLVMGetSectionName(self.section)
-        };
-        if !name.is_null() {
-            Some(unsafe { CStr::from_ptr(name) })
-        } else {
-            None
+    fn is_at_end(&self, section: &SectionCursor) -> bool {
+        unsafe {
+            LLVMIsRelocationIteratorAtEnd(section.0, self.0) == 1
         }
     }
+}
 
-    pub fn size(&self) -> u64 {
+impl Drop for RelocationCursor {
+    fn drop(&mut self) {
         unsafe {
-            LLVMGetSectionSize(self.section)
+            LLVMDisposeRelocationIterator(self.0)
         }
     }
+}
 
-    pub fn get_contents(&self) -> &[u8] {
-        unsafe {
-            std::slice::from_raw_parts(
-                LLVMGetSectionContents(self.section) as *const u8,
-                self.size() as usize)
+/// Where a `Section` or `Symbol` is, so that a cursor pointing at it can be created on demand.
+#[derive(Debug)]
+enum Location<C> {
+    /// The position an iterator yielded it at.
+    Index(usize),
+    /// A cursor LLVM positioned on it, which no iterator advances.
+    Cursor(Rc<C>),
+}
+
+impl<C> Clone for Location<C> {
+    fn clone(&self) -> Self {
+        match self {
+            Location::Index(index) => Location::Index(*index),
+            Location::Cursor(cursor) => Location::Cursor(cursor.clone()),
         }
     }
+}
+
+/// An iterator over the sections of an `ObjectFile`.
+#[derive(Debug)]
+pub struct SectionIterator<'a> {
+    object_file: &'a ObjectFile,
+    cursor: SectionCursor,
+    index: usize,
+}
+
+impl<'a> Iterator for SectionIterator<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor.is_at_end(self.object_file) {
+            return None;
+        }
+
+        let section = Section::new(self.cursor.0, self.object_file, Location::Index(self.index));
 
-    pub fn get_address(&self) -> u64 {
         unsafe {
-            LLVMGetSectionAddress(self.section)
+            LLVMMoveToNextSection(self.cursor.0);
         }
-    }
 
-    pub fn get_relocations(&self) -> RelocationIterator {
-        let relocation_iterator = unsafe {
-            LLVMGetRelocations(self.section)
-        };
+        self.index += 1;
 
-        RelocationIterator::new(relocation_iterator, self.section, self.object_file)
+        Some(section)
     }
 }
 
+/// A section of an `ObjectFile`.
 #[derive(Debug)]
-pub struct RelocationIterator {
-    relocation_iterator: LLVMRelocationIteratorRef,
-    section_iterator: LLVMSectionIteratorRef,
-    object_file: LLVMObjectFileRef,
-    before_first: bool,
+pub struct Section<'a> {
+    name: Option<&'a CStr>,
+    size: u64,
+    address: u64,
+    contents: *const ::libc::c_char,
+    location: Location<SectionCursor>,
+    object_file: &'a ObjectFile,
 }
 
-impl RelocationIterator {
-    fn new(relocation_iterator: LLVMRelocationIteratorRef, section_iterator: LLVMSectionIteratorRef, object_file: LLVMObjectFileRef) -> Self {
-        assert!(!relocation_iterator.is_null());
+impl<'a> Section<'a> {
+    fn new(cursor: LLVMSectionIteratorRef, object_file: &'a ObjectFile, location: Location<SectionCursor>) -> Self {
+        // The name and contents point into the object file, so they remain valid as the cursor moves on
+        let name = unsafe {
+            LLVMGetSectionName(cursor)
+        };
 
-        RelocationIterator {
-            relocation_iterator,
-            section_iterator,
+        Section {
+            name: if name.is_null() { None } else { Some(unsafe { CStr::from_ptr(name) }) },
+            size: unsafe { LLVMGetSectionSize(cursor) },
+            address: unsafe { LLVMGetSectionAddress(cursor) },
+            contents: unsafe { LLVMGetSectionContents(cursor) },
+            location,
             object_file,
-            before_first: true
         }
     }
-}
 
-impl Iterator for RelocationIterator {
-    type Item = Relocation;
+    fn cursor(&self) -> Rc<SectionCursor> {
+        match self.location {
+            Location::Index(index) => Rc::new(SectionCursor::at(self.object_file, index)),
+            Location::Cursor(ref cursor) => cursor.clone(),
+        }
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.before_first {
-            self.before_first = false;
-        } else {
-            unsafe {
-                LLVMMoveToNextRelocation(self.relocation_iterator)
-            }
+    /// Gets the name of this section, if it has one.
+    pub fn get_name(&self) -> Option<&CStr> {
+        self.name
+    }
+
+    /// Gets the size of this section in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Gets the contents of this section.
+    pub fn get_contents(&self) -> &[u8] {
+        unsafe {
+            std::slice::from_raw_parts(self.contents as *const u8, self.size as usize)
         }
+    }
 
-        let at_end = unsafe {
-            LLVMIsRelocationIteratorAtEnd(self.section_iterator, self.relocation_iterator) == 1
-        };
+    /// Gets the address of this section.
+    pub fn get_address(&self) -> u64 {
+        self.address
+    }
 
-        if at_end {
-            return None;
+    /// Determines whether the given `Symbol` is defined in this section.
+    ///
+    /// LLVM needs cursors to both, which are positioned anew by walking the object file.
+    pub fn contains_symbol(&self, symbol: &Symbol) -> bool {
+        unsafe {
+            LLVMGetSectionContainsSymbol(self.cursor().0, symbol.cursor().0) == 1
         }
+    }
 
-        let relocation = Relocation::new(self.relocation_iterator, self.object_file);
+    /// Iterates over the relocations applied to this section.
+    pub fn get_relocations(&self) -> RelocationIterator<'a> {
+        let section = self.cursor();
 
-        Some(relocation)
+        RelocationIterator {
+            cursor: RelocationCursor::at(&section, 0),
+            section,
+            object_file: self.object_file,
+            index: 0,
+        }
     }
 }
 
-impl Drop for RelocationIterator {
-    fn drop(&mut self) {
+/// An iterator over the relocations of a `Section`.
+#[derive(Debug)]
+pub struct RelocationIterator<'a> {
+    section: Rc<SectionCursor>,
+    object_file: &'a ObjectFile,
+    cursor: RelocationCursor,
+    index: usize,
+}
+
+impl<'a> Iterator for RelocationIterator<'a> {
+    type Item = Relocation<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cursor.is_at_end(&self.section) {
+            return None;
+        }
+
+        let relocation = Relocation::new(&self.cursor, self.section.clone(), self.index, self.object_file);
+
         unsafe {
-            LLVMDisposeRelocationIterator(self.relocation_iterator)
+            LLVMMoveToNextRelocation(self.cursor.0);
         }
+
+        self.index += 1;
+
+        Some(relocation)
     }
 }
 
+/// A relocation of a `Section`.
 #[derive(Debug)]
-pub struct Relocation {
-    relocation: LLVMRelocationIteratorRef,
-    object_file: LLVMObjectFileRef,
+pub struct Relocation<'a> {
+    offset: u64,
+    raw_type: u64,
+    symbol: Option<Symbol<'a>>,
+    section: Rc<SectionCursor>,
+    index: usize,
+    object_file: &'a ObjectFile,
 }
 
-impl Relocation {
-    fn new(relocation: LLVMRelocationIteratorRef, object_file: LLVMObjectFileRef) -> Self {
-        assert!(!relocation.is_null());
+impl<'a> Relocation<'a> {
+    fn new(cursor: &RelocationCursor, section: Rc<SectionCursor>, index: usize, object_file: &'a ObjectFile) -> Self {
+        // LLVM gives back a new cursor for the symbol rather than a position
+        let symbol = unsafe {
+            SymbolCursor(LLVMGetRelocationSymbol(cursor.0))
+        };
+        let symbol = if symbol.is_at_end(object_file) {
+            None
+        } else {
+            Some(Symbol::new(symbol.0, object_file, Location::Cursor(Rc::new(symbol))))
+        };
 
         Relocation {
-            relocation,
+            offset: unsafe { LLVMGetRelocationOffset(cursor.0) },
+            raw_type: unsafe { LLVMGetRelocationType(cursor.0) },
+            symbol,
+            section,
+            index,
             object_file,
         }
     }
 
+    /// Gets the offset of this relocation within its section.
     pub fn get_offset(&self) -> u64 {
-        unsafe {
-            LLVMGetRelocationOffset(self.relocation)
-        }
+        self.offset
     }
 
-    pub fn get_symbols(&self) -> SymbolIterator {
-        let symbol_iterator = unsafe {
-            // REVIEW: Is this just returning a single Symbol (given the name) and not a full iterator?
-            LLVMGetRelocationSymbol(self.relocation)
-        };
-
-        SymbolIterator::new(symbol_iterator, self.object_file)
+    /// Gets the symbol this relocation refers to, if any.
+    pub fn get_symbol(&self) -> Option<Symbol<'a>> {
+        self.symbol.clone()
     }
 
-    pub fn get_type(&self) -> (u64, &CStr) {
-        let type_int = unsafe {
-            LLVMGetRelocationType(self.relocation)
-        };
-        let type_name = unsafe {
-            CStr::from_ptr(LLVMGetRelocationTypeName(self.relocation))
-        };
+    /// Gets the raw, object format and architecture specific type of this relocation.
+    pub fn get_raw_type(&self) -> u64 {
+        self.raw_type
+    }
 
-        (type_int, type_name)
+    /// Gets the type of this relocation. Types which are not known to inkwell yet
+    /// are returned as `RelocationType::Other`.
+    pub fn get_type(&self) -> RelocationType {
+        RelocationType::new(self.object_file.machine, self.raw_type)
     }
 
-    pub fn get_value(&self) -> &CStr {
+    /// Gets LLVM's textual description of the value this relocation computes. LLVM stopped
+    /// computing these in 3.7 and has returned an empty string ever since.
+    #[llvm_versions(3.7..=latest)]
+    pub fn get_value(&self) -> LLVMString {
+        let cursor = RelocationCursor::at(&self.section, self.index);
+
         unsafe {
-            CStr::from_ptr(LLVMGetRelocationValueString(self.relocation))
+            LLVMString::new(LLVMGetRelocationValueString(cursor.0))
         }
     }
 }
 
-#[derive(Debug)]
-pub struct SymbolIterator {
-    symbol_iterator: LLVMSymbolIteratorRef,
-    object_file: LLVMObjectFileRef,
-    before_first: bool,
+/// The type of a `Relocation`. Relocation types are currently only decoded for
+/// x86-64 and AArch64 ELF object files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RelocationType {
+    /// `R_X86_64_NONE`
+    X86_64None,
+    /// `R_X86_64_64`
+    X86_64_64,
+    /// `R_X86_64_PC32`
+    X86_64Pc32,
+    /// `R_X86_64_GOT32`
+    X86_64Got32,
+    /// `R_X86_64_PLT32`
+    X86_64Plt32,
+    /// `R_X86_64_COPY`
+    X86_64Copy,
+    /// `R_X86_64_GLOB_DAT`
+    X86_64GlobDat,
+    /// `R_X86_64_JUMP_SLOT`
+    X86_64JumpSlot,
+    /// `R_X86_64_RELATIVE`
+    X86_64Relative,
+    /// `R_X86_64_GOTPCREL`
+    X86_64GotPcRel,
+    /// `R_X86_64_32`
+    X86_64_32,
+    /// `R_X86_64_32S`
+    X86_64_32S,
+    /// `R_X86_64_16`
+    X86_64_16,
+    /// `R_X86_64_PC16`
+    X86_64Pc16,
+    /// `R_X86_64_8`
+    X86_64_8,
+    /// `R_X86_64_PC8`
+    X86_64Pc8,
+    /// `R_X86_64_TLSGD`
+    X86_64TlsGd,
+    /// `R_X86_64_TLSLD`
+    X86_64TlsLd,
+    /// `R_X86_64_DTPOFF32`
+    X86_64DtpOff32,
+    /// `R_X86_64_GOTTPOFF`
+    X86_64GotTpOff,
+    /// `R_X86_64_TPOFF32`
+    X86_64TpOff32,
+    /// `R_X86_64_PC64`
+    X86_64Pc64,
+    /// `R_X86_64_GOTPCRELX`
+    X86_64GotPcRelX,
+    /// `R_X86_64_REX_GOTPCRELX`
+    X86_64RexGotPcRelX,
+    /// `R_AARCH64_NONE`
+    AArch64None,
+    /// `R_AARCH64_ABS64`
+    AArch64Abs64,
+    /// `R_AARCH64_ABS32`
+    AArch64Abs32,
+    /// `R_AARCH64_ABS16`
+    AArch64Abs16,
+    /// `R_AARCH64_PREL64`
+    AArch64Prel64,
+    /// `R_AARCH64_PREL32`
+    AArch64Prel32,
+    /// `R_AARCH64_PREL16`
+    AArch64Prel16,
+    /// `R_AARCH64_ADR_PREL_PG_HI21`
+    AArch64AdrPrelPgHi21,
+    /// `R_AARCH64_ADD_ABS_LO12_NC`
+    AArch64AddAbsLo12Nc,
+    /// `R_AARCH64_LDST8_ABS_LO12_NC`
+    AArch64Ldst8AbsLo12Nc,
+    /// `R_AARCH64_JUMP26`
+    AArch64Jump26,
+    /// `R_AARCH64_CALL26`
+    AArch64Call26,
+    /// `R_AARCH64_LDST16_ABS_LO12_NC`
+    AArch64Ldst16AbsLo12Nc,
+    /// `R_AARCH64_LDST32_ABS_LO12_NC`
+    AArch64Ldst32AbsLo12Nc,
+    /// `R_AARCH64_LDST64_ABS_LO12_NC`
+    AArch64Ldst64AbsLo12Nc,
+    /// `R_AARCH64_LDST128_ABS_LO12_NC`
+    AArch64Ldst128AbsLo12Nc,
+    /// `R_AARCH64_ADR_GOT_PAGE`
+    AArch64AdrGotPage,
+    /// `R_AARCH64_LD64_GOT_LO12_NC`
+    AArch64Ld64GotLo12Nc,
+    /// A relocation type which isn't decoded, along with its raw value.
+    Other(u64),
 }
 
-impl SymbolIterator {
-    fn new(symbol_iterator: LLVMSymbolIteratorRef, object_file: LLVMObjectFileRef) -> Self {
-        assert!(!symbol_iterator.is_null());
-
-        SymbolIterator {
-            symbol_iterator,
-            object_file,
-            before_first: true
+impl RelocationType {
+    fn new(machine: Machine, raw_type: u64) -> Self {
+        match (machine, raw_type) {
+            (Machine::ElfX86_64, 0) => RelocationType::X86_64None,
+            (Machine::ElfX86_64, 1) => RelocationType::X86_64_64,
+            (Machine::ElfX86_64, 2) => RelocationType::X86_64Pc32,
+            (Machine::ElfX86_64, 3) => RelocationType::X86_64Got32,
+            (Machine::ElfX86_64, 4) => RelocationType::X86_64Plt32,
+            (Machine::ElfX86_64, 5) => RelocationType::X86_64Copy,
+            (Machine::ElfX86_64, 6) => RelocationType::X86_64GlobDat,
+            (Machine::ElfX86_64, 7) => RelocationType::X86_64JumpSlot,
+            (Machine::ElfX86_64, 8) => RelocationType::X86_64Relative,
+            (Machine::ElfX86_64, 9) => RelocationType::X86_64GotPcRel,
+            (Machine::ElfX86_64, 10) => RelocationType::X86_64_32,
+            (Machine::ElfX86_64, 11) => RelocationType::X86_64_32S,
+            (Machine::ElfX86_64, 12) => RelocationType::X86_64_16,
+            (Machine::ElfX86_64, 13) => RelocationType::X86_64Pc16,
+            (Machine::ElfX86_64, 14) => RelocationType::X86_64_8,
+            (Machine::ElfX86_64, 15) => RelocationType::X86_64Pc8,
+            (Machine::ElfX86_64, 19) => RelocationType::X86_64TlsGd,
+            (Machine::ElfX86_64, 20) => RelocationType::X86_64TlsLd,
+            (Machine::ElfX86_64, 21) => RelocationType::X86_64DtpOff32,
+            (Machine::ElfX86_64, 22) => RelocationType::X86_64GotTpOff,
+            (Machine::ElfX86_64, 23) => RelocationType::X86_64TpOff32,
+            (Machine::ElfX86_64, 24) => RelocationType::X86_64Pc64,
+            (Machine::ElfX86_64, 41) => RelocationType::X86_64GotPcRelX,
+            (Machine::ElfX86_64, 42) => RelocationType::X86_64RexGotPcRelX,
+            (Machine::ElfAArch64, 0) => RelocationType::AArch64None,
+            (Machine::ElfAArch64, 257) => RelocationType::AArch64Abs64,
+            (Machine::ElfAArch64, 258) => RelocationType::AArch64Abs32,
+            (Machine::ElfAArch64, 259) => RelocationType::AArch64Abs16,
+            (Machine::ElfAArch64, 260) => RelocationType::AArch64Prel64,
+            (Machine::ElfAArch64, 261) => RelocationType::AArch64Prel32,
+            (Machine::ElfAArch64, 262) => RelocationType::AArch64Prel16,
+            (Machine::ElfAArch64, 275) => RelocationType::AArch64AdrPrelPgHi21,
+            (Machine::ElfAArch64, 277) => RelocationType::AArch64AddAbsLo12Nc,
+            (Machine::ElfAArch64, 278) => RelocationType::AArch64Ldst8AbsLo12Nc,
+            (Machine::ElfAArch64, 282) => RelocationType::AArch64Jump26,
+            (Machine::ElfAArch64, 283) => RelocationType::AArch64Call26,
+            (Machine::ElfAArch64, 284) => RelocationType::AArch64Ldst16AbsLo12Nc,
+            (Machine::ElfAArch64, 285) => RelocationType::AArch64Ldst32AbsLo12Nc,
+            (Machine::ElfAArch64, 286) => RelocationType::AArch64Ldst64AbsLo12Nc,
+            (Machine::ElfAArch64, 299) => RelocationType::AArch64Ldst128AbsLo12Nc,
+            (Machine::ElfAArch64, 311) => RelocationType::AArch64AdrGotPage,
+            (Machine::ElfAArch64, 312) => RelocationType::AArch64Ld64GotLo12Nc,
+            (_, raw_type) => RelocationType::Other(raw_type),
         }
     }
 }
 
-impl Iterator for SymbolIterator {
-    type Item = Symbol;
+/// An iterator over the symbols of an `ObjectFile`.
+#[derive(Debug)]
+pub struct SymbolIterator<'a> {
+    object_file: &'a ObjectFile,
+    cursor: SymbolCursor,
+    index: usize,
+}
+
+impl<'a> Iterator for SymbolIterator<'a> {
+    type Item = Symbol<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.before_first {
-            self.before_first = false;
-        } else {
-            unsafe {
-                LLVMMoveToNextSymbol(self.symbol_iterator)
-            }
+        if self.cursor.is_at_end(self.object_file) {
+            return None;
         }
 
-        let at_end = unsafe {
-            LLVMIsSymbolIteratorAtEnd(self.object_file, self.symbol_iterator) == 1
-        };
+        let symbol = Symbol::new(self.cursor.0, self.object_file, Location::Index(self.index));
 
-        if at_end {
-            return None;
+        unsafe {
+            LLVMMoveToNextSymbol(self.cursor.0);
         }
 
-        let symbol = Symbol::new(self.symbol_iterator);
+        self.index += 1;
 
         Some(symbol)
     }
 }
 
-impl Drop for SymbolIterator {
-    fn drop(&mut self) {
-        unsafe {
-            LLVMDisposeSymbolIterator(self.symbol_iterator)
-        }
-    }
-}
-
-#[derive(Debug)]
-pub struct Symbol {
-    symbol: LLVMSymbolIteratorRef,
+/// A symbol of an `ObjectFile`.
+#[derive(Debug, Clone)]
+pub struct Symbol<'a> {
+    name: Option<&'a CStr>,
+    size: u64,
+    address: u64,
+    location: Location<SymbolCursor>,
+    object_file: &'a ObjectFile,
 }
 
-impl Symbol {
-    fn new(symbol: LLVMSymbolIteratorRef) -> Self {
-        assert!(!symbol.is_null());
+impl<'a> Symbol<'a> {
+    fn new(cursor: LLVMSymbolIteratorRef, object_file: &'a ObjectFile, location: Location<SymbolCursor>) -> Self {
+        // The name points into the object file's string table, so it remains valid as the cursor moves on
+        let name = unsafe {
+            LLVMGetSymbolName(cursor)
+        };
 
         Symbol {
-            symbol,
+            name: if name.is_null() { None } else { Some(unsafe { CStr::from_ptr(name) }) },
+            size: unsafe { LLVMGetSymbolSize(cursor) },
+            address: unsafe { LLVMGetSymbolAddress(cursor) },
+            location,
+            object_file,
         }
     }
 
-    pub fn get_name(&self) -> Option<&CStr> {
-        let name = unsafe {
-            LLVMGetSymbolName(self.symbol)
-        };
-        if !name.is_null() {
-            Some(unsafe { CStr::from_ptr(name) })
-        } else {
-            None
+    fn cursor(&self) -> Rc<SymbolCursor> {
+        match self.location {
+            Location::Index(index) => Rc::new(SymbolCursor::at(self.object_file, index)),
+            Location::Cursor(ref cursor) => cursor.clone(),
         }
     }
 
+    /// Gets the name of this symbol, if it has one.
+    pub fn get_name(&self) -> Option<&CStr> {
+        self.name
+    }
+
+    /// Gets the size of this symbol in bytes.
     pub fn size(&self) -> u64 {
-        unsafe {
-            LLVMGetSymbolSize(self.symbol)
-        }
+        self.size
     }
 
+    /// Gets the address of this symbol.
     pub fn get_address(&self) -> u64 {
+        self.address
+    }
+
+    /// Gets the section this symbol is defined in. Returns `None` for undefined,
+    /// absolute and common symbols.
+    pub fn get_section(&self) -> Option<Section<'a>> {
+        let cursor = SectionCursor::new(self.object_file);
+
         unsafe {
-            LLVMGetSymbolAddress(self.symbol)
+            LLVMMoveToContainingSection(cursor.0, self.cursor().0);
+        }
+
+        if cursor.is_at_end(self.object_file) {
+            return None;
         }
+
+        Some(Section::new(cursor.0, self.object_file, Location::Cursor(Rc::new(cursor))))
     }
 }
@@ -18,6 +18,11 @@ use std::borrow::Borrow;
 use std::marker::PhantomData;
 
 // REVIEW: Opt Level might be identical to targets::Option<CodeGenOptLevel>
+// NOTE: The new pass manager (textual pipelines such as "default<O2>", along with its loop
+// vectorization, SLP and verify-each options) is only exposed to C through
+// llvm-c/Transforms/PassBuilder.h, which first shipped in LLVM 13. A `Module::run_passes`
+// wrapper can be added once an llvm13 feature exists; until then, the legacy pass manager
+// is the only one available for every LLVM version this crate supports.
 #[derive(Debug)]
 pub struct PassManagerBuilder {
     pass_manager_builder: LLVMPassManagerBuilderRef,
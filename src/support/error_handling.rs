@@ -5,10 +5,14 @@ use llvm_sys::core::{LLVMInstallFatalErrorHandler, LLVMResetFatalErrorHandler};
 #[llvm_versions(3.8..=latest)]
 use llvm_sys::error_handling::{LLVMInstallFatalErrorHandler, LLVMResetFatalErrorHandler};
 use llvm_sys::core::{LLVMGetDiagInfoDescription, LLVMGetDiagInfoSeverity};
-use llvm_sys::prelude::LLVMDiagnosticInfoRef;
+use llvm_sys::prelude::{LLVMContextRef, LLVMDiagnosticInfoRef};
 use llvm_sys::LLVMDiagnosticSeverity;
 use libc::c_void;
 
+use crate::support::LLVMString;
+
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
 // REVIEW: Maybe it's possible to have a safe wrapper? If we can
 // wrap the provided function input ptr into a &CStr somehow
 // TODOC: Can be used like this:
@@ -33,7 +37,28 @@ pub fn reset_fatal_error_handler() {
     }
 }
 
-pub(crate) struct DiagnosticInfo {
+/// The severity of a `DiagnosticInfo`.
+#[llvm_enum(LLVMDiagnosticSeverity)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DiagnosticSeverity {
+    /// An error, such as an invalid inline assembly instruction.
+    #[llvm_variant(LLVMDSError)]
+    Error,
+    /// A warning, such as a stack frame exceeding the size given by `warn-stack-size`.
+    #[llvm_variant(LLVMDSWarning)]
+    Warning,
+    /// An optimization remark.
+    #[llvm_variant(LLVMDSRemark)]
+    Remark,
+    /// A note which adds information to a preceding diagnostic.
+    #[llvm_variant(LLVMDSNote)]
+    Note,
+}
+
+/// A diagnostic emitted by LLVM, such as an optimization remark, a backend warning or an
+/// inline assembly error. It is only valid for the duration of a diagnostic handler call.
+#[derive(Debug)]
+pub struct DiagnosticInfo {
     diagnostic_info: LLVMDiagnosticInfoRef,
 }
 
@@ -44,19 +69,29 @@ impl DiagnosticInfo {
         }
     }
 
-    pub(crate) fn get_description(&self) -> *mut ::libc::c_char {
+    /// Gets the description of this diagnostic, which is the message LLVM would otherwise
+    /// print to stderr.
+    pub fn get_description(&self) -> LLVMString {
+        LLVMString::new(self.description_ptr())
+    }
+
+    /// Gets the severity of this diagnostic.
+    pub fn get_severity(&self) -> DiagnosticSeverity {
+        let severity = unsafe {
+            LLVMGetDiagInfoSeverity(self.diagnostic_info)
+        };
+
+        DiagnosticSeverity::new(severity)
+    }
+
+    fn description_ptr(&self) -> *mut ::libc::c_char {
         unsafe {
             LLVMGetDiagInfoDescription(self.diagnostic_info)
         }
     }
 
     pub(crate) fn severity_is_error(&self) -> bool {
-        unsafe {
-            match LLVMGetDiagInfoSeverity(self.diagnostic_info) {
-                LLVMDiagnosticSeverity::LLVMDSError => true,
-                _ => false,
-            }
-        }
+        self.get_severity() == DiagnosticSeverity::Error
     }
 }
 
@@ -72,7 +107,29 @@ pub(crate) extern "C" fn get_error_str_diagnostic_handler(diagnostic_info: LLVMD
         let c_ptr_ptr = void_ptr as *mut *mut c_void as *mut *mut ::libc::c_char;
 
         unsafe {
-            *c_ptr_ptr = diagnostic_info.get_description();
+            *c_ptr_ptr = diagnostic_info.description_ptr();
         }
     }
 }
+
+pub(crate) type DiagnosticHandler = Box<dyn FnMut(&DiagnosticInfo) + Send>;
+pub(crate) type YieldCallback = Box<dyn FnMut() + Send>;
+
+// Assumptions this handler makes:
+// * A valid *mut DiagnosticHandler is provided as the void_ptr (via Context::set_diagnostic_handler)
+//   which outlives the handler's installation
+pub(crate) extern "C" fn diagnostic_handler_shim(diagnostic_info: LLVMDiagnosticInfoRef, void_ptr: *mut c_void) {
+    let handler = unsafe { &mut *(void_ptr as *mut DiagnosticHandler) };
+    let diagnostic_info = DiagnosticInfo::new(diagnostic_info);
+
+    // Unwinding into LLVM's stack frames is undefined behavior, so a panicking handler
+    // simply drops the diagnostic
+    let _ = catch_unwind(AssertUnwindSafe(|| handler(&diagnostic_info)));
+}
+
+// Same assumptions as above, for a *mut YieldCallback provided via Context::set_yield_callback
+pub(crate) extern "C" fn yield_callback_shim(_context: LLVMContextRef, void_ptr: *mut c_void) {
+    let callback = unsafe { &mut *(void_ptr as *mut YieldCallback) };
+
+    let _ = catch_unwind(AssertUnwindSafe(callback));
+}
@@ -3,13 +3,14 @@ pub mod error_handling;
 
 use libc::c_char;
 use llvm_sys::core::{LLVMCreateMessage, LLVMDisposeMessage};
-use llvm_sys::support::LLVMLoadLibraryPermanently;
+use llvm_sys::support::{LLVMLoadLibraryPermanently, LLVMParseCommandLineOptions};
 
 use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::ffi::{CString, CStr};
 use std::ops::Deref;
+use std::ptr;
 
 /// An owned LLVM String. Also known as a LLVM Message
 #[derive(Eq)]
@@ -143,6 +144,24 @@ pub fn load_library_permanently(filename: &str) -> bool {
     }
 }
 
+/// Parses options for LLVM's internal command line parser, like clang's `-mllvm`, for example
+/// `"-x86-asm-syntax=intel"`.
+///
+/// These options are global to the process: they affect every `Context`, `TargetMachine` and
+/// pass manager, including ones which already exist. LLVM exits the process if an option is
+/// unknown or malformed, or if an option which may only be given once is parsed a second time.
+pub fn parse_command_line_options(options: &[&str]) {
+    let args: Vec<_> = std::iter::once("inkwell")
+        .chain(options.iter().cloned())
+        .map(to_c_str)
+        .collect();
+    let arg_ptrs: Vec<_> = args.iter().map(|arg| arg.as_ptr()).collect();
+
+    unsafe {
+        LLVMParseCommandLineOptions(arg_ptrs.len() as ::libc::c_int, arg_ptrs.as_ptr(), ptr::null())
+    }
+}
+
 /// Determines whether or not LLVM has been configured to run in multithreaded mode. (Inkwell currently does
 /// not officially support multithreaded mode)
 pub fn is_multithreaded() -> bool {
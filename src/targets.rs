@@ -18,27 +18,38 @@ use llvm_sys::target_machine::{
     LLVMTargetHasTargetMachine, LLVMTargetMachineEmitToFile, LLVMTargetMachineEmitToMemoryBuffer,
     LLVMTargetMachineRef, LLVMTargetRef,
 };
+#[llvm_versions(3.9..=latest)]
+use llvm_sys::core::LLVMIsNull;
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
 
+#[llvm_versions(3.9..=latest)]
+use crate::attributes::AttributeLoc;
 use crate::context::Context;
-use crate::data_layout::DataLayout;
+use crate::data_layout::{DataLayout, StructLayout};
 use crate::memory_buffer::MemoryBuffer;
 use crate::module::Module;
+use crate::object_file::{ObjectFile, SectionIterator, SymbolIterator};
 use crate::passes::PassManager;
 use crate::support::{to_c_str, LLVMString};
 use crate::types::{AnyType, AsTypeRef, IntType, StructType};
+#[llvm_versions(3.9..=latest)]
+use crate::types::BasicTypeEnum;
 use crate::values::{AsValueRef, GlobalValue};
 use crate::{AddressSpace, OptimizationLevel};
 
 use std::default::Default;
+use std::error::Error;
 use std::ffi::CStr;
 use std::fmt;
+use std::fs;
+use std::io;
 use std::mem::MaybeUninit;
 use std::path::Path;
 use std::ptr;
+use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum CodeModel {
     Default,
     JITDefault,
@@ -48,7 +59,7 @@ pub enum CodeModel {
     Large,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum RelocMode {
     Default,
     Static,
@@ -56,6 +67,30 @@ pub enum RelocMode {
     DynamicNoPic,
 }
 
+/// Whether functions keep a frame pointer, which profilers and debuggers may need to unwind the stack.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FramePointer {
+    /// The frame pointer may be eliminated in any function.
+    None,
+    /// Only functions which call other functions keep a frame pointer.
+    NonLeaf,
+    /// All functions keep a frame pointer.
+    All,
+}
+
+/// Whether floating point operations use the floating point unit.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FloatABI {
+    /// Use the target's default.
+    Default,
+    /// Emulate floating point operations in software, leaving floating point registers untouched.
+    /// On ARM this drops the `hf` from the triple's environment.
+    Soft,
+    /// Use the floating point unit and pass floating point arguments in its registers. On ARM
+    /// this selects the `hf` variant of the triple's environment, such as `gnueabihf`.
+    Hard,
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum FileType {
     Assembly,
@@ -142,6 +177,728 @@ impl fmt::Display for TargetTriple {
     }
 }
 
+impl TargetTriple {
+    /// Parses this `TargetTriple` into its components. See `Triple` for details.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::targets::{Arch, TargetTriple, OS};
+    ///
+    /// let triple = TargetTriple::create("x86_64-pc-linux-gnu").parse().unwrap();
+    ///
+    /// assert_eq!(triple.get_arch(), Arch::X86_64);
+    /// assert_eq!(triple.get_os(), OS::Linux);
+    /// ```
+    pub fn parse(&self) -> Result<Triple, TripleParseError> {
+        let triple = self.as_str().to_str().map_err(|_| TripleParseError::InvalidUtf8)?;
+
+        triple.parse()
+    }
+}
+
+/// Errors which can occur while parsing a `Triple`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum TripleParseError {
+    /// The triple was empty.
+    Empty,
+    /// The triple was not valid UTF-8.
+    InvalidUtf8,
+}
+
+impl Error for TripleParseError {}
+
+impl fmt::Display for TripleParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TripleParseError::Empty => write!(f, "TripleParseError(Triple is empty)"),
+            TripleParseError::InvalidUtf8 => write!(f, "TripleParseError(Triple is not valid UTF-8)"),
+        }
+    }
+}
+
+/// The architecture component of a `Triple`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Arch {
+    /// `aarch64` or `arm64`
+    AArch64,
+    /// `aarch64_be`
+    AArch64BE,
+    /// `aarch64_32` or `arm64_32`
+    AArch64_32,
+    /// `amdgcn`
+    AMDGCN,
+    /// `arm` and its sub architectures, such as `armv7`
+    Arm,
+    /// `armeb` and its sub architectures, such as `armv7eb`
+    ArmEB,
+    /// `avr`
+    AVR,
+    /// `bpfel` or `bpf`
+    BPFEL,
+    /// `bpfeb`
+    BPFEB,
+    /// `hexagon`
+    Hexagon,
+    /// `mips`
+    Mips,
+    /// `mipsel`
+    Mipsel,
+    /// `mips64`
+    Mips64,
+    /// `mips64el`
+    Mips64el,
+    /// `msp430`
+    MSP430,
+    /// `nvptx`
+    NVPTX,
+    /// `nvptx64`
+    NVPTX64,
+    /// `powerpc` or `ppc`
+    PowerPC,
+    /// `powerpc64` or `ppc64`
+    PowerPC64,
+    /// `powerpc64le` or `ppc64le`
+    PowerPC64LE,
+    /// `riscv32`
+    RISCV32,
+    /// `riscv64`
+    RISCV64,
+    /// `sparc`
+    Sparc,
+    /// `sparcel`
+    SparcEL,
+    /// `sparcv9` or `sparc64`
+    Sparcv9,
+    /// `s390x` or `systemz`
+    SystemZ,
+    /// `thumb` and its sub architectures, such as `thumbv7em`
+    Thumb,
+    /// `thumbeb` and its sub architectures
+    ThumbEB,
+    /// `wasm32`
+    Wasm32,
+    /// `wasm64`
+    Wasm64,
+    /// `i386`, `i686` and the like
+    X86,
+    /// `x86_64`, `x86_64h` or `amd64`
+    X86_64,
+    /// Any other architecture, such as `r600`, `xcore` or `spir64`, or an unknown ARM version
+    /// such as `armfoo`
+    Unknown,
+}
+
+impl Arch {
+    // Also returns the sub architecture, which is part of the same component for ARM
+    fn parse(arch: &str) -> (Self, Option<SubArch>) {
+        let arch = match arch {
+            "i386" | "i486" | "i586" | "i686" | "i786" | "i886" | "i986" => Arch::X86,
+            "amd64" | "x86_64" | "x86_64h" => Arch::X86_64,
+            "aarch64" | "arm64" => Arch::AArch64,
+            "aarch64_be" => Arch::AArch64BE,
+            "aarch64_32" | "arm64_32" => Arch::AArch64_32,
+            "amdgcn" => Arch::AMDGCN,
+            "avr" => Arch::AVR,
+            "bpf" | "bpfel" => Arch::BPFEL,
+            "bpfeb" => Arch::BPFEB,
+            "hexagon" => Arch::Hexagon,
+            "mips" | "mipseb" | "mipsallegrex" => Arch::Mips,
+            "mipsel" | "mipsallegrexel" => Arch::Mipsel,
+            "mips64" | "mips64eb" => Arch::Mips64,
+            "mips64el" => Arch::Mips64el,
+            "msp430" => Arch::MSP430,
+            "nvptx" => Arch::NVPTX,
+            "nvptx64" => Arch::NVPTX64,
+            "powerpc" | "ppc" | "ppc32" => Arch::PowerPC,
+            "powerpc64" | "ppu" | "ppc64" => Arch::PowerPC64,
+            "powerpc64le" | "ppc64le" => Arch::PowerPC64LE,
+            "riscv32" => Arch::RISCV32,
+            "riscv64" => Arch::RISCV64,
+            "sparc" => Arch::Sparc,
+            "sparcel" => Arch::SparcEL,
+            "sparcv9" | "sparc64" => Arch::Sparcv9,
+            "s390x" | "systemz" => Arch::SystemZ,
+            "wasm32" => Arch::Wasm32,
+            "wasm64" => Arch::Wasm64,
+            "xscale" => Arch::Arm,
+            "xscaleeb" => Arch::ArmEB,
+            _ => return Arch::parse_arm(arch).unwrap_or((Arch::Unknown, None)),
+        };
+
+        (arch, None)
+    }
+
+    fn parse_arm(arch: &str) -> Option<(Self, Option<SubArch>)> {
+        let (is_thumb, rest) = if let Some(rest) = arch.strip_prefix("thumb") {
+            (true, rest)
+        } else if let Some(rest) = arch.strip_prefix("arm") {
+            (false, rest)
+        } else {
+            return None;
+        };
+        let (is_big_endian, version) = match rest.strip_suffix("eb") {
+            Some(version) => (true, version),
+            None => (false, rest),
+        };
+        // An unknown version such as in armfoo makes the whole architecture unknown, as in LLVM
+        let sub_arch = if version.is_empty() { None } else { Some(SubArch::parse_arm(version)?) };
+        let arch = match (is_thumb, is_big_endian) {
+            (false, false) => Arch::Arm,
+            (false, true) => Arch::ArmEB,
+            (true, false) => Arch::Thumb,
+            (true, true) => Arch::ThumbEB,
+        };
+
+        Some((arch, sub_arch))
+    }
+
+    /// Gets the number of bits in a pointer on this architecture, or 0 for `Arch::Unknown` as in
+    /// LLVM. Note that some environments, such as `gnux32`, use smaller pointers; see
+    /// `Triple::get_pointer_width`.
+    pub fn get_pointer_width(self) -> u32 {
+        match self {
+            Arch::Unknown => 0,
+            Arch::AVR | Arch::MSP430 => 16,
+            Arch::AArch64_32 | Arch::Arm | Arch::ArmEB | Arch::Hexagon | Arch::Mips | Arch::Mipsel | Arch::NVPTX |
+            Arch::PowerPC | Arch::RISCV32 | Arch::Sparc | Arch::SparcEL | Arch::Thumb | Arch::ThumbEB | Arch::Wasm32 |
+            Arch::X86 => 32,
+            Arch::AArch64 | Arch::AArch64BE | Arch::AMDGCN | Arch::BPFEL | Arch::BPFEB | Arch::Mips64 | Arch::Mips64el |
+            Arch::NVPTX64 | Arch::PowerPC64 | Arch::PowerPC64LE | Arch::RISCV64 | Arch::Sparcv9 | Arch::SystemZ |
+            Arch::Wasm64 | Arch::X86_64 => 64,
+        }
+    }
+
+    /// Gets the byte ordering of this architecture. `Arch::Unknown` is assumed to be little endian.
+    pub fn get_byte_ordering(self) -> ByteOrdering {
+        match self {
+            Arch::AArch64BE | Arch::ArmEB | Arch::BPFEB | Arch::Mips | Arch::Mips64 | Arch::PowerPC | Arch::PowerPC64 |
+            Arch::Sparc | Arch::Sparcv9 | Arch::SystemZ | Arch::ThumbEB => ByteOrdering::BigEndian,
+            _ => ByteOrdering::LittleEndian,
+        }
+    }
+}
+
+/// The sub architecture of an ARM `Triple`, such as the `v7em` in `thumbv7em`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum SubArch {
+    /// `v4t`
+    ArmV4T,
+    /// `v5`
+    ArmV5,
+    /// `v5te`
+    ArmV5TE,
+    /// `v6`
+    ArmV6,
+    /// `v6k`
+    ArmV6K,
+    /// `v6m`
+    ArmV6M,
+    /// `v6t2`
+    ArmV6T2,
+    /// `v7` or `v7a`
+    ArmV7,
+    /// `v7em`
+    ArmV7EM,
+    /// `v7k`
+    ArmV7K,
+    /// `v7m`
+    ArmV7M,
+    /// `v7r`
+    ArmV7R,
+    /// `v7s`
+    ArmV7S,
+    /// `v7ve`
+    ArmV7VE,
+    /// `v8` or `v8a`
+    ArmV8,
+    /// `v8.1a`
+    ArmV8_1A,
+    /// `v8.2a`
+    ArmV8_2A,
+    /// `v8.3a`
+    ArmV8_3A,
+    /// `v8.4a`
+    ArmV8_4A,
+    /// `v8.5a`
+    ArmV8_5A,
+    /// `v8m.base`
+    ArmV8MBaseline,
+    /// `v8m.main`
+    ArmV8MMainline,
+    /// `v8.1m.main`
+    ArmV8_1MMainline,
+    /// `v8r`
+    ArmV8R,
+}
+
+impl SubArch {
+    fn parse_arm(version: &str) -> Option<Self> {
+        let sub_arch = match version {
+            "v4t" => SubArch::ArmV4T,
+            "v5" | "v5t" => SubArch::ArmV5,
+            "v5te" | "v5tej" => SubArch::ArmV5TE,
+            "v6" => SubArch::ArmV6,
+            "v6k" | "v6kz" => SubArch::ArmV6K,
+            "v6m" | "v6-m" => SubArch::ArmV6M,
+            "v6t2" => SubArch::ArmV6T2,
+            "v7" | "v7a" | "v7-a" => SubArch::ArmV7,
+            "v7em" | "v7e-m" => SubArch::ArmV7EM,
+            "v7k" => SubArch::ArmV7K,
+            "v7m" | "v7-m" => SubArch::ArmV7M,
+            "v7r" | "v7-r" => SubArch::ArmV7R,
+            "v7s" => SubArch::ArmV7S,
+            "v7ve" => SubArch::ArmV7VE,
+            "v8" | "v8a" | "v8-a" => SubArch::ArmV8,
+            "v8.1a" | "v8.1-a" => SubArch::ArmV8_1A,
+            "v8.2a" | "v8.2-a" => SubArch::ArmV8_2A,
+            "v8.3a" | "v8.3-a" => SubArch::ArmV8_3A,
+            "v8.4a" | "v8.4-a" => SubArch::ArmV8_4A,
+            "v8.5a" | "v8.5-a" => SubArch::ArmV8_5A,
+            "v8m.base" | "v8-m.base" => SubArch::ArmV8MBaseline,
+            "v8m.main" | "v8-m.main" => SubArch::ArmV8MMainline,
+            "v8.1m.main" | "v8.1-m.main" => SubArch::ArmV8_1MMainline,
+            "v8r" | "v8-r" => SubArch::ArmV8R,
+            _ => return None,
+        };
+
+        Some(sub_arch)
+    }
+}
+
+/// The vendor component of a `Triple`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Vendor {
+    /// `amd`
+    AMD,
+    /// `apple`
+    Apple,
+    /// `fsl`
+    Freescale,
+    /// `ibm`
+    IBM,
+    /// `img`
+    ImaginationTechnologies,
+    /// `mesa`
+    Mesa,
+    /// `mti`
+    MipsTechnologies,
+    /// `nvidia`
+    NVIDIA,
+    /// `oe`
+    OpenEmbedded,
+    /// `pc`
+    PC,
+    /// `scei`
+    SCEI,
+    /// `suse`
+    SUSE,
+    /// Any other vendor, including `unknown` and `none`.
+    Unknown,
+}
+
+impl Vendor {
+    fn parse(vendor: &str) -> Option<Self> {
+        let vendor = match vendor {
+            "amd" => Vendor::AMD,
+            "apple" => Vendor::Apple,
+            "fsl" => Vendor::Freescale,
+            "ibm" => Vendor::IBM,
+            "img" => Vendor::ImaginationTechnologies,
+            "mesa" => Vendor::Mesa,
+            "mti" => Vendor::MipsTechnologies,
+            "nvidia" => Vendor::NVIDIA,
+            "oe" => Vendor::OpenEmbedded,
+            "pc" => Vendor::PC,
+            "scei" => Vendor::SCEI,
+            "suse" => Vendor::SUSE,
+            _ => return None,
+        };
+
+        Some(vendor)
+    }
+}
+
+/// The operating system component of a `Triple`. Any version suffix, such as the
+/// `10.15` in `macosx10.15`, is ignored.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum OS {
+    /// `aix`
+    AIX,
+    /// `amdhsa`
+    AMDHSA,
+    /// `cuda`
+    CUDA,
+    /// `darwin`
+    Darwin,
+    /// `dragonfly`
+    DragonFly,
+    /// `emscripten`
+    Emscripten,
+    /// `freebsd`
+    FreeBSD,
+    /// `fuchsia`
+    Fuchsia,
+    /// `haiku`
+    Haiku,
+    /// `hurd`
+    Hurd,
+    /// `ios`
+    IOS,
+    /// `linux`
+    Linux,
+    /// `macos` or `macosx`
+    MacOSX,
+    /// `netbsd`
+    NetBSD,
+    /// `openbsd`
+    OpenBSD,
+    /// `ps4`
+    PS4,
+    /// `solaris`
+    Solaris,
+    /// `tvos`
+    TvOS,
+    /// `wasi`
+    WASI,
+    /// `watchos`
+    WatchOS,
+    /// `windows`, `win32`, or the `mingw32` and `cygwin` spellings which also imply the environment
+    Windows,
+    /// Any other operating system, including `unknown` and `none`.
+    Unknown,
+}
+
+impl OS {
+    fn parse(os: &str) -> Option<Self> {
+        // Order matters where one name is a prefix of another, such as macos and macosx
+        const PREFIXES: &[(&str, OS)] = &[
+            ("aix", OS::AIX),
+            ("amdhsa", OS::AMDHSA),
+            ("cuda", OS::CUDA),
+            ("darwin", OS::Darwin),
+            ("dragonfly", OS::DragonFly),
+            ("emscripten", OS::Emscripten),
+            ("freebsd", OS::FreeBSD),
+            ("fuchsia", OS::Fuchsia),
+            ("haiku", OS::Haiku),
+            ("hurd", OS::Hurd),
+            ("ios", OS::IOS),
+            ("linux", OS::Linux),
+            ("macos", OS::MacOSX),
+            ("netbsd", OS::NetBSD),
+            ("openbsd", OS::OpenBSD),
+            ("ps4", OS::PS4),
+            ("solaris", OS::Solaris),
+            ("tvos", OS::TvOS),
+            ("wasi", OS::WASI),
+            ("watchos", OS::WatchOS),
+            ("windows", OS::Windows),
+            ("win32", OS::Windows),
+            ("mingw32", OS::Windows),
+            ("cygwin", OS::Windows),
+        ];
+
+        PREFIXES.iter().find(|(prefix, _)| os.starts_with(prefix)).map(|&(_, os)| os)
+    }
+
+    /// Determines whether this is one of Apple's operating systems.
+    pub fn is_apple(self) -> bool {
+        match self {
+            OS::Darwin | OS::IOS | OS::MacOSX | OS::TvOS | OS::WatchOS => true,
+            _ => false,
+        }
+    }
+}
+
+/// The environment component of a `Triple`, which usually selects the ABI. Any version
+/// suffix, such as the `21` in `android21`, is ignored.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Environment {
+    /// `android`
+    Android,
+    /// `code16`
+    CODE16,
+    /// `coreclr`
+    CoreCLR,
+    /// `cygnus`
+    Cygnus,
+    /// `eabi`
+    EABI,
+    /// `eabihf`
+    EABIHF,
+    /// `gnu`
+    GNU,
+    /// `gnuabi64`
+    GNUABI64,
+    /// `gnuabin32`
+    GNUABIN32,
+    /// `gnueabi`
+    GNUEABI,
+    /// `gnueabihf`
+    GNUEABIHF,
+    /// `gnux32`
+    GNUX32,
+    /// `itanium`
+    Itanium,
+    /// `macabi`
+    MacABI,
+    /// `msvc`
+    MSVC,
+    /// `musl`
+    Musl,
+    /// `musleabi`
+    MuslEABI,
+    /// `musleabihf`
+    MuslEABIHF,
+    /// `simulator`
+    Simulator,
+    /// Any other environment, or none at all.
+    Unknown,
+}
+
+impl Environment {
+    fn parse(environment: &str) -> Option<Self> {
+        // Longer names come first since they share prefixes with the shorter ones
+        const PREFIXES: &[(&str, Environment)] = &[
+            ("android", Environment::Android),
+            ("code16", Environment::CODE16),
+            ("coreclr", Environment::CoreCLR),
+            ("cygnus", Environment::Cygnus),
+            ("eabihf", Environment::EABIHF),
+            ("eabi", Environment::EABI),
+            ("gnuabi64", Environment::GNUABI64),
+            ("gnuabin32", Environment::GNUABIN32),
+            ("gnueabihf", Environment::GNUEABIHF),
+            ("gnueabi", Environment::GNUEABI),
+            ("gnux32", Environment::GNUX32),
+            ("gnu", Environment::GNU),
+            ("itanium", Environment::Itanium),
+            ("macabi", Environment::MacABI),
+            ("msvc", Environment::MSVC),
+            ("musleabihf", Environment::MuslEABIHF),
+            ("musleabi", Environment::MuslEABI),
+            ("musl", Environment::Musl),
+            ("simulator", Environment::Simulator),
+        ];
+
+        PREFIXES.iter().find(|(prefix, _)| environment.starts_with(prefix)).map(|&(_, environment)| environment)
+    }
+
+    // MinGW and Cygwin are given in place of the OS, which LLVM normalizes to windows-gnu and windows-cygnus
+    fn implied_by_os(os: &str) -> Option<Self> {
+        if os.starts_with("mingw32") {
+            Some(Environment::GNU)
+        } else if os.starts_with("cygwin") {
+            Some(Environment::Cygnus)
+        } else {
+            None
+        }
+    }
+}
+
+/// The object file format code is emitted in for a `Triple`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum ObjectFormat {
+    /// Used by Windows.
+    COFF,
+    /// Used by most other operating systems.
+    ELF,
+    /// Used by Apple's operating systems.
+    MachO,
+    /// Used by WebAssembly.
+    Wasm,
+    /// Used by AIX.
+    XCOFF,
+}
+
+impl ObjectFormat {
+    fn parse(environment: &str) -> Option<Self> {
+        // The format is given as a suffix of the environment component, as in windows-msvc-elf
+        const SUFFIXES: &[(&str, ObjectFormat)] = &[
+            ("xcoff", ObjectFormat::XCOFF),
+            ("coff", ObjectFormat::COFF),
+            ("elf", ObjectFormat::ELF),
+            ("macho", ObjectFormat::MachO),
+            ("wasm", ObjectFormat::Wasm),
+        ];
+
+        SUFFIXES.iter().find(|(suffix, _)| environment.ends_with(suffix)).map(|&(_, format)| format)
+    }
+}
+
+/// A `TargetTriple` parsed into its components, following the rules LLVM uses.
+///
+/// A triple has the form `arch-vendor-os-environment`, although the vendor and environment are
+/// often left out, as in `x86_64-linux-gnu` or `wasm32-wasi`. Components other than the
+/// architecture which aren't recognized are reported as `Unknown` rather than rejected.
+///
+/// The original string is kept, so that `Display` reproduces the triple exactly as it was given.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::targets::{Arch, Environment, ObjectFormat, OS, SubArch, Triple, Vendor};
+///
+/// let triple: Triple = "thumbv7em-none-eabihf".parse().unwrap();
+///
+/// assert_eq!(triple.get_arch(), Arch::Thumb);
+/// assert_eq!(triple.get_sub_arch(), Some(SubArch::ArmV7EM));
+/// assert_eq!(triple.get_vendor(), Vendor::Unknown);
+/// assert_eq!(triple.get_os(), OS::Unknown);
+/// assert_eq!(triple.get_environment(), Environment::EABIHF);
+/// assert_eq!(triple.get_object_format(), ObjectFormat::ELF);
+/// assert_eq!(triple.get_pointer_width(), 32);
+/// assert_eq!(triple.to_string(), "thumbv7em-none-eabihf");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct Triple {
+    triple: String,
+    arch: Arch,
+    sub_arch: Option<SubArch>,
+    vendor: Vendor,
+    os: OS,
+    environment: Environment,
+    object_format: ObjectFormat,
+}
+
+impl Triple {
+    /// Gets the architecture.
+    pub fn get_arch(&self) -> Arch {
+        self.arch
+    }
+
+    /// Gets the sub architecture, which only ARM triples may have.
+    pub fn get_sub_arch(&self) -> Option<SubArch> {
+        self.sub_arch
+    }
+
+    /// Gets the vendor.
+    pub fn get_vendor(&self) -> Vendor {
+        self.vendor
+    }
+
+    /// Gets the operating system.
+    pub fn get_os(&self) -> OS {
+        self.os
+    }
+
+    /// Gets the environment.
+    pub fn get_environment(&self) -> Environment {
+        self.environment
+    }
+
+    /// Gets the object file format, which is either given explicitly at the end of the
+    /// environment or implied by the architecture and operating system.
+    pub fn get_object_format(&self) -> ObjectFormat {
+        self.object_format
+    }
+
+    /// Gets the number of bits in a pointer. This is usually the architecture's pointer width,
+    /// except for ILP32 environments on 64 bit architectures, such as `gnux32`.
+    pub fn get_pointer_width(&self) -> u32 {
+        match (self.arch, self.environment) {
+            (Arch::X86_64, Environment::GNUX32) | (Arch::Mips64, Environment::GNUABIN32) | (Arch::Mips64el, Environment::GNUABIN32) => 32,
+            (arch, _) => arch.get_pointer_width(),
+        }
+    }
+
+    /// Gets the byte ordering of the architecture.
+    pub fn get_byte_ordering(&self) -> ByteOrdering {
+        self.arch.get_byte_ordering()
+    }
+
+    /// Gets this triple as a string, exactly as it was parsed.
+    pub fn as_str(&self) -> &str {
+        &self.triple
+    }
+
+    /// Creates a `TargetTriple` from this triple, to be used with the rest of LLVM.
+    pub fn to_target_triple(&self) -> TargetTriple {
+        TargetTriple::create(&self.triple)
+    }
+}
+
+impl FromStr for Triple {
+    type Err = TripleParseError;
+
+    fn from_str(triple: &str) -> Result<Self, Self::Err> {
+        if triple.is_empty() {
+            return Err(TripleParseError::Empty);
+        }
+
+        let mut components = triple.split('-');
+        let (arch, sub_arch) = Arch::parse(components.next().unwrap_or(""));
+        let mut vendor = None;
+        let mut os = None;
+        let mut environment = None;
+        let mut implied_environment = None;
+        let mut object_format = None;
+
+        // Like LLVM's normalization, each component is matched against every kind it could be,
+        // since triples like x86_64-linux-gnu leave out the vendor.
+        for (position, component) in components.enumerate() {
+            if vendor.is_none() && position == 0 {
+                if let Some(parsed) = Vendor::parse(component) {
+                    vendor = Some(parsed);
+                    continue;
+                }
+            }
+
+            if os.is_none() && position <= 1 {
+                if let Some(parsed) = OS::parse(component) {
+                    os = Some(parsed);
+                    implied_environment = Environment::implied_by_os(component);
+                    continue;
+                }
+            }
+
+            if environment.is_none() {
+                if let Some(parsed) = Environment::parse(component) {
+                    environment = Some(parsed);
+                }
+            }
+
+            if object_format.is_none() && position >= 2 {
+                object_format = ObjectFormat::parse(component);
+            }
+
+            // An unrecognized component takes the place of the vendor, as with none in
+            // thumbv7em-none-eabihf, or of the OS, as with unknown in x86_64-unknown-unknown
+            if position == 0 && vendor.is_none() {
+                vendor = Some(Vendor::Unknown);
+            } else if position == 1 && os.is_none() && environment.is_none() {
+                os = Some(OS::Unknown);
+            }
+        }
+
+        let os = os.unwrap_or(OS::Unknown);
+        let object_format = object_format.unwrap_or_else(|| match (arch, os) {
+            (Arch::Wasm32, _) | (Arch::Wasm64, _) => ObjectFormat::Wasm,
+            (_, OS::AIX) => ObjectFormat::XCOFF,
+            (_, OS::Windows) => ObjectFormat::COFF,
+            (_, os) if os.is_apple() => ObjectFormat::MachO,
+            _ => ObjectFormat::ELF,
+        });
+
+        Ok(Triple {
+            triple: triple.to_string(),
+            arch,
+            sub_arch,
+            vendor: vendor.unwrap_or(Vendor::Unknown),
+            os,
+            environment: environment.or(implied_environment).unwrap_or(Environment::Unknown),
+            object_format,
+        })
+    }
+}
+
+impl fmt::Display for Triple {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.triple)
+    }
+}
+
 static TARGET_LOCK: Lazy<RwLock<()>> = Lazy::new(|| RwLock::new(()));
 
 // NOTE: Versions verified as target-complete: 3.6, 3.7, 3.8, 3.9, 4.0
@@ -951,6 +1708,28 @@ impl Target {
         Some(TargetMachine::new(target_machine))
     }
 
+    /// Creates a `TargetMachine` configured by `TargetMachineOptions`. See `TargetMachineOptions`
+    /// for how options without a C API equivalent are applied.
+    ///
+    /// Returns `None` if LLVM cannot create the machine.
+    #[llvm_versions(3.9..=latest)]
+    pub fn create_target_machine_from_options(&self, triple: &TargetTriple, options: &TargetMachineOptions) -> Option<TargetMachine> {
+        let parsed = triple.parse().ok();
+        let float_abi_triple = parsed.as_ref().and_then(|parsed| with_float_abi(parsed, options.float_abi));
+        let mut target_machine = self.create_target_machine(
+            float_abi_triple.as_ref().unwrap_or(triple),
+            &options.cpu,
+            &options.features.join(","),
+            options.level,
+            options.reloc_mode,
+            options.code_model,
+        )?;
+
+        target_machine.options = Some(options.clone());
+
+        Some(target_machine)
+    }
+
     pub fn get_first() -> Option<Self> {
         let target = {
             let _guard = TARGET_LOCK.read();
@@ -1029,18 +1808,289 @@ impl Target {
     pub fn has_asm_backend(&self) -> bool {
         unsafe { LLVMTargetHasAsmBackend(self.target) == 1 }
     }
+
+    /// Checks the syntax of a feature string such as `"+avx2,-sse4.1"` before it is passed to
+    /// `create_target_machine`. Each comma separated feature must be a name prefixed with `+`
+    /// to enable it or `-` to disable it.
+    ///
+    /// Whether the target recognizes the features, or a CPU name, is not checked. The C API
+    /// cannot list them, and LLVM only reports unrecognized ones with a warning on stderr.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::targets::{SubtargetError, Target};
+    ///
+    /// assert!(Target::validate_subtarget("+avx2,-sse4.1").is_ok());
+    /// assert_eq!(
+    ///     Target::validate_subtarget("+avx2,sse4.1"),
+    ///     Err(SubtargetError::InvalidFeatureSyntax("sse4.1".to_string())),
+    /// );
+    /// ```
+    pub fn validate_subtarget(features: &str) -> Result<(), SubtargetError> {
+        for feature in features.split(',').filter(|feature| !feature.is_empty()) {
+            if !(feature.starts_with('+') || feature.starts_with('-')) || feature.len() == 1 {
+                return Err(SubtargetError::InvalidFeatureSyntax(feature.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Errors returned by `Target::validate_subtarget`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SubtargetError {
+    /// A feature didn't start with `+` or `-`, or was only a sign.
+    InvalidFeatureSyntax(String),
+}
+
+impl Error for SubtargetError {}
+
+impl fmt::Display for SubtargetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubtargetError::InvalidFeatureSyntax(feature) => write!(f, "SubtargetError(Feature {:?} must start with + or -)", feature),
+        }
+    }
+}
+
+/// Options for `Target::create_target_machine_from_options`, covering settings which
+/// `Target::create_target_machine` does not expose.
+///
+/// LLVM's C API only configures a target machine with a triple, CPU, features, optimization
+/// level, relocation mode and code model. The remaining options are applied to a copy of every
+/// `Module` the resulting `TargetMachine` emits instead (see `TargetMachine::apply_options_to_module`):
+///
+/// * Function and data sections give each definition its own `.text.*`, `.data.*`, `.rodata.*`
+///   or `.bss.*` section. This is only done for ELF targets, where the linker can then
+///   garbage collect unused sections. As in LLVM, `unnamed_addr` constants such as strings are
+///   left in the shared sections where the linker merges them.
+/// * The frame pointer becomes the `frame-pointer` (`no-frame-pointer-elim` before LLVM 10)
+///   function attribute.
+/// * The float ABI becomes the `use-soft-float` function attribute. LLVM derives the ARM float
+///   ABI from the triple, so on ARM the environment is also switched to or from its `hf`
+///   variant, as in `arm-linux-gnueabi` and `arm-linux-gnueabihf`.
+///
+/// There is no option for emulated TLS, like `-femulated-tls`, as neither the C API nor an
+/// attribute can set it. LLVM decides by the triple alone, and has emulated TLS by default on
+/// Android, OpenBSD and Cygwin since LLVM 6.
+///
+/// LLVM's internal command line options, like clang's `-mllvm`, are global to the process and
+/// are set with `support::parse_command_line_options` instead.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::targets::{FramePointer, InitializationConfig, Target, TargetMachine, TargetMachineOptions};
+///
+/// Target::initialize_native(&InitializationConfig::default()).unwrap();
+///
+/// let triple = TargetMachine::get_default_triple();
+/// let target = Target::from_triple(&triple).unwrap();
+/// let options = TargetMachineOptions::default()
+///     .set_cpu("generic")
+///     .add_feature("+sse4.2")
+///     .set_function_sections(true)
+///     .set_data_sections(true)
+///     .set_frame_pointer(FramePointer::All);
+/// let target_machine = target.create_target_machine_from_options(&triple, &options).unwrap();
+/// ```
+#[llvm_versions(3.9..=latest)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TargetMachineOptions {
+    cpu: String,
+    features: Vec<String>,
+    level: OptimizationLevel,
+    reloc_mode: RelocMode,
+    code_model: CodeModel,
+    function_sections: bool,
+    data_sections: bool,
+    frame_pointer: Option<FramePointer>,
+    float_abi: FloatABI,
+}
+
+#[llvm_versions(3.9..=latest)]
+impl TargetMachineOptions {
+    /// Sets the CPU to generate code for, such as `"skylake"`.
+    pub fn set_cpu(mut self, cpu: &str) -> Self {
+        self.cpu = cpu.to_string();
+        self
+    }
+
+    /// Enables (`"+avx2"`) or disables (`"-avx2"`) a target feature.
+    pub fn add_feature(mut self, feature: &str) -> Self {
+        self.features.push(feature.to_string());
+        self
+    }
+
+    /// Sets the optimization level used by the code generator.
+    pub fn set_optimization_level(mut self, level: OptimizationLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Sets the relocation model.
+    pub fn set_reloc_mode(mut self, reloc_mode: RelocMode) -> Self {
+        self.reloc_mode = reloc_mode;
+        self
+    }
+
+    /// Sets the code model.
+    pub fn set_code_model(mut self, code_model: CodeModel) -> Self {
+        self.code_model = code_model;
+        self
+    }
+
+    /// Places each function in its own section, like `-ffunction-sections`.
+    pub fn set_function_sections(mut self, function_sections: bool) -> Self {
+        self.function_sections = function_sections;
+        self
+    }
+
+    /// Places each global variable in its own section, like `-fdata-sections`.
+    pub fn set_data_sections(mut self, data_sections: bool) -> Self {
+        self.data_sections = data_sections;
+        self
+    }
+
+    /// Sets which functions keep a frame pointer, like `-fno-omit-frame-pointer`.
+    pub fn set_frame_pointer(mut self, frame_pointer: FramePointer) -> Self {
+        self.frame_pointer = Some(frame_pointer);
+        self
+    }
+
+    /// Sets the float ABI, like `-mfloat-abi`. On ARM this may change the triple's environment.
+    pub fn set_float_abi(mut self, float_abi: FloatABI) -> Self {
+        self.float_abi = float_abi;
+        self
+    }
+}
+
+#[llvm_versions(3.9..=latest)]
+impl Default for TargetMachineOptions {
+    fn default() -> Self {
+        TargetMachineOptions {
+            cpu: String::new(),
+            features: Vec::new(),
+            level: OptimizationLevel::default(),
+            reloc_mode: RelocMode::Default,
+            code_model: CodeModel::Default,
+            function_sections: false,
+            data_sections: false,
+            frame_pointer: None,
+            float_abi: FloatABI::Default,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub struct TargetMachine {
     pub(crate) target_machine: LLVMTargetMachineRef,
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    options: Option<TargetMachineOptions>,
 }
 
 impl TargetMachine {
     fn new(target_machine: LLVMTargetMachineRef) -> Self {
         assert!(!target_machine.is_null());
 
-        TargetMachine { target_machine }
+        TargetMachine {
+            target_machine,
+            #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+            options: None,
+        }
+    }
+
+    /// Applies the options given to `Target::create_target_machine_from_options` which the
+    /// C API cannot set on the `TargetMachine` itself to a `Module`'s definitions.
+    ///
+    /// Emitting a `Module` with this `TargetMachine` applies them to a clone, leaving the
+    /// original untouched, so this is only needed to inspect or keep the result.
+    ///
+    /// Definitions which already have an explicit section keep it.
+    #[llvm_versions(3.9..=latest)]
+    pub fn apply_options_to_module(&self, module: &Module) {
+        let options = match self.options {
+            Some(ref options) => options,
+            None => return,
+        };
+        let context = module.get_context();
+        // Only ELF section names are free form, Mach-O and COFF have their own conventions
+        let is_elf = self
+            .get_triple()
+            .parse()
+            .map_or(false, |triple| triple.get_object_format() == ObjectFormat::ELF);
+
+        for function in module.get_functions() {
+            let global = function.as_global_value();
+
+            if global.is_declaration() {
+                continue;
+            }
+
+            if options.function_sections && is_elf {
+                set_unique_section(global, ".text.");
+            }
+
+            if let Some(frame_pointer) = options.frame_pointer {
+                #[cfg(any(feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0", feature = "llvm7-0", feature = "llvm8-0", feature = "llvm9-0"))]
+                {
+                    let (eliminate_all, eliminate_non_leaf) = match frame_pointer {
+                        FramePointer::None => ("false", None),
+                        FramePointer::NonLeaf => ("false", Some("true")),
+                        FramePointer::All => ("true", None),
+                    };
+
+                    function.add_attribute(AttributeLoc::Function, context.create_string_attribute("no-frame-pointer-elim", eliminate_all));
+
+                    if let Some(eliminate_non_leaf) = eliminate_non_leaf {
+                        function.add_attribute(AttributeLoc::Function, context.create_string_attribute("no-frame-pointer-elim-non-leaf", eliminate_non_leaf));
+                    }
+                }
+                #[cfg(not(any(feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0", feature = "llvm7-0", feature = "llvm8-0", feature = "llvm9-0")))]
+                {
+                    let frame_pointer = match frame_pointer {
+                        FramePointer::None => "none",
+                        FramePointer::NonLeaf => "non-leaf",
+                        FramePointer::All => "all",
+                    };
+
+                    function.add_attribute(AttributeLoc::Function, context.create_string_attribute("frame-pointer", frame_pointer));
+                }
+            }
+
+            match options.float_abi {
+                FloatABI::Default => {},
+                FloatABI::Soft => function.add_attribute(AttributeLoc::Function, context.create_string_attribute("use-soft-float", "true")),
+                FloatABI::Hard => function.add_attribute(AttributeLoc::Function, context.create_string_attribute("use-soft-float", "false")),
+            }
+        }
+
+        if options.data_sections && is_elf {
+            for global in module.get_globals() {
+                if !global.is_declaration() && !is_mergeable_constant(global) {
+                    set_unique_section(global, data_section_prefix(global));
+                }
+            }
+        }
+    }
+
+    /// Clones `module` with the options applied, or returns `None` if there are none to apply.
+    #[llvm_versions(3.9..=latest)]
+    fn clone_with_options<'ctx>(&self, module: &Module<'ctx>) -> Result<Option<Module<'ctx>>, LLVMString> {
+        if self.options.is_none() {
+            return Ok(None);
+        }
+
+        // Cloning an invalid module may segfault, so report it like a failed emission instead
+        module.verify()?;
+
+        let module = module.clone();
+
+        self.apply_options_to_module(&module);
+
+        Ok(Some(module))
     }
 
     pub fn get_target(&self) -> Target {
@@ -1175,6 +2225,10 @@ impl TargetMachine {
         module: &Module,
         file_type: FileType,
     ) -> Result<MemoryBuffer, LLVMString> {
+        #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+        let with_options = self.clone_with_options(module)?;
+        #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+        let module = with_options.as_ref().unwrap_or(module);
         let mut memory_buffer = ptr::null_mut();
         let mut err_string = MaybeUninit::uninit();
         let return_code = unsafe {
@@ -1246,6 +2300,12 @@ impl TargetMachine {
             .expect("Did not find a valid Unicode path string");
         let path_c_string = to_c_str(path);
         let mut err_string = MaybeUninit::uninit();
+
+        #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+        let with_options = self.clone_with_options(module)?;
+        #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+        let module = with_options.as_ref().unwrap_or(module);
+
         let return_code = unsafe {
             // REVIEW: Why does LLVM need a mutable ptr to path...?
             let module_ptr = module.module.get();
@@ -1268,6 +2328,57 @@ impl TargetMachine {
 
         Ok(())
     }
+
+    /// Compiles a `Module` into an in memory object file, which can then be inspected
+    /// or written to disk.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::{CodeModel, RelocMode, InitializationConfig, Target, TargetMachine};
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// let triple = TargetMachine::get_default_triple();
+    /// let target = Target::from_triple(&triple).unwrap();
+    /// let target_machine = target.create_target_machine(
+    ///     &triple,
+    ///     "generic",
+    ///     "",
+    ///     OptimizationLevel::Default,
+    ///     RelocMode::Default,
+    ///     CodeModel::Default
+    /// )
+    /// .unwrap();
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let void_type = context.void_type();
+    /// let fn_type = void_type.fn_type(&[], false);
+    /// let function = module.add_function("my_fn", fn_type, None);
+    /// let builder = context.create_builder();
+    ///
+    /// builder.position_at_end(context.append_basic_block(function, "entry"));
+    /// builder.build_return(None);
+    ///
+    /// let object = target_machine.compile_to_object(&module).unwrap();
+    ///
+    /// assert!(object.get_symbols().any(|symbol| symbol.get_name().unwrap().to_bytes().ends_with(b"my_fn")));
+    ///
+    /// object.write_to_file("my_module.o").unwrap();
+    /// ```
+    pub fn compile_to_object(&self, module: &Module) -> Result<CompiledObject, CodegenError> {
+        let memory_buffer = self
+            .write_to_memory_buffer(module, FileType::Object)
+            .map_err(CodegenError::EmitError)?;
+        let object_file = memory_buffer
+            .create_object_file()
+            .map_err(|()| CodegenError::InvalidObjectFile)?;
+
+        Ok(CompiledObject { object_file })
+    }
 }
 
 impl Drop for TargetMachine {
@@ -1276,7 +2387,163 @@ impl Drop for TargetMachine {
     }
 }
 
+/// Switches an ARM triple's environment to or from its `hf` variant to match `float_abi`, or
+/// returns `None` if the triple is left unchanged.
+#[llvm_versions(3.9..=latest)]
+fn with_float_abi(triple: &Triple, float_abi: FloatABI) -> Option<TargetTriple> {
+    match triple.get_arch() {
+        Arch::Arm | Arch::ArmEB | Arch::Thumb | Arch::ThumbEB => {},
+        _ => return None,
+    }
+
+    let (soft, hard) = match triple.get_environment() {
+        Environment::EABI | Environment::EABIHF => ("eabi", "eabihf"),
+        Environment::GNUEABI | Environment::GNUEABIHF => ("gnueabi", "gnueabihf"),
+        Environment::MuslEABI | Environment::MuslEABIHF => ("musleabi", "musleabihf"),
+        _ => return None,
+    };
+    let (from, to) = match float_abi {
+        FloatABI::Default => return None,
+        FloatABI::Soft => (hard, soft),
+        FloatABI::Hard => (soft, hard),
+    };
+    let mut changed = false;
+    let components: Vec<_> = triple
+        .as_str()
+        .split('-')
+        .map(|component| {
+            // The environment may carry a version or object format, as in gnueabi-elf
+            if !changed && component.starts_with(from) && !component[from.len()..].starts_with("hf") {
+                changed = true;
+
+                format!("{}{}", to, &component[from.len()..])
+            } else {
+                component.to_string()
+            }
+        })
+        .collect();
+
+    if changed {
+        Some(TargetTriple::create(&components.join("-")))
+    } else {
+        None
+    }
+}
+
+#[llvm_versions(3.9..=latest)]
+fn set_unique_section(global: GlobalValue, prefix: &str) {
+    let name = global.get_name().to_string_lossy();
+
+    if name.is_empty() || !global.get_section().to_bytes().is_empty() {
+        return;
+    }
+
+    global.set_section(&format!("{}{}", prefix, name));
+}
+
+// Mirrors how LLVM itself picks a section kind for a global, so the linker treats the
+// unique section the same way it would the shared one
+#[llvm_versions(3.9..=latest)]
+fn data_section_prefix(global: GlobalValue) -> &'static str {
+    let initializer = global.get_initializer();
+    let is_zeroed = initializer.map_or(false, |value| unsafe { LLVMIsNull(value.as_value_ref()) == 1 });
+
+    if global.is_thread_local() {
+        return if is_zeroed { ".tbss." } else { ".tdata." };
+    }
+
+    if global.is_constant() {
+        // Pointers need relocations, so they can't go in a truly read only section with PIC
+        return match initializer {
+            Some(value) if contains_pointers(value.get_type()) => ".data.rel.ro.",
+            _ => ".rodata.",
+        };
+    }
+
+    if is_zeroed { ".bss." } else { ".data." }
+}
+
+// LLVM keeps unnamed_addr constants without relocations, such as strings, out of unique
+// sections so the linker can merge them in sections like .rodata.str1.1
+#[llvm_versions(3.9..=latest)]
+fn is_mergeable_constant(global: GlobalValue) -> bool {
+    global.is_constant()
+        && global.has_unnamed_addr()
+        && global.get_initializer().map_or(false, |value| !contains_pointers(value.get_type()))
+}
+
+#[llvm_versions(3.9..=latest)]
+fn contains_pointers(type_: BasicTypeEnum) -> bool {
+    match type_ {
+        BasicTypeEnum::PointerType(_) => true,
+        BasicTypeEnum::ArrayType(array_type) => contains_pointers(array_type.get_element_type()),
+        BasicTypeEnum::VectorType(vector_type) => contains_pointers(vector_type.get_element_type()),
+        BasicTypeEnum::StructType(struct_type) => struct_type.get_field_types().into_iter().any(contains_pointers),
+        BasicTypeEnum::FloatType(_) | BasicTypeEnum::IntType(_) => false,
+    }
+}
+
+/// Errors which can occur while compiling a `Module` with `TargetMachine::compile_to_object`.
 #[derive(Debug, PartialEq, Eq)]
+pub enum CodegenError {
+    /// LLVM failed to emit the `Module`, for example because the target does not support
+    /// emitting object files.
+    EmitError(LLVMString),
+    /// LLVM emitted a buffer which it could not parse back as an object file.
+    InvalidObjectFile,
+}
+
+impl Error for CodegenError {}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodegenError::EmitError(err) => write!(f, "CodegenError({})", err),
+            CodegenError::InvalidObjectFile => write!(f, "CodegenError(LLVM emitted an object file it cannot parse)"),
+        }
+    }
+}
+
+/// An object file produced by `TargetMachine::compile_to_object`, which owns both the
+/// emitted bytes and their parsed representation.
+#[derive(Debug)]
+pub struct CompiledObject {
+    object_file: ObjectFile,
+}
+
+impl CompiledObject {
+    /// Gets the bytes of this object file.
+    pub fn as_slice(&self) -> &[u8] {
+        self.object_file.as_slice()
+    }
+
+    /// Gets the parsed object file.
+    pub fn get_object_file(&self) -> &ObjectFile {
+        &self.object_file
+    }
+
+    /// Iterates over the sections of this object file.
+    pub fn get_sections(&self) -> SectionIterator {
+        self.object_file.get_sections()
+    }
+
+    /// Iterates over the symbols of this object file.
+    pub fn get_symbols(&self) -> SymbolIterator {
+        self.object_file.get_symbols()
+    }
+
+    /// Writes this object file to disk.
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.as_slice())
+    }
+
+    /// Takes ownership of the parsed object file.
+    pub fn into_object_file(self) -> ObjectFile {
+        self.object_file
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum ByteOrdering {
     BigEndian,
     LittleEndian,
@@ -1412,6 +2679,32 @@ impl TargetData {
             ))
         }
     }
+
+    /// Computes the full `StructLayout` of a `StructType`: the offset and size of every
+    /// field, the padding between them, and the total size and alignment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the `StructType` is opaque or contains an opaque type.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::TargetData;
+    ///
+    /// let context = Context::create();
+    /// let struct_type = context.struct_type(&[context.i8_type().into(), context.i64_type().into()], false);
+    /// let target_data = TargetData::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
+    /// let struct_layout = target_data.get_struct_layout(&struct_type);
+    ///
+    /// assert_eq!(struct_layout.get_size(), 16);
+    /// assert_eq!(struct_layout.get_field(1).unwrap().offset, 8);
+    /// assert_eq!(struct_layout.get_padding_size(), 7);
+    /// ```
+    pub fn get_struct_layout<'ctx>(&self, struct_type: &StructType<'ctx>) -> StructLayout<'ctx> {
+        StructLayout::new(self, *struct_type)
+    }
 }
 
 impl Drop for TargetData {
@@ -180,3 +180,29 @@ impl<'ctx> BasicValueUse<'ctx> {
         }
     }
 }
+
+/// An iterator over the uses of a value, created by `get_uses`.
+///
+/// LLVM's use lists are singly linked, so unlike the other IR iterators this one cannot be
+/// iterated from the back. The next use is looked up before the current one is yielded, so
+/// the user of the current use may be modified or erased while iterating.
+#[derive(Debug)]
+pub struct BasicValueUseIter<'ctx>(Option<BasicValueUse<'ctx>>);
+
+impl<'ctx> BasicValueUseIter<'ctx> {
+    pub(crate) fn new(first_use: Option<BasicValueUse<'ctx>>) -> Self {
+        BasicValueUseIter(first_use)
+    }
+}
+
+impl<'ctx> Iterator for BasicValueUseIter<'ctx> {
+    type Item = BasicValueUse<'ctx>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let use_ = self.0?;
+
+        self.0 = use_.get_next_use();
+
+        Some(use_)
+    }
+}
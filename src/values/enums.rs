@@ -246,6 +246,19 @@ impl<'ctx> AnyValueEnum<'ctx> {
             panic!("Found {:?} but expected a different variant", self)
         }
     }
+
+    /// Gets this value as an `InstructionValue` if it is produced by an instruction. Unlike
+    /// `into_instruction_value`, this also covers instructions which produce a value, such
+    /// as a load, which are represented by the variant matching their type.
+    pub fn as_instruction_value(self) -> Option<InstructionValue<'ctx>> {
+        let value = self.as_value_ref();
+
+        if unsafe { LLVMIsAInstruction(value) }.is_null() {
+            return None;
+        }
+
+        Some(InstructionValue::new(value))
+    }
 }
 
 impl<'ctx> BasicValueEnum<'ctx> {
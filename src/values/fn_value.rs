@@ -15,7 +15,7 @@ use std::fmt;
 
 #[llvm_versions(3.9..=latest)]
 use crate::attributes::{Attribute, AttributeLoc};
-use crate::basic_block::BasicBlock;
+use crate::basic_block::{BasicBlock, BasicBlockIter};
 #[llvm_versions(7.0..=latest)]
 use crate::debug_info::DISubprogram;
 use crate::module::Linkage;
@@ -81,7 +81,6 @@ impl<'ctx> FunctionValue<'ctx> {
         code != 1
     }
 
-    // REVIEW: If there's a demand, could easily create a module.get_functions() -> Iterator
     pub fn get_next_function(self) -> Option<Self> {
         let function = unsafe {
             LLVMGetNextFunction(self.as_value_ref())
@@ -172,6 +171,12 @@ impl<'ctx> FunctionValue<'ctx> {
         raw_vec.iter().map(|val| BasicBlock::new(*val).unwrap()).collect()
     }
 
+    /// Iterates over the `BasicBlock`s of this function without collecting them into a `Vec`
+    /// like `get_basic_blocks` does. The current `BasicBlock` may be removed or deleted while iterating.
+    pub fn get_basic_block_iter(self) -> BasicBlockIter<'ctx> {
+        BasicBlockIter::new(self.get_first_basic_block(), self.get_last_basic_block())
+    }
+
     pub fn get_param_iter(self) -> ParamValueIter<'ctx> {
         ParamValueIter {
             param_iter_value: self.fn_value.value,
@@ -4,16 +4,25 @@ use llvm_sys::core::{LLVMGetAlignment, LLVMSetAlignment, LLVMGetInstructionOpcod
 use llvm_sys::core::{LLVMGetOrdering, LLVMSetOrdering};
 #[llvm_versions(3.9..=latest)]
 use llvm_sys::core::LLVMInstructionRemoveFromParent;
+#[llvm_versions(3.9..=latest)]
+use llvm_sys::core::{LLVMGetNormalDest, LLVMGetUnwindDest};
+#[llvm_versions(8.0..=latest)]
+use llvm_sys::core::{LLVMGetNumClauses, LLVMGetClause, LLVMIsCleanup, LLVMGetNumHandlers, LLVMGetHandlers, LLVMGetParentCatchSwitch};
 #[llvm_versions(10.0..=latest)]
 use llvm_sys::core::{LLVMIsAAtomicRMWInst, LLVMIsAAtomicCmpXchgInst};
 use llvm_sys::LLVMOpcode;
 use llvm_sys::prelude::LLVMValueRef;
 
 use crate::basic_block::BasicBlock;
+#[llvm_versions(9.0..=latest)]
+use crate::debug_info::{DILocalVariable, DILocation};
 use crate::values::traits::AsValueRef;
-use crate::values::{BasicValue, BasicValueEnum, BasicValueUse, Value, MetadataValue};
+use crate::values::{BasicValue, BasicValueEnum, BasicValueUse, BasicValueUseIter, InstructionEnum, Value, MetadataValue};
 use crate::{AtomicOrdering, IntPredicate, FloatPredicate};
 
+#[llvm_versions(9.0..=latest)]
+use std::marker::PhantomData;
+
 // REVIEW: Split up into structs for SubTypes on InstructionValues?
 // REVIEW: This should maybe be split up into InstructionOpcode and ConstOpcode?
 // see LLVMGetConstOpcode
@@ -144,6 +153,12 @@ impl<'ctx> InstructionValue<'ctx> {
         InstructionOpcode::new(opcode)
     }
 
+    /// Downcasts this instruction into a typed wrapper according to its opcode, falling
+    /// back to `InstructionEnum::Other` for opcodes without a dedicated wrapper.
+    pub fn as_instruction_enum(self) -> InstructionEnum<'ctx> {
+        InstructionEnum::new(self)
+    }
+
     pub fn get_previous_instruction(self) -> Option<Self> {
         let value = unsafe {
             LLVMGetPreviousInstruction(self.as_value_ref())
@@ -595,6 +610,49 @@ impl<'ctx> InstructionValue<'ctx> {
         self.instruction_value.get_first_use()
     }
 
+    /// Iterates over the uses of this `InstructionValue`, starting with `get_first_use`.
+    pub fn get_uses(self) -> BasicValueUseIter<'ctx> {
+        BasicValueUseIter::new(self.get_first_use())
+    }
+
+    /// Iterates over the operands of this `InstructionValue` from either end, yielding
+    /// the same values as `get_operand` would for each index.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    /// use inkwell::values::BasicValue;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("ivs");
+    /// let builder = context.create_builder();
+    /// let void_type = context.void_type();
+    /// let f32_type = context.f32_type();
+    /// let f32_ptr_type = f32_type.ptr_type(AddressSpace::Generic);
+    /// let fn_type = void_type.fn_type(&[f32_ptr_type.into()], false);
+    ///
+    /// let function = module.add_function("take_f32_ptr", fn_type, None);
+    /// let basic_block = context.append_basic_block(function, "entry");
+    ///
+    /// builder.position_at_end(basic_block);
+    ///
+    /// let arg1 = function.get_first_param().unwrap().into_pointer_value();
+    /// let f32_val = f32_type.const_float(::std::f64::consts::PI);
+    /// let store_instruction = builder.build_store(arg1, f32_val);
+    ///
+    /// assert_eq!(store_instruction.get_operands().len(), 2);
+    /// assert_eq!(store_instruction.get_operands().next_back().unwrap().unwrap().left(), Some(arg1.as_basic_value_enum()));
+    /// ```
+    pub fn get_operands(self) -> OperandIter<'ctx> {
+        OperandIter {
+            instruction: self,
+            front: 0,
+            back: self.get_num_operands(),
+        }
+    }
+
     /// Gets the predicate of an `ICmp` `InstructionValue`.
     /// For instance, in the LLVM instruction
     /// `%3 = icmp slt i32 %0, %1`
@@ -635,6 +693,128 @@ impl<'ctx> InstructionValue<'ctx> {
         }
     }
 
+    /// Gets the block an `Invoke` `InstructionValue` continues in when the callee returns normally.
+    ///
+    /// If the instruction is not an `Invoke`, this returns None.
+    #[llvm_versions(3.9..=latest)]
+    pub fn get_normal_destination(self) -> Option<BasicBlock<'ctx>> {
+        if self.get_opcode() != InstructionOpcode::Invoke {
+            return None;
+        }
+
+        let bb = unsafe {
+            LLVMGetNormalDest(self.as_value_ref())
+        };
+
+        BasicBlock::new(bb)
+    }
+
+    /// Gets the block an `Invoke` `InstructionValue` continues in when the callee unwinds.
+    ///
+    /// If the instruction is not an `Invoke`, this returns None.
+    #[llvm_versions(3.9..=7.0)]
+    pub fn get_unwind_destination(self) -> Option<BasicBlock<'ctx>> {
+        // The C API only supports cleanupret and catchswitch from 8.0 onwards.
+        if self.get_opcode() != InstructionOpcode::Invoke {
+            return None;
+        }
+
+        let bb = unsafe {
+            LLVMGetUnwindDest(self.as_value_ref())
+        };
+
+        BasicBlock::new(bb)
+    }
+
+    /// Gets the block an `Invoke`, `CleanupRet` or `CatchSwitch` `InstructionValue` unwinds to.
+    ///
+    /// If the instruction is not one of those, or it unwinds to the caller, this returns None.
+    #[llvm_versions(8.0..=latest)]
+    pub fn get_unwind_destination(self) -> Option<BasicBlock<'ctx>> {
+        match self.get_opcode() {
+            InstructionOpcode::Invoke | InstructionOpcode::CleanupRet | InstructionOpcode::CatchSwitch => {},
+            _ => return None,
+        }
+
+        let bb = unsafe {
+            LLVMGetUnwindDest(self.as_value_ref())
+        };
+
+        BasicBlock::new(bb)
+    }
+
+    /// Determines whether a `LandingPad` `InstructionValue` is a cleanup landing pad.
+    ///
+    /// If the instruction is not a `LandingPad`, this returns None.
+    #[llvm_versions(8.0..=latest)]
+    pub fn is_cleanup(self) -> Option<bool> {
+        if self.get_opcode() != InstructionOpcode::LandingPad {
+            return None;
+        }
+
+        Some(unsafe { LLVMIsCleanup(self.as_value_ref()) } == 1)
+    }
+
+    /// Gets the `catch` and `filter` clauses of a `LandingPad` `InstructionValue`.
+    ///
+    /// If the instruction is not a `LandingPad`, this returns an empty `Vec`.
+    #[llvm_versions(8.0..=latest)]
+    pub fn get_clauses(self) -> Vec<BasicValueEnum<'ctx>> {
+        if self.get_opcode() != InstructionOpcode::LandingPad {
+            return Vec::new();
+        }
+
+        let num_clauses = unsafe {
+            LLVMGetNumClauses(self.as_value_ref())
+        };
+
+        (0..num_clauses).map(|i| BasicValueEnum::new(unsafe { LLVMGetClause(self.as_value_ref(), i) }))
+                        .collect()
+    }
+
+    /// Gets the handler blocks of a `CatchSwitch` `InstructionValue`.
+    ///
+    /// If the instruction is not a `CatchSwitch`, this returns an empty `Vec`.
+    #[llvm_versions(8.0..=latest)]
+    pub fn get_handlers(self) -> Vec<BasicBlock<'ctx>> {
+        if self.get_opcode() != InstructionOpcode::CatchSwitch {
+            return Vec::new();
+        }
+
+        let num_handlers = unsafe {
+            LLVMGetNumHandlers(self.as_value_ref())
+        };
+        let mut handlers = vec![std::ptr::null_mut(); num_handlers as usize];
+
+        unsafe {
+            LLVMGetHandlers(self.as_value_ref(), handlers.as_mut_ptr());
+        }
+
+        handlers.into_iter()
+                .map(|bb| BasicBlock::new(bb).expect("CatchSwitch handler should be a valid BasicBlock"))
+                .collect()
+    }
+
+    /// Gets the `CatchSwitch` a `CatchPad` `InstructionValue` belongs to.
+    ///
+    /// If the instruction is not a `CatchPad`, this returns None.
+    #[llvm_versions(8.0..=latest)]
+    pub fn get_parent_catch_switch(self) -> Option<InstructionValue<'ctx>> {
+        if self.get_opcode() != InstructionOpcode::CatchPad {
+            return None;
+        }
+
+        let value = unsafe {
+            LLVMGetParentCatchSwitch(self.as_value_ref())
+        };
+
+        if value.is_null() {
+            return None;
+        }
+
+        Some(InstructionValue::new(value))
+    }
+
     /// Determines whether or not this `Instruction` has any associated metadata.
     pub fn has_metadata(self) -> bool {
         unsafe {
@@ -663,8 +843,105 @@ impl<'ctx> InstructionValue<'ctx> {
             LLVMSetMetadata(self.instruction_value.value, kind_id, metadata.as_value_ref())
         }
     }
+
+    /// Gets the debug location of this `Instruction`, if it has one.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_debug_location(self) -> Option<DILocation<'ctx>> {
+        use llvm_sys::debuginfo::LLVMInstructionGetDebugLoc;
+
+        let metadata_ref = unsafe {
+            LLVMInstructionGetDebugLoc(self.instruction_value.value)
+        };
+
+        if metadata_ref.is_null() {
+            return None;
+        }
+
+        Some(DILocation {
+            metadata_ref,
+            _marker: PhantomData,
+        })
+    }
+
+    /// If this `Instruction` is a call to `llvm.dbg.declare` or `llvm.dbg.value`, gets the
+    /// variable it describes.
+    #[llvm_versions(9.0..=latest)]
+    pub fn get_debug_variable(self) -> Option<DILocalVariable<'ctx>> {
+        use crate::intrinsics::Intrinsic;
+        use llvm_sys::core::{LLVMGetCalledValue, LLVMGetIntrinsicID, LLVMValueAsMetadata};
+
+        if self.get_opcode() != InstructionOpcode::Call {
+            return None;
+        }
+
+        let intrinsic_id = unsafe {
+            LLVMGetIntrinsicID(LLVMGetCalledValue(self.instruction_value.value))
+        };
+        let is_dbg_intrinsic = ["llvm.dbg.declare", "llvm.dbg.value"].iter()
+            .filter_map(|name| Intrinsic::find(name))
+            .any(|intrinsic| intrinsic.get_id() == intrinsic_id);
+
+        if !is_dbg_intrinsic {
+            return None;
+        }
+
+        // The variable is the second argument, wrapped as a metadata value
+        let metadata_ref = unsafe {
+            LLVMValueAsMetadata(LLVMGetOperand(self.instruction_value.value, 1))
+        };
+
+        Some(DILocalVariable {
+            metadata_ref,
+            _marker: PhantomData,
+        })
+    }
+}
+
+/// A double ended iterator over the operands of an `InstructionValue`, created by
+/// `InstructionValue::get_operands`.
+#[derive(Debug)]
+pub struct OperandIter<'ctx> {
+    instruction: InstructionValue<'ctx>,
+    front: u32,
+    back: u32,
+}
+
+impl<'ctx> Iterator for OperandIter<'ctx> {
+    type Item = Option<Either<BasicValueEnum<'ctx>, BasicBlock<'ctx>>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        let operand = self.instruction.get_operand(self.front);
+
+        self.front += 1;
+
+        Some(operand)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back.saturating_sub(self.front) as usize;
+
+        (len, Some(len))
+    }
+}
+
+impl<'ctx> DoubleEndedIterator for OperandIter<'ctx> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front >= self.back {
+            return None;
+        }
+
+        self.back -= 1;
+
+        Some(self.instruction.get_operand(self.back))
+    }
 }
 
+impl ExactSizeIterator for OperandIter<'_> {}
+
 impl Clone for InstructionValue<'_> {
     /// Creates a clone of this `InstructionValue`, and returns it.
     /// The clone will have no parent, and no name.
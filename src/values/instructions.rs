@@ -0,0 +1,420 @@
+use either::{Either, Either::{Left, Right}};
+use llvm_sys::LLVMTypeKind;
+use llvm_sys::core::{LLVMGetAlignment, LLVMGetElementType, LLVMGetFCmpPredicate, LLVMGetICmpPredicate, LLVMGetNumOperands, LLVMGetOperand, LLVMGetTypeKind, LLVMGetVolatile, LLVMIsAFunction, LLVMIsTailCall, LLVMTypeOf, LLVMValueAsBasicBlock};
+#[llvm_versions(3.9..=latest)]
+use llvm_sys::core::LLVMGetNumArgOperands;
+use llvm_sys::prelude::LLVMValueRef;
+
+use crate::basic_block::BasicBlock;
+use crate::types::BasicTypeEnum;
+use crate::values::{AsValueRef, BasicMetadataValueEnum, BasicValueEnum, CallSiteValue, FunctionValue, InstructionOpcode, InstructionValue, IntValue, PointerValue};
+use crate::{FloatPredicate, IntPredicate};
+
+use std::convert::TryFrom;
+use std::ops::Deref;
+
+// Shared by the wrappers rather than generated for each of them, since not all of them need these
+fn operand<V: AsValueRef>(instruction: V, index: u32) -> LLVMValueRef {
+    unsafe {
+        LLVMGetOperand(instruction.as_value_ref(), index)
+    }
+}
+
+fn num_operands<V: AsValueRef>(instruction: V) -> u32 {
+    unsafe {
+        LLVMGetNumOperands(instruction.as_value_ref()) as u32
+    }
+}
+
+macro_rules! instruction_wrapper {
+    ($(#[$attrs:meta])* $name:ident: $($opcode:ident)|+) => {
+        $(#[$attrs])*
+        #[derive(Debug, PartialEq, Eq, Copy, Hash)]
+        pub struct $name<'ctx>(InstructionValue<'ctx>);
+
+        impl<'ctx> $name<'ctx> {
+            /// Gets the underlying `InstructionValue`.
+            pub fn as_instruction_value(self) -> InstructionValue<'ctx> {
+                self.0
+            }
+        }
+
+        // InstructionValue's Clone duplicates the instruction, which is not what copying a wrapper should do
+        impl Clone for $name<'_> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<'ctx> TryFrom<InstructionValue<'ctx>> for $name<'ctx> {
+            type Error = ();
+
+            fn try_from(value: InstructionValue<'ctx>) -> Result<Self, Self::Error> {
+                match value.get_opcode() {
+                    $(InstructionOpcode::$opcode)|+ => Ok($name(value)),
+                    _ => Err(()),
+                }
+            }
+        }
+
+        impl<'ctx> From<$name<'ctx>> for InstructionValue<'ctx> {
+            fn from(value: $name<'ctx>) -> Self {
+                value.0
+            }
+        }
+
+        impl<'ctx> Deref for $name<'ctx> {
+            type Target = InstructionValue<'ctx>;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl AsValueRef for $name<'_> {
+            fn as_value_ref(&self) -> LLVMValueRef {
+                self.0.as_value_ref()
+            }
+        }
+    };
+}
+
+instruction_wrapper! {
+    /// An `alloca` instruction, which allocates memory on the stack.
+    AllocaInst: Alloca
+}
+
+instruction_wrapper! {
+    /// A `br` instruction, which is either unconditional or branches on an `i1` condition.
+    BranchInst: Br
+}
+
+instruction_wrapper! {
+    /// A `call` instruction.
+    CallInst: Call
+}
+
+instruction_wrapper! {
+    /// An `icmp` or `fcmp` instruction.
+    CmpInst: ICmp | FCmp
+}
+
+instruction_wrapper! {
+    /// A `getelementptr` instruction.
+    GetElementPtrInst: GetElementPtr
+}
+
+instruction_wrapper! {
+    /// A `load` instruction.
+    LoadInst: Load
+}
+
+instruction_wrapper! {
+    /// A `store` instruction.
+    StoreInst: Store
+}
+
+instruction_wrapper! {
+    /// A `switch` instruction.
+    SwitchInst: Switch
+}
+
+impl<'ctx> AllocaInst<'ctx> {
+    /// Gets the type this instruction allocates memory for.
+    pub fn get_allocated_type(self) -> BasicTypeEnum<'ctx> {
+        let allocated_type = unsafe {
+            LLVMGetElementType(LLVMTypeOf(self.as_value_ref()))
+        };
+
+        BasicTypeEnum::new(allocated_type)
+    }
+
+    /// Gets the number of elements allocated, which is a constant one unless this is an
+    /// array allocation.
+    pub fn get_array_size(self) -> IntValue<'ctx> {
+        IntValue::new(operand(self, 0))
+    }
+
+    /// Gets the alignment of this allocation.
+    pub fn get_alignment(self) -> u32 {
+        unsafe {
+            LLVMGetAlignment(self.as_value_ref())
+        }
+    }
+}
+
+impl<'ctx> BranchInst<'ctx> {
+    /// Determines whether this branch depends on a condition.
+    pub fn is_conditional(self) -> bool {
+        num_operands(self) == 3
+    }
+
+    /// Gets the condition of a conditional branch.
+    pub fn get_condition(self) -> Option<IntValue<'ctx>> {
+        if !self.is_conditional() {
+            return None;
+        }
+
+        Some(IntValue::new(operand(self, 0)))
+    }
+
+    /// Gets the blocks this branch may jump to. For a conditional branch the block taken when
+    /// the condition is true comes first, like in the IR.
+    pub fn get_successors(self) -> Vec<BasicBlock<'ctx>> {
+        // LLVM stores the operands of a conditional branch as [cond, false_dest, true_dest]
+        let indices: &[u32] = if self.is_conditional() { &[2, 1] } else { &[0] };
+
+        indices.iter()
+               .map(|&index| block_operand(operand(self, index)))
+               .collect()
+    }
+}
+
+impl<'ctx> CallInst<'ctx> {
+    /// Gets this instruction as a `CallSiteValue`, which gives access to attributes and
+    /// calling conventions.
+    pub fn as_call_site_value(self) -> CallSiteValue<'ctx> {
+        CallSiteValue::new(self.as_value_ref())
+    }
+
+    /// Gets the value being called, which is either a function or a function pointer
+    /// (including inline assembly).
+    pub fn get_called_value(self) -> Either<FunctionValue<'ctx>, PointerValue<'ctx>> {
+        let callee = operand(self, num_operands(self) - 1);
+        let is_function = unsafe {
+            !LLVMIsAFunction(callee).is_null()
+        };
+
+        if is_function {
+            Left(FunctionValue::new(callee).expect("callee to be a function"))
+        } else {
+            Right(PointerValue::new(callee))
+        }
+    }
+
+    /// Gets the arguments passed to the callee. Arguments may be metadata, as in calls to
+    /// `llvm.dbg.value`. Token arguments, such as `token none` or the token returned by
+    /// `llvm.coro.id`, have no value type in inkwell and are given as `None`.
+    pub fn get_arguments(self) -> Vec<Option<BasicMetadataValueEnum<'ctx>>> {
+        #[cfg(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8"))]
+        let num_args = num_operands(self) - 1;
+        #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+        let num_args = unsafe {
+            LLVMGetNumArgOperands(self.as_value_ref())
+        };
+
+        (0..num_args).map(|index| {
+                         let argument = operand(self, index);
+
+                         #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7")))]
+                         {
+                             let type_kind = unsafe {
+                                 LLVMGetTypeKind(LLVMTypeOf(argument))
+                             };
+
+                             if type_kind == LLVMTypeKind::LLVMTokenTypeKind {
+                                 return None;
+                             }
+                         }
+
+                         Some(BasicMetadataValueEnum::new(argument))
+                     })
+                     .collect()
+    }
+
+    /// Determines whether this call is a tail call.
+    pub fn is_tail_call(self) -> bool {
+        unsafe {
+            LLVMIsTailCall(self.as_value_ref()) == 1
+        }
+    }
+}
+
+impl<'ctx> CmpInst<'ctx> {
+    /// Gets the predicate of this comparison, which is an `IntPredicate` for an `icmp`
+    /// and a `FloatPredicate` for an `fcmp`.
+    pub fn get_predicate(self) -> Either<IntPredicate, FloatPredicate> {
+        unsafe {
+            match self.get_opcode() {
+                InstructionOpcode::ICmp => Left(IntPredicate::new(LLVMGetICmpPredicate(self.as_value_ref()))),
+                _ => Right(FloatPredicate::new(LLVMGetFCmpPredicate(self.as_value_ref()))),
+            }
+        }
+    }
+
+    /// Gets the left hand side of this comparison.
+    pub fn get_lhs(self) -> BasicValueEnum<'ctx> {
+        BasicValueEnum::new(operand(self, 0))
+    }
+
+    /// Gets the right hand side of this comparison.
+    pub fn get_rhs(self) -> BasicValueEnum<'ctx> {
+        BasicValueEnum::new(operand(self, 1))
+    }
+}
+
+impl<'ctx> GetElementPtrInst<'ctx> {
+    /// Gets the pointer (or vector of pointers) this GEP is based on.
+    pub fn get_pointer_operand(self) -> BasicValueEnum<'ctx> {
+        BasicValueEnum::new(operand(self, 0))
+    }
+
+    /// Gets the type the pointer operand points to, which the first index steps over.
+    pub fn get_source_element_type(self) -> BasicTypeEnum<'ctx> {
+        let source_type = unsafe {
+            let mut ptr_type = LLVMTypeOf(operand(self, 0));
+
+            if LLVMGetTypeKind(ptr_type) == LLVMTypeKind::LLVMVectorTypeKind {
+                ptr_type = LLVMGetElementType(ptr_type);
+            }
+
+            LLVMGetElementType(ptr_type)
+        };
+
+        BasicTypeEnum::new(source_type)
+    }
+
+    /// Gets the indices of this GEP, which may be integers or vectors of integers.
+    pub fn get_indices(self) -> Vec<BasicValueEnum<'ctx>> {
+        (1..num_operands(self)).map(|index| BasicValueEnum::new(operand(self, index)))
+                                .collect()
+    }
+}
+
+impl<'ctx> LoadInst<'ctx> {
+    /// Gets the pointer being loaded from.
+    pub fn get_pointer_operand(self) -> PointerValue<'ctx> {
+        PointerValue::new(operand(self, 0))
+    }
+
+    /// Determines whether this load is volatile.
+    pub fn is_volatile(self) -> bool {
+        unsafe {
+            LLVMGetVolatile(self.as_value_ref()) == 1
+        }
+    }
+
+    /// Gets the alignment of this load.
+    pub fn get_alignment(self) -> u32 {
+        unsafe {
+            LLVMGetAlignment(self.as_value_ref())
+        }
+    }
+}
+
+impl<'ctx> StoreInst<'ctx> {
+    /// Gets the value being stored.
+    pub fn get_value_operand(self) -> BasicValueEnum<'ctx> {
+        BasicValueEnum::new(operand(self, 0))
+    }
+
+    /// Gets the pointer being stored to.
+    pub fn get_pointer_operand(self) -> PointerValue<'ctx> {
+        PointerValue::new(operand(self, 1))
+    }
+
+    /// Determines whether this store is volatile.
+    pub fn is_volatile(self) -> bool {
+        unsafe {
+            LLVMGetVolatile(self.as_value_ref()) == 1
+        }
+    }
+
+    /// Gets the alignment of this store.
+    pub fn get_alignment(self) -> u32 {
+        unsafe {
+            LLVMGetAlignment(self.as_value_ref())
+        }
+    }
+}
+
+impl<'ctx> SwitchInst<'ctx> {
+    /// Gets the value being switched on.
+    pub fn get_condition(self) -> IntValue<'ctx> {
+        IntValue::new(operand(self, 0))
+    }
+
+    /// Gets the block jumped to when no case matches.
+    pub fn get_default_destination(self) -> BasicBlock<'ctx> {
+        block_operand(operand(self, 1))
+    }
+
+    /// Gets the number of cases, not counting the default destination.
+    pub fn count_cases(self) -> u32 {
+        (num_operands(self) - 2) / 2
+    }
+
+    /// Gets each case value along with the block it jumps to.
+    pub fn get_cases(self) -> Vec<(IntValue<'ctx>, BasicBlock<'ctx>)> {
+        // LLVM stores the operands of a switch as [cond, default_dest, (case_value, case_dest)*]
+        (0..self.count_cases()).map(|case| {
+                                   let index = 2 + case * 2;
+
+                                   (IntValue::new(operand(self, index)), block_operand(operand(self, index + 1)))
+                               })
+                               .collect()
+    }
+}
+
+fn block_operand<'ctx>(operand: LLVMValueRef) -> BasicBlock<'ctx> {
+    let basic_block = unsafe {
+        LLVMValueAsBasicBlock(operand)
+    };
+
+    BasicBlock::new(basic_block).expect("operand to be a basic block")
+}
+
+/// An `InstructionValue` downcast according to its opcode, created by
+/// `InstructionValue::as_instruction_enum`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum InstructionEnum<'ctx> {
+    /// An `alloca` instruction.
+    Alloca(AllocaInst<'ctx>),
+    /// A `br` instruction.
+    Branch(BranchInst<'ctx>),
+    /// A `call` instruction.
+    Call(CallInst<'ctx>),
+    /// An `icmp` or `fcmp` instruction.
+    Cmp(CmpInst<'ctx>),
+    /// A `getelementptr` instruction.
+    GetElementPtr(GetElementPtrInst<'ctx>),
+    /// A `load` instruction.
+    Load(LoadInst<'ctx>),
+    /// A `store` instruction.
+    Store(StoreInst<'ctx>),
+    /// A `switch` instruction.
+    Switch(SwitchInst<'ctx>),
+    /// Any instruction which does not have a dedicated wrapper yet.
+    Other(InstructionValue<'ctx>),
+}
+
+impl<'ctx> InstructionEnum<'ctx> {
+    pub(crate) fn new(instruction: InstructionValue<'ctx>) -> Self {
+        match instruction.get_opcode() {
+            InstructionOpcode::Alloca => InstructionEnum::Alloca(AllocaInst(instruction)),
+            InstructionOpcode::Br => InstructionEnum::Branch(BranchInst(instruction)),
+            InstructionOpcode::Call => InstructionEnum::Call(CallInst(instruction)),
+            InstructionOpcode::ICmp | InstructionOpcode::FCmp => InstructionEnum::Cmp(CmpInst(instruction)),
+            InstructionOpcode::GetElementPtr => InstructionEnum::GetElementPtr(GetElementPtrInst(instruction)),
+            InstructionOpcode::Load => InstructionEnum::Load(LoadInst(instruction)),
+            InstructionOpcode::Store => InstructionEnum::Store(StoreInst(instruction)),
+            InstructionOpcode::Switch => InstructionEnum::Switch(SwitchInst(instruction)),
+            _ => InstructionEnum::Other(instruction),
+        }
+    }
+
+    /// Gets the underlying `InstructionValue`.
+    pub fn as_instruction_value(self) -> InstructionValue<'ctx> {
+        match self {
+            InstructionEnum::Alloca(inst) => inst.as_instruction_value(),
+            InstructionEnum::Branch(inst) => inst.as_instruction_value(),
+            InstructionEnum::Call(inst) => inst.as_instruction_value(),
+            InstructionEnum::Cmp(inst) => inst.as_instruction_value(),
+            InstructionEnum::GetElementPtr(inst) => inst.as_instruction_value(),
+            InstructionEnum::Load(inst) => inst.as_instruction_value(),
+            InstructionEnum::Store(inst) => inst.as_instruction_value(),
+            InstructionEnum::Switch(inst) => inst.as_instruction_value(),
+            InstructionEnum::Other(inst) => inst,
+        }
+    }
+}
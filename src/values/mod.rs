@@ -12,6 +12,8 @@ mod fn_value;
 mod generic_value;
 mod global_value;
 mod instruction_value;
+#[deny(missing_docs)]
+mod instructions;
 mod int_value;
 mod metadata_value;
 mod phi_value;
@@ -22,7 +24,7 @@ mod vec_value;
 
 use crate::support::LLVMString;
 pub use crate::values::array_value::ArrayValue;
-pub use crate::values::basic_value_use::BasicValueUse;
+pub use crate::values::basic_value_use::{BasicValueUse, BasicValueUseIter};
 pub use crate::values::call_site_value::CallSiteValue;
 pub use crate::values::enums::{AnyValueEnum, AggregateValueEnum, BasicValueEnum, BasicMetadataValueEnum};
 pub use crate::values::float_value::FloatValue;
@@ -31,11 +33,12 @@ pub use crate::values::generic_value::GenericValue;
 pub use crate::values::global_value::GlobalValue;
 #[llvm_versions(7.0..=latest)]
 pub use crate::values::global_value::UnnamedAddress;
-pub use crate::values::instruction_value::{InstructionValue, InstructionOpcode};
+pub use crate::values::instruction_value::{InstructionValue, InstructionOpcode, OperandIter};
+pub use crate::values::instructions::{AllocaInst, BranchInst, CallInst, CmpInst, GetElementPtrInst, InstructionEnum, LoadInst, StoreInst, SwitchInst};
 pub use crate::values::int_value::IntValue;
 pub use crate::values::metadata_value::{MetadataValue, FIRST_CUSTOM_METADATA_KIND_ID};
 pub use crate::values::phi_value::PhiValue;
-pub use crate::values::ptr_value::PointerValue;
+pub use crate::values::ptr_value::{GEPError, PointerValue};
 pub use crate::values::struct_value::StructValue;
 pub use crate::values::traits::{AnyValue, AggregateValue, BasicValue, IntMathValue, FloatMathValue, PointerMathValue};
 pub use crate::values::vec_value::VectorValue;
@@ -1,11 +1,47 @@
 use llvm_sys::core::{LLVMConstGEP, LLVMConstInBoundsGEP, LLVMConstPtrToInt, LLVMConstPointerCast, LLVMConstAddrSpaceCast};
 use llvm_sys::prelude::LLVMValueRef;
 
+use std::error::Error;
 use std::ffi::CStr;
+use std::fmt::{self, Display, Formatter};
 
-use crate::types::{AsTypeRef, IntType, PointerType};
+use crate::types::{AsTypeRef, BasicType, BasicTypeEnum, IntType, PointerType};
 use crate::values::{AsValueRef, InstructionValue, IntValue, Value};
 
+/// Errors that can occur when validating the indices of a GEP against its pointee type.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GEPError {
+    /// The pointer points to an unsized type, such as an opaque struct or a function.
+    UnsizedPointee,
+    /// The index at `position` indexes into a struct but is not a constant.
+    NonConstantStructIndex { position: usize },
+    /// The index at `position` indexes into a struct but is not an `i32`.
+    InvalidStructIndexType { position: usize },
+    /// The index at `position` is past the last field of the struct it indexes into.
+    StructIndexOutOfBounds { position: usize, index: u64, num_fields: u32 },
+    /// The index at `position` indexes into a type which is not an aggregate.
+    TooManyIndices { position: usize },
+    /// The index at `position` is not a constant, which a constant GEP expression requires.
+    NonConstantIndex { position: usize },
+}
+
+impl Error for GEPError {}
+
+impl Display for GEPError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            GEPError::UnsizedPointee => write!(f, "GEPError(Pointee type is unsized)"),
+            GEPError::NonConstantStructIndex { position } => write!(f, "GEPError(Struct index at position {} is not a constant)", position),
+            GEPError::InvalidStructIndexType { position } => write!(f, "GEPError(Struct index at position {} is not an i32)", position),
+            GEPError::StructIndexOutOfBounds { position, index, num_fields } => {
+                write!(f, "GEPError(Struct index {} at position {} is out of bounds for a struct with {} fields)", index, position, num_fields)
+            },
+            GEPError::TooManyIndices { position } => write!(f, "GEPError(Index at position {} does not index into an aggregate)", position),
+            GEPError::NonConstantIndex { position } => write!(f, "GEPError(Index at position {} is not a constant)", position),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct PointerValue<'ctx> {
     ptr_value: Value<'ctx>,
@@ -86,6 +122,93 @@ impl<'ctx> PointerValue<'ctx> {
         PointerValue::new(value)
     }
 
+    /// Checks the indices against the pointee type and then builds a constant GEP
+    /// expression, so that incorrect indices produce an error rather than a crash.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("const_gep");
+    /// let i32_type = context.i32_type();
+    /// let struct_type = context.struct_type(&[i32_type.into(), i32_type.into()], false);
+    /// let global = module.add_global(struct_type, None, "global");
+    /// let ptr = global.as_pointer_value();
+    ///
+    /// let zero = i32_type.const_zero();
+    /// let one = i32_type.const_int(1, false);
+    /// let two = i32_type.const_int(2, false);
+    ///
+    /// assert!(ptr.const_checked_gep(&[zero, one]).is_ok());
+    /// assert!(ptr.const_checked_gep(&[zero, two]).is_err());
+    /// ```
+    pub fn const_checked_gep(self, ordered_indexes: &[IntValue<'ctx>]) -> Result<PointerValue<'ctx>, GEPError> {
+        validate_const_gep_indexes(ordered_indexes)?;
+        self.validate_gep_indexes(ordered_indexes)?;
+
+        unsafe {
+            Ok(self.const_gep(ordered_indexes))
+        }
+    }
+
+    /// Checks the indices against the pointee type and then builds a constant in bounds
+    /// GEP expression, so that incorrect indices produce an error rather than a crash.
+    pub fn const_checked_in_bounds_gep(self, ordered_indexes: &[IntValue<'ctx>]) -> Result<PointerValue<'ctx>, GEPError> {
+        validate_const_gep_indexes(ordered_indexes)?;
+        self.validate_gep_indexes(ordered_indexes)?;
+
+        unsafe {
+            Ok(self.const_in_bounds_gep(ordered_indexes))
+        }
+    }
+
+    /// Walks the pointee type along the given GEP indices. The first index steps over
+    /// the pointer itself and every following one must index into a struct, array or vector.
+    pub(crate) fn validate_gep_indexes(self, ordered_indexes: &[IntValue<'ctx>]) -> Result<(), GEPError> {
+        let pointee_type = self.get_type().get_element_type();
+
+        if pointee_type.is_function_type() || pointee_type.is_void_type() {
+            return Err(GEPError::UnsizedPointee);
+        }
+
+        let mut current_type = pointee_type.to_basic_type_enum();
+
+        if !current_type.is_sized() {
+            return Err(GEPError::UnsizedPointee);
+        }
+
+        for (position, index) in ordered_indexes.iter().enumerate().skip(1) {
+            current_type = match current_type {
+                BasicTypeEnum::StructType(struct_type) => {
+                    if !index.is_constant_int() {
+                        return Err(GEPError::NonConstantStructIndex { position });
+                    }
+
+                    if index.get_type().get_bit_width() != 32 {
+                        return Err(GEPError::InvalidStructIndexType { position });
+                    }
+
+                    let field_index = index.get_zero_extended_constant().expect("i32 constant to fit in a u64");
+                    let num_fields = struct_type.count_fields();
+
+                    if field_index >= num_fields as u64 {
+                        return Err(GEPError::StructIndexOutOfBounds { position, index: field_index, num_fields });
+                    }
+
+                    struct_type.get_field_type_at_index(field_index as u32).expect("field index to be in bounds")
+                },
+                BasicTypeEnum::ArrayType(array_type) => array_type.get_element_type(),
+                BasicTypeEnum::VectorType(vector_type) => vector_type.get_element_type(),
+                _ => return Err(GEPError::TooManyIndices { position }),
+            };
+        }
+
+        Ok(())
+    }
+
     pub fn const_to_int(self, int_type: IntType<'ctx>) -> IntValue<'ctx> {
         let value = unsafe {
             LLVMConstPtrToInt(self.as_value_ref(), int_type.as_type_ref())
@@ -115,6 +238,14 @@ impl<'ctx> PointerValue<'ctx> {
     }
 }
 
+/// Constant GEP expressions can only be built from constant indices, whatever they index into.
+fn validate_const_gep_indexes(ordered_indexes: &[IntValue]) -> Result<(), GEPError> {
+    match ordered_indexes.iter().position(|index| !index.is_const()) {
+        Some(position) => Err(GEPError::NonConstantIndex { position }),
+        None => Ok(()),
+    }
+}
+
 impl AsValueRef for PointerValue<'_> {
     fn as_value_ref(&self) -> LLVMValueRef {
         self.ptr_value.value
@@ -3,7 +3,7 @@ use llvm_sys::core::{LLVMConstExtractValue, LLVMConstInsertValue};
 
 use std::fmt::Debug;
 
-use crate::values::{ArrayValue, AggregateValueEnum, BasicValueUse, CallSiteValue, GlobalValue, StructValue, BasicValueEnum, AnyValueEnum, IntValue, FloatValue, PointerValue, PhiValue, VectorValue, FunctionValue, InstructionValue, Value};
+use crate::values::{ArrayValue, AggregateValueEnum, BasicValueUse, BasicValueUseIter, CallSiteValue, GlobalValue, StructValue, BasicValueEnum, AnyValueEnum, IntValue, FloatValue, PointerValue, PhiValue, VectorValue, FunctionValue, InstructionValue, Value};
 use crate::types::{IntMathType, FloatMathType, PointerMathType, IntType, FloatType, PointerType, VectorType};
 use crate::support::LLVMString;
 
@@ -89,6 +89,11 @@ pub trait BasicValue<'ctx>: AnyValue<'ctx> {
         Value::new(self.as_value_ref()).get_first_use()
     }
 
+    /// Iterates over the uses of this value, starting with `get_first_use`.
+    fn get_uses(&self) -> BasicValueUseIter<'ctx> {
+        BasicValueUseIter::new(Value::new(self.as_value_ref()).get_first_use())
+    }
+
     /// Sets the name of a `BasicValue`. If the value is a constant, this is a noop.
     fn set_name(&self, name: &str) {
         Value::new(self.as_value_ref()).set_name(name)
@@ -4,6 +4,8 @@ extern crate either;
 #[macro_use]
 extern crate inkwell_internals;
 
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+mod test_abi;
 #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
 mod test_attributes;
 mod test_basic_block;
@@ -21,6 +23,19 @@ mod test_context;
 mod test_debug_info;
 mod test_execution_engine;
 mod test_instruction_values;
+#[cfg(not(any(
+    feature = "llvm3-6",
+    feature = "llvm3-7",
+    feature = "llvm3-8",
+    feature = "llvm3-9",
+    feature = "llvm4-0",
+    feature = "llvm5-0",
+    feature = "llvm6-0",
+    feature = "llvm7-0",
+    feature = "llvm8-0"
+)))]
+mod test_intrinsics;
+mod test_lto;
 mod test_module;
 mod test_object_file;
 mod test_passes;
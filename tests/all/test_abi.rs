@@ -0,0 +1,221 @@
+use inkwell::OptimizationLevel;
+use inkwell::abi::{Abi, FnAbi, PassMode};
+use inkwell::attributes::{Attribute, AttributeLoc};
+use inkwell::context::Context;
+use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetData, TargetMachine};
+use inkwell::types::BasicType;
+
+const X86_64_LINUX_LAYOUT: &str = "e-m:e-i64:64-f80:128-n8:16:32:64-S128";
+const AARCH64_LINUX_LAYOUT: &str = "e-m:e-i8:8:32-i16:16:32-i64:64-i128:128-n32:64-S128";
+
+#[test]
+fn test_x86_64_sysv_lowering() {
+    let context = Context::create();
+    let module = context.create_module("abi");
+    let target_data = TargetData::create(X86_64_LINUX_LAYOUT);
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let f32_type = context.f32_type();
+    let f64_type = context.f64_type();
+    let two_ints = context.struct_type(&[i32_type.into(), i32_type.into()], false);
+    let three_floats = context.struct_type(&[f32_type.into(), f32_type.into(), f32_type.into()], false);
+    let mixed = context.struct_type(&[f64_type.into(), i64_type.into()], false);
+    let big = context.struct_type(&[i64_type.into(), i64_type.into(), i64_type.into()], false);
+    let packed = context.struct_type(&[i8_type.into(), i32_type.into()], true);
+    let empty = context.struct_type(&[], false);
+    let params = [
+        two_ints.into(), three_floats.into(), mixed.into(), big.into(), packed.into(), empty.into(),
+    ];
+    let fn_abi = FnAbi::new(Abi::X86_64SysV, &context, &target_data, Some(big.into()), &params, false);
+    let modes: Vec<_> = fn_abi.get_params().iter().map(|param| param.get_pass_mode()).collect();
+    let float_pair = context.struct_type(&[f32_type.vec_type(2).into(), f32_type.into()], false);
+
+    assert_eq!(fn_abi.get_abi(), Abi::X86_64SysV);
+    assert_eq!(modes, vec![
+        PassMode::Coerce(i64_type.into()),
+        PassMode::Coerce(float_pair.into()),
+        PassMode::Coerce(mixed.into()),
+        PassMode::Indirect { byval: true, align: 8 },
+        PassMode::Indirect { byval: true, align: 8 },
+        PassMode::Ignore,
+    ]);
+
+    let ret = fn_abi.get_return().unwrap();
+
+    assert_eq!(ret.get_pass_mode(), PassMode::Indirect { byval: false, align: 8 });
+    assert_eq!(ret.get_lowered_index(), Some(0));
+    assert_eq!(fn_abi.get_params()[0].get_lowered_index(), Some(1));
+    assert!(fn_abi.get_params()[5].get_lowered_index().is_none());
+
+    let fn_type = fn_abi.get_fn_type();
+    let big_ptr_type = big.ptr_type(inkwell::AddressSpace::Generic);
+
+    assert!(fn_type.get_return_type().is_none());
+    assert_eq!(fn_type.get_param_types(), vec![
+        big_ptr_type.into(),
+        i64_type.into(),
+        float_pair.into(),
+        mixed.into(),
+        big_ptr_type.into(),
+        packed.ptr_type(inkwell::AddressSpace::Generic).into(),
+    ]);
+
+    let function = fn_abi.add_function(&module, "lowered", None);
+    let sret = Attribute::get_named_enum_kind_id("sret");
+    let byval = Attribute::get_named_enum_kind_id("byval");
+    let align = Attribute::get_named_enum_kind_id("align");
+
+    assert!(function.get_enum_attribute(AttributeLoc::Param(0), sret).is_some());
+    assert!(function.get_enum_attribute(AttributeLoc::Param(4), byval).is_some());
+    assert_eq!(function.get_enum_attribute(AttributeLoc::Param(4), align).unwrap().get_enum_value(), 8);
+    assert_eq!(function.count_attributes(AttributeLoc::Param(1)), 0);
+
+    // Once the integer registers run out, small aggregates go to memory too
+    let mut params = vec![i64_type.as_basic_type_enum(); 6];
+
+    params.push(two_ints.into());
+
+    let fn_abi = FnAbi::new(Abi::X86_64SysV, &context, &target_data, None, &params, false);
+
+    assert_eq!(fn_abi.get_params()[6].get_pass_mode(), PassMode::Indirect { byval: true, align: 8 });
+}
+
+#[test]
+fn test_aarch64_aapcs_lowering() {
+    let context = Context::create();
+    let target_data = TargetData::create(AARCH64_LINUX_LAYOUT);
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let f32_type = context.f32_type();
+    let hfa = context.struct_type(&[f32_type.into(); 4], false);
+    let three_ints = context.struct_type(&[i32_type.into(), i32_type.into(), i32_type.into()], false);
+    let three_bytes = context.struct_type(&[i8_type.into(), i8_type.into(), i8_type.into()], false);
+    let big = context.struct_type(&[i64_type.into(), i64_type.into(), i64_type.into()], false);
+    let params = [hfa.into(), three_ints.into(), three_bytes.into(), big.into()];
+    let fn_abi = FnAbi::new(Abi::AArch64AAPCS, &context, &target_data, Some(three_bytes.into()), &params, false);
+    let modes: Vec<_> = fn_abi.get_params().iter().map(|param| param.get_pass_mode()).collect();
+
+    assert_eq!(modes, vec![
+        PassMode::Coerce(f32_type.array_type(4).into()),
+        PassMode::Coerce(i64_type.array_type(2).into()),
+        PassMode::Coerce(i64_type.into()),
+        PassMode::Indirect { byval: false, align: 8 },
+    ]);
+    assert_eq!(fn_abi.get_return().unwrap().get_pass_mode(), PassMode::Coerce(context.custom_width_int_type(24).into()));
+    assert_eq!(fn_abi.get_fn_type().get_return_type(), Some(context.custom_width_int_type(24).into()));
+    assert!(fn_abi.get_attributes().is_empty());
+
+    let fn_abi = FnAbi::new(Abi::AArch64AAPCS, &context, &target_data, Some(big.into()), &[], false);
+
+    assert_eq!(fn_abi.get_return().unwrap().get_pass_mode(), PassMode::Indirect { byval: false, align: 8 });
+    assert_eq!(fn_abi.get_attributes().len(), 2);
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Big {
+    a: i64,
+    b: i64,
+    c: i64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Pair {
+    x: f32,
+    y: f32,
+}
+
+extern "C" fn rust_combine(big: Big, pair: Pair) -> Big {
+    Big {
+        a: big.a + big.b,
+        b: big.c,
+        c: (pair.x + pair.y) as i64,
+    }
+}
+
+#[test]
+fn test_abi_marshalling() {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let triple = TargetMachine::get_default_triple();
+    let abi = match Abi::from_triple(&triple.parse().unwrap()) {
+        Some(abi) => abi,
+        None => return,
+    };
+    let target = Target::from_triple(&triple).unwrap();
+    let target_machine = target.create_target_machine(
+        &triple,
+        "generic",
+        "",
+        OptimizationLevel::None,
+        RelocMode::Default,
+        CodeModel::JITDefault,
+    ).unwrap();
+    let target_data = target_machine.get_target_data();
+
+    let context = Context::create();
+    let module = context.create_module("abi");
+    let builder = context.create_builder();
+    let i64_type = context.i64_type();
+    let f32_type = context.f32_type();
+    let big_type = context.struct_type(&[i64_type.into(), i64_type.into(), i64_type.into()], false);
+    let pair_type = context.struct_type(&[f32_type.into(), f32_type.into()], false);
+    let fn_abi = FnAbi::new(abi, &context, &target_data, Some(big_type.into()), &[big_type.into(), pair_type.into()], false);
+
+    // A function with the C signature defined in IR, called from Rust
+    let combine = fn_abi.add_function(&module, "combine", None);
+
+    builder.position_at_end(context.append_basic_block(combine, "entry"));
+
+    let params = fn_abi.build_prologue(&builder, combine);
+    let big = params[0].into_struct_value();
+    let pair = params[1].into_struct_value();
+    let a = builder.build_extract_value(big, 0, "a").unwrap().into_int_value();
+    let b = builder.build_extract_value(big, 1, "b").unwrap().into_int_value();
+    let c = builder.build_extract_value(big, 2, "c").unwrap();
+    let x = builder.build_extract_value(pair, 0, "x").unwrap().into_float_value();
+    let y = builder.build_extract_value(pair, 1, "y").unwrap().into_float_value();
+    let sum = builder.build_float_add(x, y, "sum");
+    let result = builder.build_insert_value(big_type.get_undef(), builder.build_int_add(a, b, "a_b"), 0, "").unwrap();
+    let result = builder.build_insert_value(result, c, 1, "").unwrap();
+    let result = builder.build_insert_value(result, builder.build_float_to_signed_int(sum, i64_type, ""), 2, "").unwrap();
+
+    fn_abi.build_return(&builder, combine, Some(result.into_struct_value().into()));
+
+    // A function calling into Rust through the C signature
+    let rust_fn = fn_abi.add_function(&module, "rust_combine", None);
+    let call_rust = module.add_function("call_rust", i64_type.fn_type(&[], false), None);
+
+    builder.position_at_end(context.append_basic_block(call_rust, "entry"));
+
+    let big = big_type.const_named_struct(&[
+        i64_type.const_int(1, false).into(),
+        i64_type.const_int(2, false).into(),
+        i64_type.const_int(3, false).into(),
+    ]);
+    let pair = pair_type.const_named_struct(&[f32_type.const_float(1.5).into(), f32_type.const_float(2.5).into()]);
+    let result = fn_abi.build_call(&builder, rust_fn, &[big.into(), pair.into()], "result").unwrap();
+    let a = builder.build_extract_value(result.into_struct_value(), 0, "a").unwrap().into_int_value();
+    let c = builder.build_extract_value(result.into_struct_value(), 2, "c").unwrap().into_int_value();
+
+    builder.build_return(Some(&builder.build_int_add(a, c, "a_c")));
+
+    assert!(module.verify().is_ok());
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    execution_engine.add_global_mapping(&rust_fn, rust_combine as *const () as usize);
+
+    unsafe {
+        let combine = execution_engine.get_function::<unsafe extern "C" fn(Big, Pair) -> Big>("combine").unwrap();
+
+        assert_eq!(combine.call(Big { a: 1, b: 2, c: 3 }, Pair { x: 0.5, y: 3.5 }), Big { a: 3, b: 3, c: 4 });
+
+        let call_rust = execution_engine.get_function::<unsafe extern "C" fn() -> i64>("call_rust").unwrap();
+
+        assert_eq!(call_rust.call(), 7);
+    }
+}
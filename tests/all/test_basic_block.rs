@@ -201,3 +201,62 @@ fn test_get_first_use() {
     assert_eq!(bb1.get_first_use().unwrap().get_user(), branch_inst);
     assert!(bb1.get_first_use().unwrap().get_next_use().is_none());
 }
+
+#[test]
+fn test_instruction_and_basic_block_iters() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = context.void_type().fn_type(&[], false);
+    let function = module.add_function("testing", fn_type, None);
+
+    assert_eq!(function.get_basic_block_iter().count(), 0);
+
+    let entry = context.append_basic_block(function, "entry");
+    let middle = context.append_basic_block(function, "middle");
+    let end = context.append_basic_block(function, "end");
+
+    assert_eq!(entry.get_instructions().count(), 0);
+    assert_eq!(function.get_basic_block_iter().collect::<Vec<_>>(), [entry, middle, end]);
+    assert_eq!(function.get_basic_block_iter().rev().collect::<Vec<_>>(), [end, middle, entry]);
+
+    let mut iter = function.get_basic_block_iter();
+
+    assert_eq!(iter.next(), Some(entry));
+    assert_eq!(iter.next_back(), Some(end));
+    assert_eq!(iter.next(), Some(middle));
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+
+    builder.position_at_end(entry);
+    builder.build_alloca(i32_type, "a");
+    builder.build_alloca(i32_type, "b");
+    builder.build_unconditional_branch(middle);
+
+    let opcodes: Vec<_> = entry.get_instructions().map(|instruction| instruction.get_opcode()).collect();
+
+    assert_eq!(opcodes, [InstructionOpcode::Alloca, InstructionOpcode::Alloca, InstructionOpcode::Br]);
+    assert_eq!(entry.get_instructions().rev().next(), entry.get_last_instruction());
+    assert_eq!(middle.get_uses().count(), 1);
+
+    // Erasing the current instruction must not invalidate the iterator
+    for instruction in entry.get_instructions() {
+        if instruction.get_opcode() == InstructionOpcode::Alloca {
+            instruction.erase_from_basic_block();
+        }
+    }
+
+    let opcodes: Vec<_> = entry.get_instructions().map(|instruction| instruction.get_opcode()).collect();
+
+    assert_eq!(opcodes, [InstructionOpcode::Br]);
+
+    // Same goes for the current basic block
+    for basic_block in function.get_basic_block_iter() {
+        if basic_block != entry {
+            basic_block.remove_from_function().unwrap();
+        }
+    }
+
+    assert_eq!(function.get_basic_block_iter().collect::<Vec<_>>(), [entry]);
+}
@@ -1,6 +1,7 @@
-use inkwell::{AddressSpace, AtomicOrdering, AtomicRMWBinOp, OptimizationLevel};
+use inkwell::{AddressSpace, AtomicOrdering, AtomicRMWBinOp, IntPredicate, OptimizationLevel};
+use inkwell::builder::BuilderError;
 use inkwell::context::Context;
-use inkwell::values::BasicValue;
+use inkwell::values::{BasicValue, GEPError, InstructionOpcode};
 
 use std::ptr::null;
 
@@ -1008,3 +1009,411 @@ fn test_safe_struct_gep() {
     assert!(builder.build_struct_gep(struct_ptr, 1, "struct_gep").is_ok());
     assert!(builder.build_struct_gep(struct_ptr, 2, "struct_gep").is_err());
 }
+
+#[test]
+fn test_checked_gep() {
+    let context = Context::create();
+    let builder = context.create_builder();
+    let module = context.create_module("checked_gep");
+    let void_type = context.void_type();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let array_type = i32_type.array_type(4);
+    let vec_type = i32_type.vec_type(2);
+    let struct_type = context.struct_type(&[i32_type.into(), array_type.into(), vec_type.into()], false);
+    let struct_ptr_type = struct_type.ptr_type(AddressSpace::Generic);
+    let opaque_type = context.opaque_struct_type("opaque");
+    let opaque_ptr_type = opaque_type.ptr_type(AddressSpace::Generic);
+    let fn_type = void_type.fn_type(&[struct_ptr_type.into(), opaque_ptr_type.into(), i32_type.into()], false);
+    let fn_value = module.add_function("checked_gep", fn_type, None);
+    let entry = context.append_basic_block(fn_value, "entry");
+
+    builder.position_at_end(entry);
+
+    let struct_ptr = fn_value.get_nth_param(0).unwrap().into_pointer_value();
+    let opaque_ptr = fn_value.get_nth_param(1).unwrap().into_pointer_value();
+    let dynamic_index = fn_value.get_nth_param(2).unwrap().into_int_value();
+    let zero = i32_type.const_zero();
+    let one = i32_type.const_int(1, false);
+    let two = i32_type.const_int(2, false);
+    let three = i32_type.const_int(3, false);
+
+    assert!(builder.build_checked_gep(struct_ptr, &[], "no_indices").is_ok());
+    assert!(builder.build_checked_gep(struct_ptr, &[dynamic_index], "ptr_offset").is_ok());
+    assert!(builder.build_checked_gep(struct_ptr, &[zero, zero], "field").is_ok());
+    assert!(builder.build_checked_gep(struct_ptr, &[zero, one, dynamic_index], "array_elem").is_ok());
+    assert!(builder.build_checked_in_bounds_gep(struct_ptr, &[zero, two, one], "vec_elem").is_ok());
+
+    assert_eq!(
+        builder.build_checked_gep(struct_ptr, &[zero, three], "oob_field"),
+        Err(GEPError::StructIndexOutOfBounds { position: 1, index: 3, num_fields: 3 }),
+    );
+    assert_eq!(
+        builder.build_checked_gep(struct_ptr, &[zero, dynamic_index], "dynamic_field"),
+        Err(GEPError::NonConstantStructIndex { position: 1 }),
+    );
+    assert_eq!(
+        builder.build_checked_gep(struct_ptr, &[zero, i64_type.const_zero()], "i64_field"),
+        Err(GEPError::InvalidStructIndexType { position: 1 }),
+    );
+    assert_eq!(
+        builder.build_checked_in_bounds_gep(struct_ptr, &[zero, zero, zero], "too_deep"),
+        Err(GEPError::TooManyIndices { position: 2 }),
+    );
+    assert_eq!(
+        builder.build_checked_gep(opaque_ptr, &[zero], "opaque"),
+        Err(GEPError::UnsizedPointee),
+    );
+}
+
+#[test]
+fn test_build_invoke_landing_pad() {
+    let context = Context::create();
+    let module = context.create_module("eh");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    let exception_type = context.struct_type(&[i8_ptr_type.into(), i32_type.into()], false);
+
+    let personality_type = i32_type.fn_type(&[], true);
+    let personality = module.add_function("__gxx_personality_v0", personality_type, None);
+    let type_info = module.add_global(i8_ptr_type, None, "type_info");
+
+    let may_throw = module.add_function("may_throw", i32_type.fn_type(&[i32_type.into()], false), None);
+    let function = module.add_function("wrapper", i32_type.fn_type(&[i32_type.into()], false), None);
+    let entry = context.append_basic_block(function, "entry");
+    let then_block = context.append_basic_block(function, "then");
+    let catch_block = context.append_basic_block(function, "catch");
+    let arg = function.get_first_param().unwrap();
+
+    builder.position_at_end(entry);
+
+    let call_site = builder.build_invoke(may_throw, &[arg], then_block, catch_block, "call");
+    let invoke = entry.get_terminator().unwrap();
+
+    assert_eq!(invoke.get_opcode(), InstructionOpcode::Invoke);
+
+    builder.position_at_end(then_block);
+    builder.build_return(Some(&call_site.try_as_basic_value().left().unwrap()));
+
+    builder.position_at_end(catch_block);
+
+    let landing_pad = builder.build_landing_pad(exception_type, personality, &[type_info.as_pointer_value().into()], true, "res");
+
+    assert!(landing_pad.is_struct_value());
+
+    let resume = builder.build_resume(landing_pad);
+
+    assert_eq!(resume.get_opcode(), InstructionOpcode::Resume);
+    assert!(module.verify().is_ok());
+
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    {
+        assert_eq!(function.get_personality_function(), Some(personality));
+        assert_eq!(invoke.get_normal_destination(), Some(then_block));
+        assert_eq!(invoke.get_unwind_destination(), Some(catch_block));
+        assert_eq!(resume.get_normal_destination(), None);
+    }
+
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9",
+                  feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0", feature = "llvm7-0")))]
+    {
+        let landing_pad = landing_pad.as_instruction_value().unwrap();
+
+        assert_eq!(landing_pad.get_opcode(), InstructionOpcode::LandingPad);
+        assert_eq!(landing_pad.is_cleanup(), Some(true));
+        assert_eq!(landing_pad.get_clauses(), vec![type_info.as_pointer_value().as_basic_value_enum()]);
+        assert_eq!(resume.is_cleanup(), None);
+    }
+}
+
+#[llvm_versions(8.0..=latest)]
+#[test]
+fn test_build_funclet_eh() {
+    let context = Context::create();
+    let module = context.create_module("eh");
+    let builder = context.create_builder();
+    let void_type = context.void_type();
+    let i32_type = context.i32_type();
+
+    let personality = module.add_function("__CxxFrameHandler3", i32_type.fn_type(&[], true), None);
+    let may_throw = module.add_function("may_throw", void_type.fn_type(&[], false), None);
+    let function = module.add_function("wrapper", void_type.fn_type(&[], false), None);
+
+    function.set_personality_function(personality);
+
+    let entry = context.append_basic_block(function, "entry");
+    let call_cont = context.append_basic_block(function, "call_cont");
+    let exit = context.append_basic_block(function, "exit");
+    let cleanup = context.append_basic_block(function, "cleanup");
+    let dispatch = context.append_basic_block(function, "dispatch");
+    let handler = context.append_basic_block(function, "handler");
+
+    builder.position_at_end(entry);
+    builder.build_invoke(may_throw, &[], call_cont, cleanup, "");
+
+    builder.position_at_end(call_cont);
+    builder.build_invoke(may_throw, &[], exit, dispatch, "");
+
+    builder.position_at_end(exit);
+    builder.build_return(None);
+
+    builder.position_at_end(cleanup);
+
+    let cleanup_pad = builder.build_cleanup_pad(None, &[], "cleanup_pad");
+    let cleanup_ret = builder.build_cleanup_ret(cleanup_pad, None);
+
+    builder.position_at_end(dispatch);
+
+    let catch_switch = builder.build_catch_switch(None, None, &[handler], "cs");
+
+    builder.position_at_end(handler);
+
+    let null = context.i8_type().ptr_type(AddressSpace::Generic).const_null();
+    let catch_pad = builder.build_catch_pad(catch_switch, &[null.into(), i32_type.const_int(64, false).into(), null.into()], "cp");
+    let catch_ret = builder.build_catch_ret(catch_pad, exit);
+
+    assert!(module.verify().is_ok());
+
+    assert_eq!(cleanup_pad.get_opcode(), InstructionOpcode::CleanupPad);
+    assert_eq!(cleanup_ret.get_opcode(), InstructionOpcode::CleanupRet);
+    assert_eq!(cleanup_ret.get_unwind_destination(), None);
+    assert_eq!(catch_switch.get_opcode(), InstructionOpcode::CatchSwitch);
+    assert_eq!(catch_switch.get_handlers(), vec![handler]);
+    assert_eq!(catch_switch.get_unwind_destination(), None);
+    assert_eq!(catch_pad.get_opcode(), InstructionOpcode::CatchPad);
+    assert_eq!(catch_pad.get_parent_catch_switch(), Some(catch_switch));
+    assert_eq!(catch_ret.get_opcode(), InstructionOpcode::CatchRet);
+    assert!(catch_ret.get_handlers().is_empty());
+}
+
+#[test]
+fn test_try_build() {
+    let context = Context::create();
+    let builder = context.create_builder();
+    let module = context.create_module("try_build");
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let f32_type = context.f32_type();
+    let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    let fn_type = i32_type.fn_type(&[i32_type.into(), i32_ptr_type.into()], false);
+    let fn_value = module.add_function("try_build", fn_type, None);
+    let var_args_fn = module.add_function("var_args", i32_type.fn_type(&[i32_type.into()], true), None);
+    let entry = context.append_basic_block(fn_value, "entry");
+    let then_block = context.append_basic_block(fn_value, "then");
+    let else_block = context.append_basic_block(fn_value, "else");
+
+    let i32_arg = fn_value.get_first_param().unwrap().into_int_value();
+    let ptr_arg = fn_value.get_last_param().unwrap().into_pointer_value();
+    let i32_zero = i32_type.const_zero();
+    let i64_zero = i64_type.const_zero();
+
+    assert_eq!(builder.try_build_int_add(i32_arg, i32_zero, "add"), Err(BuilderError::UnsetPosition));
+    assert_eq!(builder.try_build_return(None), Err(BuilderError::UnsetPosition));
+
+    builder.position_at_end(entry);
+
+    // Binary operands
+    assert!(builder.try_build_int_add(i32_arg, i32_zero, "add").is_ok());
+    assert_eq!(builder.try_build_int_add(i32_arg, i64_zero, "add"), Err(BuilderError::OperandTypeMismatch));
+    assert_eq!(builder.try_build_int_compare(IntPredicate::EQ, i32_arg, i64_zero, "cmp"), Err(BuilderError::OperandTypeMismatch));
+    assert_eq!(
+        builder.try_build_float_add(f32_type.const_zero(), context.f64_type().const_zero(), "fadd"),
+        Err(BuilderError::OperandTypeMismatch),
+    );
+
+    // Calls
+    let args = [i32_zero.into(), ptr_arg.into()];
+
+    assert!(builder.try_build_call(fn_value, &args, "call").is_ok());
+    assert_eq!(
+        builder.try_build_call(fn_value, &args[..1], "call"),
+        Err(BuilderError::WrongArgumentCount { expected: 2, found: 1, is_var_args: false }),
+    );
+    assert_eq!(
+        builder.try_build_call(fn_value, &[i64_zero.into(), ptr_arg.into()], "call"),
+        Err(BuilderError::ArgumentTypeMismatch { position: 0 }),
+    );
+    assert_eq!(builder.try_build_call(ptr_arg, &[], "call"), Err(BuilderError::NotAFunctionPointer));
+    assert!(builder.try_build_call(var_args_fn, &[i32_zero.into(), i64_zero.into()], "call").is_ok());
+    assert_eq!(
+        builder.try_build_call(var_args_fn, &[], "call"),
+        Err(BuilderError::WrongArgumentCount { expected: 1, found: 0, is_var_args: true }),
+    );
+
+    // Memory
+    assert!(builder.try_build_store(ptr_arg, i32_zero).is_ok());
+    assert_eq!(builder.try_build_store(ptr_arg, i64_zero), Err(BuilderError::StoreTypeMismatch));
+    assert!(builder.try_build_load(ptr_arg, "load").is_ok());
+    assert_eq!(builder.try_build_struct_gep(ptr_arg, 0, "gep"), Err(BuilderError::NotAStructPointer));
+    assert!(builder.try_build_gep(ptr_arg, &[i32_arg], "gep").is_ok());
+
+    // Control flow
+    assert_eq!(builder.try_build_conditional_branch(i32_arg, then_block, else_block), Err(BuilderError::NonBooleanCondition));
+
+    let cond = builder.try_build_int_compare(IntPredicate::EQ, i32_arg, i32_zero, "cond").unwrap();
+
+    assert!(builder.try_build_conditional_branch(cond, then_block, else_block).is_ok());
+
+    builder.position_at_end(then_block);
+
+    assert_eq!(builder.try_build_return(None), Err(BuilderError::ReturnTypeMismatch));
+    assert_eq!(builder.try_build_return(Some(&i64_zero)), Err(BuilderError::ReturnTypeMismatch));
+    assert!(builder.try_build_return(Some(&i32_zero)).is_ok());
+
+    builder.position_at_end(else_block);
+
+    assert_eq!(
+        builder.try_build_phi(i32_type, &[(&i64_zero, entry)], "phi"),
+        Err(BuilderError::IncomingTypeMismatch { position: 0 }),
+    );
+    assert!(builder.try_build_phi(i32_type, &[(&i32_zero, entry)], "phi").is_ok());
+}
+
+#[test]
+fn test_try_build_casts_aggregates_and_atomics() {
+    let context = Context::create();
+    let builder = context.create_builder();
+    let module = context.create_module("try_build");
+    let bool_type = context.bool_type();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let f32_type = context.f32_type();
+    let f64_type = context.f64_type();
+    let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    let struct_type = context.struct_type(&[i32_type.into(), f32_type.into()], false);
+    let fn_type = context.void_type().fn_type(&[i32_type.into(), i32_ptr_type.into(), struct_type.into()], false);
+    let fn_value = module.add_function("try_build", fn_type, None);
+    let entry = context.append_basic_block(fn_value, "entry");
+    let exit = context.append_basic_block(fn_value, "exit");
+
+    let i32_arg = fn_value.get_first_param().unwrap().into_int_value();
+    let ptr_arg = fn_value.get_nth_param(1).unwrap().into_pointer_value();
+    let struct_arg = fn_value.get_nth_param(2).unwrap().into_struct_value();
+    let i32_zero = i32_type.const_zero();
+
+    assert_eq!(builder.try_build_alloca(i32_type, "alloca"), Err(BuilderError::UnsetPosition));
+    assert_eq!(builder.try_build_unconditional_branch(exit), Err(BuilderError::UnsetPosition));
+
+    builder.position_at_end(entry);
+
+    // Allocations
+    assert!(builder.try_build_alloca(i32_type, "alloca").is_ok());
+    assert_eq!(builder.try_build_alloca(context.opaque_struct_type("opaque"), "alloca"), Err(BuilderError::UnsizedType));
+
+    // Casts
+    assert!(builder.try_build_int_truncate(i32_arg, context.i8_type(), "trunc").is_ok());
+    assert_eq!(builder.try_build_int_truncate(i32_arg, i64_type, "trunc"), Err(BuilderError::InvalidCast));
+    assert!(builder.try_build_int_z_extend(i32_arg, i64_type, "zext").is_ok());
+    assert_eq!(builder.try_build_int_s_extend(i32_arg, i32_type, "sext"), Err(BuilderError::InvalidCast));
+    assert!(builder.try_build_int_s_extend_or_bit_cast(i32_arg, i32_type, "sext").is_ok());
+    assert!(builder.try_build_int_cast(i32_arg, i64_type, "cast").is_ok());
+    assert_eq!(builder.try_build_float_ext(f64_type.const_zero(), f32_type, "fpext"), Err(BuilderError::InvalidCast));
+    assert!(builder.try_build_float_cast(f64_type.const_zero(), f32_type, "fpcast").is_ok());
+    assert!(builder.try_build_bitcast(i32_arg, f32_type, "bitcast").is_ok());
+    assert_eq!(builder.try_build_bitcast(i32_arg, f64_type, "bitcast"), Err(BuilderError::InvalidCast));
+    assert_eq!(builder.try_build_bitcast(ptr_arg, i64_type, "bitcast"), Err(BuilderError::InvalidCast));
+    assert_eq!(builder.try_build_bitcast(struct_arg, i64_type, "bitcast"), Err(BuilderError::InvalidCast));
+    assert!(builder.try_build_pointer_cast(ptr_arg, f32_type.ptr_type(AddressSpace::Generic), "ptrcast").is_ok());
+    assert_eq!(
+        builder.try_build_address_space_cast(ptr_arg, i32_type.ptr_type(AddressSpace::Generic), "ascast"),
+        Err(BuilderError::InvalidCast),
+    );
+    assert!(builder.try_build_address_space_cast(ptr_arg, i32_type.ptr_type(AddressSpace::Global), "ascast").is_ok());
+    assert!(builder.try_build_cast(InstructionOpcode::SIToFP, i32_arg, f32_type, "cast").is_ok());
+    assert_eq!(builder.try_build_cast(InstructionOpcode::Add, i32_arg, f32_type, "cast"), Err(BuilderError::InvalidCast));
+
+    // Select
+    let cond = builder.try_build_int_compare(IntPredicate::EQ, i32_arg, i32_zero, "cond").unwrap();
+    let bool_vec = bool_type.vec_type(2).const_zero();
+
+    assert!(builder.try_build_select(cond, i32_arg, i32_zero, "select").is_ok());
+    assert_eq!(builder.try_build_select(i32_arg, i32_arg, i32_zero, "select"), Err(BuilderError::NonBooleanCondition));
+    assert_eq!(builder.try_build_select(bool_vec, i32_arg, i32_zero, "select"), Err(BuilderError::NonBooleanCondition));
+
+    // Aggregates and vectors
+    let vec_value = i32_type.vec_type(2).const_zero();
+
+    assert!(builder.try_build_extract_value(struct_arg, 1, "extract").unwrap().is_float_value());
+    assert_eq!(
+        builder.try_build_extract_value(struct_arg, 2, "extract"),
+        Err(BuilderError::AggregateIndexOutOfBounds { index: 2, len: 2 }),
+    );
+    assert!(builder.try_build_insert_value(struct_arg, i32_zero, 0, "insert").is_ok());
+    assert_eq!(builder.try_build_insert_value(struct_arg, i32_zero, 1, "insert"), Err(BuilderError::ElementTypeMismatch));
+    assert!(builder.try_build_insert_element(vec_value, i32_zero, i32_zero, "insert").is_ok());
+    assert_eq!(
+        builder.try_build_insert_element(vec_value, i64_type.const_zero(), i32_zero, "insert"),
+        Err(BuilderError::ElementTypeMismatch),
+    );
+    assert_eq!(builder.try_build_shuffle_vector(vec_value, vec_value, bool_vec, "shuffle"), Err(BuilderError::InvalidShuffleMask));
+
+    // Atomics
+    let bool_ptr = builder.try_build_alloca(bool_type, "bool_ptr").unwrap();
+
+    assert!(builder.try_build_fence(AtomicOrdering::Acquire, 0, "fence").is_ok());
+    assert_eq!(builder.try_build_fence(AtomicOrdering::Monotonic, 0, "fence"), Err(BuilderError::InvalidAtomicOrdering));
+    assert!(builder.try_build_atomicrmw(AtomicRMWBinOp::Add, ptr_arg, i32_zero, AtomicOrdering::Monotonic).is_ok());
+    assert_eq!(
+        builder.try_build_atomicrmw(AtomicRMWBinOp::Add, ptr_arg, i32_zero, AtomicOrdering::Unordered),
+        Err(BuilderError::InvalidAtomicOrdering),
+    );
+    assert_eq!(
+        builder.try_build_atomicrmw(AtomicRMWBinOp::Add, bool_ptr, bool_type.const_zero(), AtomicOrdering::Monotonic),
+        Err(BuilderError::InvalidAtomicOperand),
+    );
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    assert_eq!(
+        builder.try_build_cmpxchg(ptr_arg, i32_zero, i32_zero, AtomicOrdering::Monotonic, AtomicOrdering::Release),
+        Err(BuilderError::InvalidAtomicOrdering),
+    );
+
+    // Terminators
+    assert_eq!(builder.try_build_indirect_branch(i32_arg, &[exit]), Err(BuilderError::NotAPointer));
+    assert_eq!(builder.try_build_switch(i32_arg, exit, &[(i32_arg, exit)]), Err(BuilderError::NonConstantCase { position: 0 }));
+    assert_eq!(
+        builder.try_build_switch(i32_arg, exit, &[(i32_zero, exit), (i64_type.const_zero(), exit)]),
+        Err(BuilderError::CaseTypeMismatch { position: 1 }),
+    );
+    assert!(builder.try_build_switch(i32_arg, exit, &[(i32_zero, exit)]).is_ok());
+
+    builder.position_at_end(exit);
+
+    assert!(builder.try_build_aggregate_return(&[i32_zero.into()]).is_err());
+    assert!(builder.try_build_return(None).is_ok());
+}
+
+#[test]
+fn test_try_build_eh() {
+    let context = Context::create();
+    let module = context.create_module("eh");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    let exception_type = context.struct_type(&[i8_ptr_type.into(), i32_type.into()], false);
+    let personality = module.add_function("__gxx_personality_v0", i32_type.fn_type(&[], true), None);
+    let function = module.add_function("wrapper", i32_type.fn_type(&[i32_type.into()], false), None);
+    let entry = context.append_basic_block(function, "entry");
+    let arg = function.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(entry);
+
+    assert_eq!(
+        builder.try_build_landing_pad(exception_type, personality, &[arg.into()], false, "res"),
+        Err(BuilderError::NonConstantClause { position: 0 }),
+    );
+
+    let landing_pad = builder.try_build_landing_pad(exception_type, personality, &[i8_ptr_type.const_null().into()], true, "res").unwrap();
+
+    assert!(builder.try_build_resume(landing_pad).is_ok());
+
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9",
+                  feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0", feature = "llvm7-0")))]
+    {
+        let not_a_pad = landing_pad.as_instruction_value().unwrap();
+
+        assert_eq!(builder.try_build_catch_ret(not_a_pad, entry), Err(BuilderError::InvalidPad));
+        assert_eq!(builder.try_build_cleanup_ret(not_a_pad, None), Err(BuilderError::InvalidPad));
+        assert_eq!(builder.try_build_catch_switch(Some(not_a_pad), None, &[], "cs"), Err(BuilderError::InvalidPad));
+    }
+}
@@ -1,5 +1,11 @@
 use inkwell::AddressSpace;
 use inkwell::context::Context;
+use inkwell::memory_buffer::MemoryBuffer;
+use inkwell::passes::PassManager;
+use inkwell::support::error_handling::DiagnosticSeverity;
+
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[test]
 fn test_no_context_double_free() {
@@ -67,3 +73,113 @@ fn test_values_get_context() {
     assert_eq!(*i8_type.get_context(), context);
     assert_eq!(*struct_type.get_context(), context);
 }
+
+#[test]
+fn test_diagnostic_handler() {
+    let context = Context::create();
+    let diagnostics = Arc::new(Mutex::new(Vec::new()));
+    let handler_diagnostics = diagnostics.clone();
+
+    context.set_diagnostic_handler(move |diagnostic| {
+        handler_diagnostics.lock().unwrap().push((diagnostic.get_severity(), diagnostic.get_description().to_string()));
+    });
+
+    // Debug info with an outdated version is stripped with a warning when the IR is parsed
+    let ir = b"!llvm.dbg.cu = !{}\n!llvm.module.flags = !{!0}\n!0 = !{i32 2, !\"Debug Info Version\", i32 1}\n";
+    let memory_buffer = MemoryBuffer::create_from_memory_range_copy(ir, "outdated_debug_info");
+
+    assert!(context.create_module_from_ir(memory_buffer).is_ok());
+
+    {
+        let diagnostics = diagnostics.lock().unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].0, DiagnosticSeverity::Warning);
+        assert!(diagnostics[0].1.contains("invalid version"));
+    }
+
+    // Linking temporarily replaces the handler, which should be restored afterwards
+    let module = context.create_module("first");
+    let module2 = context.create_module("second");
+
+    assert!(module.link_in_module(module2).is_ok());
+
+    let memory_buffer = MemoryBuffer::create_from_memory_range_copy(ir, "outdated_debug_info");
+
+    assert!(context.create_module_from_ir(memory_buffer).is_ok());
+    assert_eq!(diagnostics.lock().unwrap().len(), 2);
+
+    context.clear_diagnostic_handler();
+}
+
+#[test]
+fn test_yield_callback() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let builder = context.create_builder();
+    let fn_type = context.void_type().fn_type(&[], false);
+    let function = module.add_function("my_fn", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+
+    builder.position_at_end(entry);
+    builder.build_return(None);
+
+    let yields = Arc::new(AtomicUsize::new(0));
+    let callback_yields = yields.clone();
+
+    context.set_yield_callback(move || {
+        callback_yields.fetch_add(1, Ordering::SeqCst);
+    });
+
+    let fpm = PassManager::create(&module);
+
+    fpm.add_instruction_combining_pass();
+    fpm.initialize();
+    fpm.run_on(&function);
+    fpm.finalize();
+
+    assert!(yields.load(Ordering::SeqCst) > 0);
+
+    context.clear_yield_callback();
+}
+
+#[test]
+fn test_parse_type_and_constant() {
+    let context = Context::create();
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let f32_type = context.f32_type();
+    let struct_type = context.struct_type(&[i32_type.into(), f32_type.into()], false);
+
+    assert_eq!(context.parse_type("i32"), Ok(i32_type.into()));
+    assert_eq!(context.parse_type("{ i32, float }"), Ok(struct_type.into()));
+    assert_eq!(context.parse_type("<4 x float>*"), Ok(f32_type.vec_type(4).ptr_type(AddressSpace::Generic).into()));
+    assert!(context.parse_type("void").is_err());
+
+    let error = context.parse_type("{ i32,\n  %undefined }").unwrap_err();
+
+    assert_eq!(error.get_line(), Some(2));
+    assert_eq!(error.get_source_line(), Some("  %undefined }"));
+
+    let constant = context.parse_constant("{ i32, float } { i32 7, float 2.5 }").unwrap().into_struct_value();
+
+    assert_eq!(constant.get_type(), struct_type);
+    assert_eq!(constant, struct_type.const_named_struct(&[i32_type.const_int(7, false).into(), f32_type.const_float(2.5).into()]));
+
+    let string = context.parse_constant("[3 x i8] c\"ab\\00\"").unwrap().into_array_value();
+
+    assert_eq!(string.get_type(), i8_type.array_type(3));
+    assert!(string.is_const());
+
+    let error = context.parse_constant("i32 @missing").unwrap_err();
+
+    assert_eq!(error.get_line(), Some(1));
+    assert_eq!(error.get_column(), Some(5));
+    assert!(context.parse_constant("i8** @0").is_err());
+
+    // Global values defined on further lines of the snippet would be dropped with it too
+    assert!(context.parse_constant("i32* @g\n@g = global i32 0").is_err());
+    assert!(context.parse_constant("i8* bitcast (void ()* @f to i8*)\ndeclare void @f()").is_err());
+    assert!(context.parse_constant("{ i32, i64 } { i32 1, i64 ptrtoint (i32* @g to i64) }\n@g = global i32 0").is_err());
+    assert!(context.parse_constant("i32 1\n@g = global i32 0").is_ok());
+}
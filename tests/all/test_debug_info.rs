@@ -374,4 +374,359 @@ fn test_global_expressions() {
     // TODO: Metadata set on the global values cannot be retrieved using the C api, 
     // therefore, it's currently not possible to test that the data was set without generating the IR
     assert!(gv.print_to_string().to_string().contains("!dbg"), format!("expected !dbg but generated gv was {}",gv.print_to_string()));
-}
\ No newline at end of file
+}
+#[llvm_versions(8.0..=latest)]
+#[test]
+fn test_derived_and_composite_types() {
+    let context = Context::create();
+    let module = context.create_module("bin");
+
+    let (dibuilder, compile_unit) = module.create_debug_info_builder(
+        true,
+        DWARFSourceLanguage::CPlusPlus,
+        "source_file",
+        ".",
+        "my llvm compiler frontend",
+        false,
+        "",
+        0,
+        "",
+        DWARFEmissionKind::Full,
+        0,
+        false,
+        false,
+        #[cfg(feature = "llvm11-0")]
+        "",
+        #[cfg(feature = "llvm11-0")]
+        "",
+    );
+
+    let file = compile_unit.get_file();
+    let scope = file.as_debug_info_scope();
+    let i32_type = dibuilder.create_basic_type("i32", 32, 0x05, DIFlags::PUBLIC).unwrap().as_type();
+    let u8_type = dibuilder.create_basic_type("u8", 8, 0x08, DIFlags::PUBLIC).unwrap().as_type();
+
+    let pointer_type = dibuilder.create_pointer_type("*i32", i32_type, 64, 64, inkwell::AddressSpace::Generic);
+    let reference_type = dibuilder.create_reference_type(i32_type, 0x10);
+    let const_type = dibuilder.create_qualified_type(i32_type, 0x26);
+    let volatile_type = dibuilder.create_qualified_type(const_type.as_type(), 0x35);
+    let array_type = dibuilder.create_array_type(i32_type, 4 * 3 * 32, 32, &[
+        dibuilder.get_or_create_subrange(0, 4),
+        dibuilder.get_or_create_subrange(0, 3),
+    ]);
+    let vector_type = dibuilder.create_vector_type(i32_type, 128, 128, &[dibuilder.get_or_create_subrange(0, 4)]);
+
+    assert_eq!(array_type.as_type().get_size_in_bits(), 384);
+    assert_eq!(pointer_type.as_type().get_align_in_bits(), 64);
+
+    let enum_type = dibuilder.create_enumeration_type(
+        scope,
+        "Color",
+        file,
+        1,
+        8,
+        8,
+        &[
+            dibuilder.create_enumerator("Red", 0, true),
+            dibuilder.create_enumerator("Green", 1, true),
+        ],
+        Some(u8_type),
+    );
+
+    let opaque_type = dibuilder.create_forward_decl(0x13, "Opaque", scope, file, 2, 0, 0, 0, "Opaque");
+    let opaque_pointer = dibuilder.create_pointer_type("*Opaque", opaque_type.as_type(), 64, 64, inkwell::AddressSpace::Generic);
+
+    // A self referential type is built through a replaceable type
+    let node_decl = dibuilder.create_replaceable_composite_type(0x13, "Node", scope, file, 3, 0, 0, 0, DIFlags::FWD_DECL, "Node");
+    let next_pointer = dibuilder.create_pointer_type("*Node", node_decl.as_type(), 64, 64, inkwell::AddressSpace::Generic);
+    let base_type = dibuilder.create_struct_type(scope, "Base", file, 4, 32, 32, DIFlags::PUBLIC, None, &[], 0, None, "Base");
+    let members = [
+        ("pointer", pointer_type.as_type(), 64),
+        ("reference", reference_type.as_type(), 64),
+        ("volatile", volatile_type.as_type(), 32),
+        ("array", array_type.as_type(), 384),
+        ("vector", vector_type.as_type(), 128),
+        ("color", enum_type.as_type(), 8),
+        ("opaque", opaque_pointer.as_type(), 64),
+        ("next", next_pointer.as_type(), 64),
+    ];
+    let inheritance = dibuilder.create_inheritance(node_decl.as_type(), base_type.as_type(), 0, 0, DIFlags::PUBLIC);
+    let mut elements = vec![inheritance.as_type()];
+    let mut offset = 32;
+
+    for (name, ty, size) in members.iter() {
+        let member = dibuilder.create_member_type(scope, name, file, 5, *size, 8, offset, DIFlags::PUBLIC, *ty);
+
+        elements.push(member.as_type());
+        offset += size;
+    }
+
+    let flags = dibuilder.create_bit_field_member_type(scope, "flags", file, 6, 3, offset, offset, DIFlags::PUBLIC, u8_type);
+
+    elements.push(flags.as_type());
+
+    let node_type = dibuilder.create_struct_type(scope, "Node", file, 3, offset + 8, 64, DIFlags::PUBLIC, None, &elements, 0, None, "Node");
+
+    unsafe {
+        dibuilder.replace_composite_type(node_decl, node_type);
+    }
+
+    let gv = module.add_global(context.i64_type(), Some(inkwell::AddressSpace::Global), "node");
+    let gv_debug = dibuilder.create_global_variable_expression(
+        compile_unit.as_debug_info_scope(),
+        "node",
+        "",
+        file,
+        7,
+        node_type.as_type(),
+        true,
+        None,
+        None,
+        64,
+    );
+
+    gv.set_metadata(gv_debug.as_metadata_value(&context), 0);
+    dibuilder.finalize();
+
+    assert!(module.verify().is_ok());
+
+    let ir = module.print_to_string().to_string();
+
+    for expected in &[
+        "DW_TAG_pointer_type, name: \"*i32\"",
+        "DW_TAG_reference_type",
+        "DW_TAG_const_type",
+        "DW_TAG_volatile_type",
+        "DICompositeType(tag: DW_TAG_array_type",
+        "flags: DIFlagVector",
+        "DICompositeType(tag: DW_TAG_enumeration_type, name: \"Color\"",
+        "DIEnumerator(name: \"Green\", value: 1, isUnsigned: true)",
+        "name: \"Opaque\"",
+        "DIFlagFwdDecl",
+        "DW_TAG_inheritance",
+        "name: \"flags\"",
+        "DIFlagBitField",
+    ] {
+        assert!(ir.contains(expected), "expected {} in {}", expected, ir);
+    }
+}
+
+#[llvm_versions(9.0..=latest)]
+#[test]
+fn test_reading_debug_info() {
+    use inkwell::debug_info::debug_metadata_version;
+    use inkwell::module::Module;
+
+    let context = Context::create();
+    let module = context.create_module("bin");
+
+    module.add_basic_value_flag(
+        "Debug Info Version",
+        FlagBehavior::Warning,
+        context.i32_type().const_int(debug_metadata_version() as u64, false),
+    );
+
+    let (dibuilder, compile_unit) = module.create_debug_info_builder(
+        true,
+        DWARFSourceLanguage::C,
+        "source_file.c",
+        "/src",
+        "my llvm compiler frontend",
+        false,
+        "",
+        0,
+        "",
+        DWARFEmissionKind::Full,
+        0,
+        false,
+        false,
+        #[cfg(feature = "llvm11-0")]
+        "",
+        #[cfg(feature = "llvm11-0")]
+        "",
+    );
+
+    let file = compile_unit.get_file();
+    let i32_type = dibuilder.create_basic_type("int", 32, 0x05, DIFlags::PUBLIC).unwrap().as_type();
+    let subroutine_type = dibuilder.create_subroutine_type(file, Some(i32_type), &[], DIFlags::PUBLIC);
+    let subprogram = dibuilder.create_function(
+        compile_unit.as_debug_info_scope(),
+        "answer",
+        Some("_Z6answerv"),
+        file,
+        10,
+        subroutine_type,
+        false,
+        true,
+        10,
+        DIFlags::PUBLIC,
+        false,
+    );
+    let function = module.add_function("_Z6answerv", context.i32_type().fn_type(&[], false), None);
+
+    function.set_subprogram(subprogram);
+
+    let builder = context.create_builder();
+    let entry = context.append_basic_block(function, "entry");
+
+    builder.position_at_end(entry);
+
+    let location = dibuilder.create_debug_location(&context, 11, 5, subprogram.as_debug_info_scope(), None);
+    let variable = dibuilder.create_auto_variable(
+        subprogram.as_debug_info_scope(),
+        "result",
+        file,
+        11,
+        i32_type,
+        true,
+        DIFlags::ZERO,
+        32,
+    );
+
+    builder.set_current_debug_location(&context, location);
+
+    let alloca = builder.build_alloca(context.i32_type(), "result");
+
+    dibuilder.insert_declare_at_end(alloca, Some(variable), None, location, entry);
+    builder.build_return(Some(&context.i32_type().const_int(42, false)));
+    dibuilder.finalize();
+
+    assert!(module.verify().is_ok());
+
+    // Read everything back from a module parsed into another context
+    let buffer = module.write_bitcode_to_memory();
+    let context = Context::create();
+    let module = Module::parse_bitcode_from_buffer(&buffer, &context).unwrap();
+    let compile_units = module.get_compile_units();
+
+    assert_eq!(compile_units.len(), 1);
+    assert_eq!(compile_units[0].get_file().get_filename().to_str(), Ok("source_file.c"));
+    assert_eq!(compile_units[0].get_file().get_directory().to_str(), Ok("/src"));
+
+    let function = module.get_function("_Z6answerv").unwrap();
+    let subprogram = function.get_subprogram().unwrap();
+
+    assert_eq!(subprogram.get_name(&context).unwrap().to_str(), Ok("answer"));
+    assert_eq!(subprogram.get_linkage_name(&context).unwrap().to_str(), Ok("_Z6answerv"));
+    assert_eq!(subprogram.get_file().unwrap().get_filename().to_str(), Ok("source_file.c"));
+    assert_eq!(subprogram.get_line(), 10);
+
+    let alloca = function.get_first_basic_block().unwrap().get_first_instruction().unwrap();
+    let location = alloca.get_debug_location().unwrap();
+
+    assert_eq!(location.get_line(), 11);
+    assert_eq!(location.get_column(), 5);
+    assert_eq!(location.get_scope(), subprogram.as_debug_info_scope());
+    assert!(location.get_inlined_at().is_none());
+    assert!(alloca.get_debug_variable().is_none());
+
+    let declare = alloca.get_next_instruction().unwrap();
+    let variable = declare.get_debug_variable().unwrap();
+
+    assert_eq!(variable.get_name(&context).unwrap().to_str(), Ok("result"));
+    assert_eq!(variable.get_line(), 11);
+    assert_eq!(variable.get_scope(), subprogram.as_debug_info_scope());
+    assert_eq!(variable.get_file().unwrap().get_filename().to_str(), Ok("source_file.c"));
+    assert!(declare.get_next_instruction().unwrap().get_debug_variable().is_none());
+}
+
+#[llvm_versions(8.0..=latest)]
+#[test]
+fn test_imported_entities_macros_and_inlined_locations() {
+    let context = Context::create();
+    let module = context.create_module("bin");
+
+    let (dibuilder, compile_unit) = module.create_debug_info_builder(
+        true,
+        DWARFSourceLanguage::CPlusPlus,
+        "source_file.cpp",
+        ".",
+        "my llvm compiler frontend",
+        false,
+        "",
+        0,
+        "",
+        DWARFEmissionKind::Full,
+        0,
+        false,
+        false,
+        #[cfg(feature = "llvm11-0")]
+        "",
+        #[cfg(feature = "llvm11-0")]
+        "",
+    );
+
+    let file = compile_unit.get_file();
+    let cu_scope = compile_unit.as_debug_info_scope();
+    let namespace = dibuilder.create_namespace(cu_scope, "runtime", false);
+    let subroutine_type = dibuilder.create_subroutine_type(file, None, &[], DIFlags::PUBLIC);
+    let helper = dibuilder.create_function(
+        namespace.as_debug_info_scope(), "helper", None, file, 2, subroutine_type, false, true, 2, DIFlags::PUBLIC, false,
+    );
+    let caller = dibuilder.create_function(
+        cu_scope, "caller", None, file, 8, subroutine_type, false, true, 8, DIFlags::PUBLIC, false,
+    );
+
+    dibuilder.create_imported_module_from_namespace(cu_scope, namespace, file, 5);
+    dibuilder.create_imported_declaration(cu_scope, helper.as_debug_info_scope(), file, 6, "helper");
+
+    #[cfg(feature = "llvm11-0")]
+    {
+        use inkwell::debug_info::DWARFMacinfoRecordType;
+
+        let header = dibuilder.create_file("runtime.h", ".");
+        let macro_file = dibuilder.create_temp_macro_file(None, 1, header);
+
+        dibuilder.create_macro(Some(macro_file), 3, DWARFMacinfoRecordType::Define, "RUNTIME_DEBUG", "1");
+        dibuilder.create_macro(None, 4, DWARFMacinfoRecordType::Undef, "NDEBUG", "");
+    }
+
+    // The body of helper is inlined into caller at line 9
+    let function = module.add_function("caller", context.void_type().fn_type(&[], false), None);
+
+    function.set_subprogram(caller);
+
+    let builder = context.create_builder();
+
+    builder.position_at_end(context.append_basic_block(function, "entry"));
+
+    let call_site = dibuilder.create_debug_location(&context, 9, 3, caller.as_debug_info_scope(), None);
+    let inlined = dibuilder.create_debug_location(&context, 3, 7, helper.as_debug_info_scope(), Some(call_site));
+
+    builder.set_current_debug_location(&context, inlined);
+
+    builder.build_alloca(context.i32_type(), "x");
+
+    builder.set_current_debug_location(&context, call_site);
+    builder.build_return(None);
+    dibuilder.finalize();
+
+    assert!(module.verify().is_ok());
+
+    #[cfg(not(feature = "llvm8-0"))]
+    {
+        let alloca = function.get_first_basic_block().unwrap().get_first_instruction().unwrap();
+        let location = alloca.get_debug_location().unwrap();
+
+        assert_eq!(location.get_line(), 3);
+        assert_eq!(location.get_inlined_at(), Some(call_site));
+    }
+
+    let ir = module.print_to_string().to_string();
+
+    for expected in &[
+        "DIImportedEntity(tag: DW_TAG_imported_module",
+        "DIImportedEntity(tag: DW_TAG_imported_declaration, name: \"helper\"",
+        "!DILocation(line: 3, column: 7",
+        "inlinedAt: ",
+    ] {
+        assert!(ir.contains(expected), "expected {} in {}", expected, ir);
+    }
+
+    #[cfg(feature = "llvm11-0")]
+    {
+        assert!(ir.contains("!DIMacroFile(line: 1"), "expected a macro file in {}", ir);
+        assert!(ir.contains("!DIMacro(type: DW_MACINFO_define, line: 3, name: \"RUNTIME_DEBUG\", value: \"1\")"), "{}", ir);
+        assert!(ir.contains("!DIMacro(type: DW_MACINFO_undef, line: 4, name: \"NDEBUG\")"), "{}", ir);
+    }
+}
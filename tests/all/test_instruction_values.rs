@@ -1,5 +1,5 @@
 use inkwell::context::Context;
-use inkwell::values::{BasicValue, InstructionOpcode::*};
+use inkwell::values::{AllocaInst, BasicMetadataValueEnum, BasicValue, BranchInst, CallInst, InstructionEnum, InstructionOpcode::*, LoadInst, StoreInst, SwitchInst};
 use inkwell::{AddressSpace, AtomicOrdering, AtomicRMWBinOp, FloatPredicate, IntPredicate};
 
 #[test]
@@ -499,3 +499,185 @@ fn test_metadata_kinds() {
         md_string.into(),
     ]);
 }
+
+#[test]
+fn test_operand_and_use_iters() {
+    let context = Context::create();
+    let module = context.create_module("ivs");
+    let builder = context.create_builder();
+    let void_type = context.void_type();
+    let f32_type = context.f32_type();
+    let f32_ptr_type = f32_type.ptr_type(AddressSpace::Generic);
+    let fn_type = void_type.fn_type(&[f32_ptr_type.into()], false);
+
+    let function = module.add_function("take_f32_ptr", fn_type, None);
+    let basic_block = context.append_basic_block(function, "entry");
+
+    builder.position_at_end(basic_block);
+
+    let arg1 = function.get_first_param().unwrap().into_pointer_value();
+    let f32_val = f32_type.const_float(::std::f64::consts::PI);
+    let store_instruction = builder.build_store(arg1, f32_val);
+    let load = builder.build_load(arg1, "load");
+    let return_instruction = builder.build_return(None);
+
+    let operands: Vec<_> = store_instruction.get_operands().map(|operand| operand.unwrap().left().unwrap()).collect();
+
+    assert_eq!(operands, [f32_val.as_basic_value_enum(), arg1.as_basic_value_enum()]);
+    assert_eq!(store_instruction.get_operands().len(), 2);
+    assert_eq!(store_instruction.get_operands().rev().next().unwrap().unwrap().left().unwrap(), arg1.as_basic_value_enum());
+    assert_eq!(return_instruction.get_operands().count(), 0);
+
+    let mut operands = store_instruction.get_operands();
+
+    assert!(operands.next().is_some());
+    assert_eq!(operands.len(), 1);
+    assert!(operands.next_back().is_some());
+    assert!(operands.next().is_none());
+    assert!(operands.next_back().is_none());
+
+    // arg1 is used by both the store and the load
+    let users: Vec<_> = arg1.get_uses().map(|use_| use_.get_user().as_instruction_value().unwrap()).collect();
+
+    assert_eq!(users.len(), 2);
+    assert!(users.contains(&store_instruction));
+    assert!(users.contains(&load.as_instruction_value().unwrap()));
+    assert_eq!(store_instruction.get_uses().count(), 0);
+
+    // Erasing the user of the current use must not invalidate the iterator
+    for use_ in arg1.get_uses() {
+        use_.get_user().as_instruction_value().unwrap().erase_from_basic_block();
+    }
+
+    assert!(arg1.get_first_use().is_none());
+    assert_eq!(basic_block.get_instructions().collect::<Vec<_>>(), [return_instruction]);
+}
+
+#[test]
+fn test_typed_instructions() {
+    use std::convert::TryFrom;
+
+    let context = Context::create();
+    let module = context.create_module("ivs");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+
+    let function = module.add_function("typed", fn_type, None);
+    let entry = context.append_basic_block(function, "entry");
+    let then_block = context.append_basic_block(function, "then");
+    let else_block = context.append_basic_block(function, "else");
+    let exit = context.append_basic_block(function, "exit");
+
+    builder.position_at_end(entry);
+
+    let arg = function.get_first_param().unwrap().into_int_value();
+    let size = i32_type.const_int(4, false);
+    let array = builder.build_array_alloca(i32_type, size, "array");
+    let store = builder.build_store(array, arg);
+    let load = builder.build_load(array, "load").as_instruction_value().unwrap();
+    let cmp = builder.build_int_compare(IntPredicate::SGT, arg, size, "cmp");
+    let branch = builder.build_conditional_branch(cmp, then_block, else_block);
+
+    builder.position_at_end(then_block);
+
+    let call = builder.build_call(function, &[arg.into()], "call");
+
+    builder.build_unconditional_branch(exit);
+    builder.position_at_end(else_block);
+
+    let one = i32_type.const_int(1, false);
+    let switch = builder.build_switch(arg, exit, &[(one, then_block)]);
+
+    builder.position_at_end(exit);
+    builder.build_return(Some(&arg));
+
+    let alloca = AllocaInst::try_from(array.as_instruction_value().unwrap()).unwrap();
+
+    assert_eq!(alloca.get_allocated_type().into_int_type(), i32_type);
+    assert_eq!(alloca.get_array_size(), size);
+
+    let store = StoreInst::try_from(store).unwrap();
+
+    assert_eq!(store.get_value_operand(), arg.as_basic_value_enum());
+    assert_eq!(store.get_pointer_operand(), array);
+    assert!(!store.is_volatile());
+
+    let load = LoadInst::try_from(load).unwrap();
+
+    assert_eq!(load.get_pointer_operand(), array);
+    assert!(LoadInst::try_from(store.as_instruction_value()).is_err());
+
+    match cmp.as_instruction().unwrap().as_instruction_enum() {
+        InstructionEnum::Cmp(cmp) => {
+            assert_eq!(cmp.get_predicate().left(), Some(IntPredicate::SGT));
+            assert_eq!(cmp.get_lhs(), arg.as_basic_value_enum());
+            assert_eq!(cmp.get_rhs(), size.as_basic_value_enum());
+        },
+        other => panic!("expected a cmp instruction, found {:?}", other),
+    }
+
+    let branch = BranchInst::try_from(branch).unwrap();
+
+    assert!(branch.is_conditional());
+    assert_eq!(branch.get_condition(), Some(cmp));
+    assert_eq!(branch.get_successors(), [then_block, else_block]);
+
+    let unconditional = BranchInst::try_from(then_block.get_terminator().unwrap()).unwrap();
+
+    assert!(!unconditional.is_conditional());
+    assert_eq!(unconditional.get_condition(), None);
+    assert_eq!(unconditional.get_successors(), [exit]);
+
+    let call = CallInst::try_from(call.try_as_basic_value().left().unwrap().as_instruction_value().unwrap()).unwrap();
+
+    assert_eq!(call.get_called_value().left(), Some(function));
+    assert_eq!(call.get_arguments(), [Some(BasicMetadataValueEnum::from(arg))]);
+
+    let switch = SwitchInst::try_from(switch).unwrap();
+
+    assert_eq!(switch.get_condition(), arg);
+    assert_eq!(switch.get_default_destination(), exit);
+    assert_eq!(switch.count_cases(), 1);
+    assert_eq!(switch.get_cases(), [(one, then_block)]);
+
+    match exit.get_terminator().unwrap().as_instruction_enum() {
+        InstructionEnum::Other(instruction) => assert_eq!(instruction.get_opcode(), Return),
+        other => panic!("expected a return instruction, found {:?}", other),
+    }
+}
+
+// dbg.value lost its offset argument in LLVM 6
+#[llvm_versions(6.0..=latest)]
+#[test]
+fn test_call_metadata_and_token_arguments() {
+    use inkwell::module::Module;
+    use std::convert::TryFrom;
+
+    let context = Context::create();
+    let ir = "\
+declare void @llvm.dbg.value(metadata, metadata, metadata)
+declare i1 @llvm.coro.suspend(token, i1)
+
+define void @f(i32 %x) {
+entry:
+  call void @llvm.dbg.value(metadata i32 %x, metadata !0, metadata !DIExpression())
+  %suspended = call i1 @llvm.coro.suspend(token none, i1 false)
+  ret void
+}
+
+!0 = !{}
+";
+    let module = Module::parse_ir_str(&context, ir, "calls").unwrap();
+    let function = module.get_function("f").unwrap();
+    let entry = function.get_first_basic_block().unwrap();
+    let dbg_value = CallInst::try_from(entry.get_first_instruction().unwrap()).unwrap();
+    let arguments = dbg_value.get_arguments();
+
+    assert_eq!(arguments.len(), 3);
+    assert!(arguments.iter().all(|argument| argument.map_or(false, |argument| argument.is_metadata_value())));
+
+    let suspend = CallInst::try_from(dbg_value.get_next_instruction().unwrap()).unwrap();
+
+    assert_eq!(suspend.get_arguments(), [None, Some(BasicMetadataValueEnum::from(context.bool_type().const_zero()))]);
+}
@@ -0,0 +1,59 @@
+use inkwell::AddressSpace;
+use inkwell::context::Context;
+use inkwell::intrinsics::Intrinsic;
+
+#[test]
+fn test_find_intrinsic() {
+    let trap = Intrinsic::find("llvm.trap").unwrap();
+
+    assert!(!trap.is_overloaded());
+
+    let ctpop = Intrinsic::find("llvm.ctpop").unwrap();
+
+    assert!(ctpop.is_overloaded());
+    assert_eq!(Intrinsic::find("llvm.ctpop.v4i32"), Some(ctpop));
+    assert_ne!(trap, ctpop);
+    assert!(Intrinsic::find("llvm.not_an_intrinsic").is_none());
+    assert!(Intrinsic::find("trap").is_none());
+}
+
+#[test]
+fn test_get_intrinsic_declaration() {
+    let context = Context::create();
+    let module = context.create_module("intrinsics");
+    let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+
+    let trap = Intrinsic::find("llvm.trap").unwrap();
+    let trap_decl = trap.get_declaration(&module, &[]).unwrap();
+
+    assert_eq!(trap_decl.get_name().to_str(), Ok("llvm.trap"));
+    assert_eq!(trap_decl.get_intrinsic_id(), trap.get_id());
+    assert!(trap.get_declaration(&module, &[i32_type.into()]).is_none());
+
+    let memset = Intrinsic::find("llvm.memset").unwrap();
+
+    assert!(memset.get_declaration(&module, &[]).is_none());
+
+    let memset_decl = memset.get_declaration(&module, &[i8_ptr_type.into(), i64_type.into()]).unwrap();
+
+    assert_eq!(memset_decl.get_name().to_str(), Ok("llvm.memset.p0i8.i64"));
+    assert_eq!(memset_decl.count_params(), 4);
+
+    let sadd = Intrinsic::find("llvm.sadd.with.overflow").unwrap();
+    let sadd_decl = sadd.get_declaration(&module, &[i32_type.into()]).unwrap();
+
+    assert_eq!(sadd_decl.get_name().to_str(), Ok("llvm.sadd.with.overflow.i32"));
+    assert!(sadd_decl.get_type().get_return_type().unwrap().is_struct_type());
+
+    let ctpop = Intrinsic::find("llvm.ctpop").unwrap();
+    let ctpop_decl = ctpop.get_declaration(&module, &[i32_type.vec_type(4).into()]).unwrap();
+
+    assert_eq!(ctpop_decl.get_name().to_str(), Ok("llvm.ctpop.v4i32"));
+
+    // Declaring an intrinsic twice reuses the existing declaration
+    assert_eq!(ctpop.get_declaration(&module, &[i32_type.vec_type(4).into()]), Some(ctpop_decl));
+    assert_eq!(module.get_function("llvm.ctpop.v4i32"), Some(ctpop_decl));
+    assert!(module.verify().is_ok());
+}
@@ -0,0 +1,131 @@
+use inkwell::OptimizationLevel;
+use inkwell::context::Context;
+use inkwell::lto::FullLTO;
+use inkwell::module::{Linkage, Module};
+use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetMachine};
+
+fn native_target_machine() -> TargetMachine {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let triple = TargetMachine::get_default_triple();
+    let target = Target::from_triple(&triple).unwrap();
+
+    target.create_target_machine(
+        &triple,
+        "generic",
+        "",
+        OptimizationLevel::Default,
+        RelocMode::PIC,
+        CodeModel::Default,
+    ).unwrap()
+}
+
+// Builds a module defining `helper`, and one defining `entry` which calls it
+fn build_modules(context: &Context) -> (Module, Module) {
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+
+    let runtime = context.create_module("runtime");
+    let helper = runtime.add_function("helper", fn_type, None);
+
+    builder.position_at_end(context.append_basic_block(helper, "entry"));
+
+    let param = helper.get_first_param().unwrap().into_int_value();
+
+    builder.build_return(Some(&builder.build_int_mul(param, i32_type.const_int(3, false), "triple")));
+
+    let main = context.create_module("main");
+    let helper_decl = main.add_function("helper", fn_type, None);
+    let entry = main.add_function("entry", fn_type, None);
+
+    builder.position_at_end(context.append_basic_block(entry, "entry"));
+
+    let param = entry.get_first_param().unwrap();
+    let result = builder.build_call(helper_decl, &[param], "result").try_as_basic_value().left().unwrap();
+
+    builder.build_return(Some(&result));
+
+    (main, runtime)
+}
+
+#[test]
+fn test_full_lto() {
+    let target_machine = native_target_machine();
+    let context = Context::create();
+    let (main, runtime) = build_modules(&context);
+    let mut lto = FullLTO::new(&context, "merged");
+
+    assert!(lto.add_module(main).is_ok());
+    assert!(lto.add_bitcode(&runtime.write_bitcode_to_memory()).is_ok());
+
+    lto.add_preserved_symbol("entry");
+    lto.internalize();
+
+    let module = lto.get_module();
+
+    assert_eq!(module.get_function("entry").unwrap().get_linkage(), Linkage::External);
+    assert_eq!(module.get_function("helper").unwrap().get_linkage(), Linkage::Internal);
+    assert!(module.verify().is_ok());
+
+    lto.optimize(&target_machine, OptimizationLevel::Default);
+
+    // The helper was inlined into its only caller and removed
+    assert!(lto.get_module().get_function("helper").is_none());
+    assert!(lto.get_module().verify().is_ok());
+
+    let object_file = lto.codegen(&target_machine).unwrap().create_object_file().unwrap();
+    let has_symbol = |name: &str| {
+        object_file.get_symbols().any(|symbol| {
+            symbol.get_name().map(|symbol_name| symbol_name.to_string_lossy().ends_with(name)).unwrap_or(false)
+        })
+    };
+
+    assert!(has_symbol("entry"));
+    assert!(!has_symbol("helper"));
+}
+
+#[test]
+fn test_full_lto_run() {
+    let target_machine = native_target_machine();
+    let context = Context::create();
+    let (main, runtime) = build_modules(&context);
+    let lto = FullLTO::new(&context, "merged");
+
+    assert!(lto.add_module(main).is_ok());
+    assert!(lto.add_module(runtime).is_ok());
+
+    // Without preserved symbols, everything is internalized and optimized away
+    let object = lto.run(&target_machine, OptimizationLevel::Default).unwrap();
+
+    assert!(object.get_size() > 0);
+}
+
+#[cfg(all(feature = "thinlto", not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9"))))]
+#[test]
+fn test_thin_lto() {
+    use inkwell::lto::ThinLTO;
+
+    let target_machine = native_target_machine();
+    let context = Context::create();
+    let (main, _) = build_modules(&context);
+
+    main.set_triple(&target_machine.get_triple());
+
+    // Without a module summary there is no importing, so this only checks each module is compiled
+    let mut thin_lto = ThinLTO::create().unwrap();
+
+    thin_lto.set_reloc_mode(RelocMode::PIC);
+    thin_lto.add_module(&main);
+    thin_lto.add_must_preserve_symbol("entry");
+
+    let objects = thin_lto.run();
+
+    assert_eq!(objects.len(), 1);
+
+    let object_file = objects.into_iter().next().unwrap().create_object_file().unwrap();
+
+    assert!(object_file.get_symbols().any(|symbol| {
+        symbol.get_name().map(|name| name.to_string_lossy().ends_with("entry")).unwrap_or(false)
+    }));
+}
@@ -290,6 +290,47 @@ fn test_clone() {
     assert_eq!(module.print_to_string(), module2.print_to_string());
 }
 
+#[test]
+fn test_clone_into_context() {
+    let context = Context::create();
+    let module = context.create_module("mod");
+    let void_type = context.void_type();
+    let fn_type = void_type.fn_type(&[], false);
+    let f = module.add_function("f", fn_type, None);
+    let basic_block = context.append_basic_block(f, "entry");
+    let builder = context.create_builder();
+
+    builder.position_at_end(basic_block);
+    builder.build_return(None);
+
+    let other_context = Context::create();
+    let module2 = module.clone_into_context(&other_context).unwrap();
+
+    assert_eq!(*module2.get_context(), other_context);
+    assert_eq!(f.print_to_string(), module2.get_function("f").unwrap().print_to_string());
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    assert_eq!(module2.get_name().to_str(), Ok("mod"));
+
+    // The copy must stay usable after the original and its context are gone
+    drop(builder);
+    drop(module);
+    drop(context);
+
+    assert!(module2.get_function("f").is_some());
+    assert!(module2.verify().is_ok());
+
+    // Invalid modules are rejected instead of being serialized
+    let context = Context::create();
+    let module = context.create_module("invalid");
+    let fn_type = context.void_type().fn_type(&[], false);
+    let f = module.add_function("f", fn_type, None);
+
+    // A block without a terminator fails verification
+    context.append_basic_block(f, "entry");
+
+    assert!(module.clone_into_context(&other_context).is_err());
+}
+
 #[test]
 fn test_print_to_file() {
     let context = Context::create();
@@ -483,3 +524,145 @@ fn test_double_ee_from_same_module() {
 
     assert!(module.create_interpreter_execution_engine().is_err());
 }
+
+#[test]
+fn test_function_and_global_iters() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let i8_type = context.i8_type();
+    let fn_type = context.void_type().fn_type(&[], false);
+
+    assert_eq!(module.get_functions().count(), 0);
+    assert_eq!(module.get_globals().count(), 0);
+
+    let fn1 = module.add_function("fn1", fn_type, None);
+    let fn2 = module.add_function("fn2", fn_type, None);
+    let fn3 = module.add_function("fn3", fn_type, None);
+    let global1 = module.add_global(i8_type, None, "global1");
+    let global2 = module.add_global(i8_type, None, "global2");
+
+    assert_eq!(module.get_functions().collect::<Vec<_>>(), [fn1, fn2, fn3]);
+    assert_eq!(module.get_functions().rev().collect::<Vec<_>>(), [fn3, fn2, fn1]);
+    assert_eq!(module.get_globals().collect::<Vec<_>>(), [global1, global2]);
+    assert_eq!(module.get_globals().rev().collect::<Vec<_>>(), [global2, global1]);
+
+    let mut functions = module.get_functions();
+
+    assert_eq!(functions.next_back(), Some(fn3));
+    assert_eq!(functions.next(), Some(fn1));
+    assert_eq!(functions.next_back(), Some(fn2));
+    assert_eq!(functions.next(), None);
+
+    // Deleting the current function must not invalidate the iterator
+    for function in module.get_functions() {
+        if function != fn3 {
+            unsafe {
+                function.delete();
+            }
+        }
+    }
+
+    assert_eq!(module.get_functions().collect::<Vec<_>>(), [fn3]);
+
+    for global in module.get_globals() {
+        unsafe {
+            global.delete();
+        }
+    }
+
+    assert!(module.get_first_global().is_none());
+}
+
+#[test]
+fn test_parse_ir_str() {
+    let context = Context::create();
+    let ir = "\
+define i32 @add(i32 %a, i32 %b) {
+entry:
+  %sum = add i32 %a, %b
+  ret i32 %sum
+}
+";
+    let module = Module::parse_ir_str(&context, ir, "golden").unwrap();
+
+    assert_eq!(*module.get_context(), context);
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    assert_eq!(module.get_name().to_str(), Ok("golden"));
+    assert!(module.get_function("add").is_some());
+    assert!(module.verify().is_ok());
+
+    let ir = "\
+define i32 @add(i32 %a, i32 %b) {
+entry:
+  %sum = add i32 %a, %c
+  ret i32 %sum
+}
+";
+    let error = Module::parse_ir_str(&context, ir, "golden").unwrap_err();
+
+    assert_eq!(error.get_line(), Some(3));
+    assert_eq!(error.get_column(), Some(22));
+    assert_eq!(error.get_source_line(), Some("  %sum = add i32 %a, %c"));
+    assert_eq!(error.get_message(), "use of undefined value '%c'");
+    assert_eq!(
+        error.to_string(),
+        "IrParseError(3:22: use of undefined value '%c'\n  %sum = add i32 %a, %c\n                     ^)"
+    );
+
+    // Errors are located the same way without a buffer name
+    let error = Module::parse_ir_str(&context, "@g = global i32 x", "").unwrap_err();
+
+    assert_eq!(error.get_line(), Some(1));
+    assert_eq!(error.get_column(), Some(17));
+    assert_eq!(error.get_source_line(), Some("@g = global i32 x"));
+    assert!(!error.to_string().contains("<inkwell>"));
+
+    // The placeholder buffer name used for locations doesn't leak into the module
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    assert_eq!(Module::parse_ir_str(&context, "", "").unwrap().get_name().to_str(), Ok(""));
+}
+
+#[llvm_versions(3.9..=latest)]
+#[test]
+fn test_parse_bitcode_lazily() {
+    let context = Context::create();
+    let garbage_buffer = MemoryBuffer::create_from_memory_range_copy(b"garbage ir data", "my_ir");
+
+    assert!(Module::parse_bitcode_lazily_from_buffer(garbage_buffer, &context).is_err());
+
+    let module = context.create_module("lazy");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+
+    for name in &["first", "second"] {
+        let function = module.add_function(name, fn_type, None);
+
+        builder.position_at_end(context.append_basic_block(function, "entry"));
+        builder.build_return(Some(&i32_type.const_int(42, false)));
+    }
+
+    module.add_function("external", fn_type, None);
+
+    let buffer = module.write_bitcode_to_memory();
+    let lazy_module = Module::parse_bitcode_lazily_from_buffer(buffer, &context).unwrap();
+    let first = lazy_module.get_function("first").unwrap();
+    let second = lazy_module.get_function("second").unwrap();
+    let external = lazy_module.get_function("external").unwrap();
+
+    assert_eq!(first.count_basic_blocks(), 0);
+    assert_eq!(second.count_basic_blocks(), 0);
+    assert!(lazy_module.print_to_string().to_string().contains("; Materializable"));
+
+    lazy_module.materialize_function(first);
+
+    assert_eq!(first.count_basic_blocks(), 1);
+    assert_eq!(second.count_basic_blocks(), 0);
+
+    lazy_module.materialize_all();
+
+    assert_eq!(second.count_basic_blocks(), 1);
+    assert_eq!(external.count_basic_blocks(), 0);
+    assert!(!lazy_module.print_to_string().to_string().contains("; Materializable"));
+    assert!(lazy_module.verify().is_ok());
+}
@@ -2,6 +2,7 @@ extern crate inkwell;
 
 use self::inkwell::context::Context;
 use self::inkwell::module::Module;
+use self::inkwell::object_file::{ObjectFile, RelocationType};
 use self::inkwell::targets::{
     CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine,
 };
@@ -203,14 +204,109 @@ fn test_reloc_iterator() {
 
     let mut found_relocation = false;
     for section in object_file.get_sections() {
-        for _ in section.get_relocations() {
+        for relocation in section.get_relocations() {
             found_relocation = true;
             // We don't stop the traversal here, so as to exercise the iterators.
+
+            // LLVM no longer describes relocation values
+            #[cfg(not(feature = "llvm3-6"))]
+            assert!(relocation.get_value().to_bytes().is_empty());
+
+            let symbol_name = relocation.get_symbol()
+                .and_then(|symbol| symbol.get_name().and_then(|name| name.to_str().ok()).map(str::to_owned));
+
+            if symbol_name.as_deref() != Some("x") {
+                continue;
+            }
+
+            if cfg!(all(target_arch = "x86_64", target_os = "linux")) {
+                assert_eq!(relocation.get_type(), RelocationType::X86_64_64);
+            } else if cfg!(all(target_arch = "aarch64", target_os = "linux")) {
+                assert_eq!(relocation.get_type(), RelocationType::AArch64Abs64);
+            }
         }
     }
     assert!(found_relocation);
 }
 
+#[test]
+fn test_symbol_section() {
+    let target_machine = get_native_target_machine();
+
+    let context = Context::create();
+    let mut module = context.create_module("test_symbol_section");
+
+    let gv_a = module.add_global(context.i8_type(), None, "a");
+    gv_a.set_initializer(&context.i8_type().const_zero().as_basic_value_enum());
+    gv_a.set_section("A");
+
+    let gv_b = module.add_global(context.i16_type(), None, "b");
+    gv_b.set_initializer(&context.i16_type().const_zero().as_basic_value_enum());
+    gv_b.set_section("B");
+
+    apply_target_to_module(&target_machine, &module);
+
+    let memory_buffer = target_machine
+        .write_to_memory_buffer(&mut module, FileType::Object)
+        .unwrap();
+    let object_file = memory_buffer.create_object_file().unwrap();
+
+    // Sections and symbols must stay valid when the iterator moves on
+    let sections: Vec<_> = object_file.get_sections().collect();
+    let symbols: Vec<_> = object_file.get_symbols().collect();
+    let section_names: Vec<_> = sections.iter().filter_map(|section| section.get_name()).collect();
+
+    assert_eq!(sections.len(), object_file.get_sections().count());
+    assert!(section_names.iter().any(|name| name.to_str() == Ok("A")));
+    assert!(section_names.iter().any(|name| name.to_str() == Ok("B")));
+
+    let mut checked = 0;
+    for symbol in &symbols {
+        let expected_section = match symbol.get_name().and_then(|name| name.to_str().ok()) {
+            Some("a") => "A",
+            Some("b") => "B",
+            _ => continue,
+        };
+        let section = symbol.get_section().unwrap();
+
+        assert_eq!(section.get_name().and_then(|name| name.to_str().ok()), Some(expected_section));
+
+        for section in &sections {
+            let is_expected = section.get_name().and_then(|name| name.to_str().ok()) == Some(expected_section);
+
+            assert_eq!(section.contains_symbol(symbol), is_expected);
+        }
+
+        checked += 1;
+    }
+    assert_eq!(checked, 2);
+}
+
+#[test]
+fn test_object_file_from_bytes() {
+    let target_machine = get_native_target_machine();
+
+    let context = Context::create();
+    let mut module = context.create_module("test_object_file_from_bytes");
+
+    module
+        .add_global(context.i8_type(), None, "a")
+        .set_initializer(&context.i8_type().const_zero().as_basic_value_enum());
+
+    apply_target_to_module(&target_machine, &module);
+
+    let memory_buffer = target_machine
+        .write_to_memory_buffer(&mut module, FileType::Object)
+        .unwrap();
+    let object_file = ObjectFile::from_bytes(memory_buffer.as_slice()).unwrap();
+
+    drop(memory_buffer);
+
+    assert!(object_file.get_symbols().any(|symbol| symbol.get_name().and_then(|name| name.to_str().ok()) == Some("a")));
+    assert!(ObjectFile::from_bytes(b"not an object file").is_err());
+    assert!(ObjectFile::from_path("does/not/exist.o").is_err());
+}
+
 #[test]
 fn test_section_contains_nul() {
     let target_machine = get_native_target_machine();
@@ -244,3 +340,38 @@ fn test_section_contains_nul() {
     }
     assert!(has_section_test);
 }
+
+#[test]
+fn test_compile_to_object() {
+    let target_machine = get_native_target_machine();
+
+    let context = Context::create();
+    let module = context.create_module("test_compile_to_object");
+    let builder = context.create_builder();
+    let fn_type = context.void_type().fn_type(&[], false);
+    let function = module.add_function("compiled_fn", fn_type, None);
+
+    builder.position_at_end(context.append_basic_block(function, "entry"));
+    builder.build_return(None);
+
+    apply_target_to_module(&target_machine, &module);
+
+    let object = target_machine.compile_to_object(&module).unwrap();
+    let symbol = object
+        .get_symbols()
+        .find(|symbol| symbol.get_name().map_or(false, |name| name.to_bytes().ends_with(b"compiled_fn")))
+        .unwrap();
+
+    assert!(symbol.get_section().unwrap().size() > 0);
+    assert!(object.get_sections().any(|section| section.contains_symbol(&symbol)));
+
+    let path = std::env::temp_dir().join("inkwell_test_compile_to_object.o");
+
+    object.write_to_file(&path).unwrap();
+
+    let object_file = ObjectFile::from_path(&path).unwrap();
+
+    assert_eq!(object_file.as_slice(), object.as_slice());
+
+    std::fs::remove_file(&path).unwrap();
+}
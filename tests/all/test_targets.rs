@@ -1,5 +1,6 @@
 use inkwell::{AddressSpace, OptimizationLevel};
 use inkwell::context::Context;
+use inkwell::data_layout::{AlignSpec, DataLayout, DataLayoutParseError, FunctionPtrAlignType, Mangling, Padding, PointerSpec};
 use inkwell::targets::{ByteOrdering, CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetData, TargetMachine, TargetTriple};
 
 use regex::Regex;
@@ -278,6 +279,120 @@ fn test_target_data() {
     TargetData::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
 }
 
+#[test]
+fn test_struct_layout() {
+    let context = Context::create();
+    let i8_type = context.i8_type();
+    let i16_type = context.i16_type();
+    let i64_type = context.i64_type();
+    let inner = context.struct_type(&[i8_type.into(), i16_type.into()], false);
+    let struct_type = context.struct_type(&[i8_type.into(), i64_type.into(), inner.into(), i8_type.into()], false);
+    let target_data = TargetData::create("e-m:e-i64:64-f80:128-n8:16:32:64-S128");
+    let struct_layout = target_data.get_struct_layout(&struct_type);
+
+    assert_eq!(struct_layout.get_struct_type(), struct_type);
+    assert_eq!(struct_layout.get_size(), 24);
+    assert_eq!(struct_layout.get_alignment(), 8);
+
+    let offsets: Vec<_> = struct_layout.get_fields().iter().map(|field| (field.offset, field.size, field.alignment)).collect();
+
+    assert_eq!(offsets, vec![(0, 1, 1), (8, 8, 8), (16, 4, 2), (20, 1, 1)]);
+    assert_eq!(struct_layout.get_field(2).unwrap().ty, inner.into());
+    assert!(struct_layout.get_field(4).is_none());
+    assert_eq!(struct_layout.get_padding(), &[Padding { offset: 1, size: 7 }, Padding { offset: 21, size: 3 }]);
+    assert_eq!(struct_layout.get_padding_size(), 10);
+    assert_eq!(struct_layout.get_field_at_offset(17).unwrap().index, 2);
+    assert!(struct_layout.get_field_at_offset(4).is_none());
+
+    // Packed structs have no padding
+    let packed = context.struct_type(&[i8_type.into(), i64_type.into()], true);
+    let packed_layout = target_data.get_struct_layout(&packed);
+
+    assert_eq!(packed_layout.get_size(), 9);
+    assert_eq!(packed_layout.get_alignment(), 1);
+    assert!(packed_layout.get_padding().is_empty());
+
+    // 32 bit i64 alignment, without a TargetMachine or TargetData
+    let data_layout = DataLayout::create("e-m:e-p:32:32-i64:32-n8:16:32-S32");
+    let struct_layout = data_layout.get_struct_layout(&struct_type).unwrap();
+
+    assert_eq!(struct_layout.get_size(), 20);
+    assert_eq!(struct_layout.get_field(1).unwrap().offset, 4);
+    assert_eq!(struct_layout, data_layout.get_struct_layout(&struct_type).unwrap());
+
+    let invalid = DataLayout::create("e-q:32");
+
+    assert_eq!(invalid.get_struct_layout(&struct_type), Err(DataLayoutParseError::InvalidComponent("q:32".into())));
+}
+
+#[test]
+fn test_data_layout_parsing() {
+    let spec = DataLayout::create("e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-f80:128-n8:16:32:64-S128").parse().unwrap();
+
+    assert_eq!(spec.get_byte_ordering(), ByteOrdering::LittleEndian);
+    assert_eq!(spec.get_mangling(), Some(Mangling::ELF));
+    assert_eq!(spec.get_stack_alignment(), Some(128));
+    assert_eq!(spec.get_native_integer_widths(), &[8, 16, 32, 64]);
+    assert!(spec.is_native_integer_width(32));
+    assert!(!spec.is_native_integer_width(128));
+    assert_eq!(spec.get_pointer_spec(0), PointerSpec { address_space: 0, bit_width: 64, abi_alignment: 64, preferred_alignment: 64, index_bit_width: 64 });
+    assert_eq!(spec.get_pointer_spec(270).bit_width, 32);
+    assert_eq!(spec.get_pointer_spec(1).bit_width, 64);
+    assert_eq!(spec.get_pointer_specs().len(), 4);
+    assert_eq!(spec.get_integer_alignment(64), AlignSpec { bit_width: 64, abi_alignment: 64, preferred_alignment: 64 });
+    assert_eq!(spec.get_integer_alignment(24).abi_alignment, 32);
+    assert_eq!(spec.get_integer_alignment(128).abi_alignment, 64);
+    assert_eq!(spec.get_vector_alignment(128).abi_alignment, 128);
+    assert_eq!(spec.get_vector_alignment(96).abi_alignment, 128);
+    assert!(spec.get_float_specs().contains(&AlignSpec { bit_width: 80, abi_alignment: 128, preferred_alignment: 128 }));
+    assert_eq!(spec.get_aggregate_alignment(), (0, 64));
+    assert!(spec.get_function_pointer_alignment().is_none());
+    assert!(spec.get_non_integral_address_spaces().is_empty());
+
+    let spec = DataLayout::create("E-m:o-p:32:32:32:16-i64:64-v128:64:128-a:0:32-Fn32-ni:1:2-P1-A5-G1-n32").parse().unwrap();
+
+    assert_eq!(spec.get_byte_ordering(), ByteOrdering::BigEndian);
+    assert_eq!(spec.get_mangling(), Some(Mangling::MachO));
+    assert_eq!(spec.get_mangling().unwrap().get_global_prefix(), Some('_'));
+    assert_eq!(spec.get_pointer_spec(0).index_bit_width, 16);
+    assert_eq!(spec.get_vector_alignment(128), AlignSpec { bit_width: 128, abi_alignment: 64, preferred_alignment: 128 });
+    assert_eq!(spec.get_aggregate_alignment(), (0, 32));
+    assert_eq!(spec.get_function_pointer_alignment(), Some((FunctionPtrAlignType::MultipleOfFunctionAlign, 32)));
+    assert_eq!(spec.get_non_integral_address_spaces(), &[1, 2]);
+    assert_eq!(spec.get_program_address_space(), 1);
+    assert_eq!(spec.get_alloca_address_space(), 5);
+    assert_eq!(spec.get_globals_address_space(), 1);
+    assert!(spec.get_stack_alignment().is_none());
+
+    // An empty data layout is LLVM's default one
+    let spec = DataLayout::create("").parse().unwrap();
+
+    assert_eq!(spec.get_integer_alignment(64).abi_alignment, 32);
+    assert!(spec.get_mangling().is_none());
+
+    let target_data = TargetData::create("e-m:w-p:32:32-i64:64-f80:32-n8:16:32-a:0:32-S32");
+
+    assert_eq!(target_data.get_data_layout().parse().unwrap().get_mangling(), Some(Mangling::WindowsCOFF));
+
+    assert_eq!(DataLayout::create("e-m:q").parse(), Err(DataLayoutParseError::InvalidComponent("m:q".into())));
+    assert_eq!(DataLayout::create("e-p:64").parse(), Err(DataLayoutParseError::InvalidComponent("p:64".into())));
+    assert_eq!(DataLayout::create("e-i64:64:32").parse(), Err(DataLayoutParseError::InvalidComponent("i64:64:32".into())));
+
+    // Specifications LLVM would abort on are rejected the same way
+    for component in &["i64:48", "i64:0", "i64:64:48", "i8:16", "f32:0", "v128:12", "a64:64", "p:64:48", "p:64:64:96", "p:60:64", "p:32:32:32:64", "p:32:32:32:0", "p16777216:64:64", "S48", "Fi24", "ni:0", "n0"] {
+        let data_layout = format!("e-{}", component);
+
+        assert_eq!(DataLayout::create(&data_layout).parse(), Err(DataLayoutParseError::InvalidComponent(component.to_string())));
+    }
+
+    assert!(DataLayout::create("e-i8:8:16-a:0:64-S0").parse().is_ok());
+
+    let context = Context::create();
+    let struct_type = context.struct_type(&[], false);
+
+    assert_eq!(DataLayout::create("e-i64:48").get_struct_layout(&struct_type), Err(DataLayoutParseError::InvalidComponent("i64:48".into())));
+}
+
 #[test]
 fn test_ptr_sized_int() {
     Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
@@ -383,3 +498,226 @@ fn test_write_target_machine_to_memory_buffer() {
     assert!(string.contains("my_module"));
     assert!(string.contains(".section"));
 }
+
+#[llvm_versions(3.9..=latest)]
+#[test]
+fn test_target_machine_options() {
+    use inkwell::attributes::AttributeLoc;
+    use inkwell::targets::{FloatABI, FramePointer, TargetMachineOptions};
+
+    Target::initialize_x86(&InitializationConfig::default());
+
+    let target = Target::from_name("x86-64").unwrap();
+    let options = TargetMachineOptions::default()
+        .set_cpu("x86-64")
+        .add_feature("+avx2")
+        .set_reloc_mode(RelocMode::PIC)
+        .set_function_sections(true)
+        .set_data_sections(true)
+        .set_frame_pointer(FramePointer::All)
+        .set_float_abi(FloatABI::Hard);
+    let target_machine = target.create_target_machine_from_options(&TargetTriple::create("x86_64-pc-linux-gnu"), &options).unwrap();
+
+    assert_eq!(target_machine.get_cpu().to_str(), Ok("x86-64"));
+    assert_eq!(target_machine.get_feature_string().to_str(), Ok("+avx2"));
+
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = context.void_type().fn_type(&[], false);
+    let function = module.add_function("my_fn", fn_type, None);
+    let declaration = module.add_function("my_decl", fn_type, None);
+    let zeroed = module.add_global(i32_type, None, "zeroed");
+    let constant = module.add_global(i32_type, None, "constant");
+    let pointer = module.add_global(i32_type.ptr_type(AddressSpace::Generic), None, "pointer");
+    let explicit = module.add_global(i32_type, None, "explicit");
+    let greeting = module.add_global(context.i8_type().array_type(6), None, "greeting");
+
+    zeroed.set_initializer(&i32_type.const_zero());
+    constant.set_initializer(&i32_type.const_int(42, false));
+    constant.set_constant(true);
+    pointer.set_initializer(&constant.as_pointer_value());
+    pointer.set_constant(true);
+    explicit.set_initializer(&i32_type.const_int(1, false));
+    explicit.set_section("my_section");
+    greeting.set_initializer(&context.const_string(b"hello", true));
+    greeting.set_constant(true);
+    greeting.set_unnamed_addr(true);
+
+    builder.position_at_end(context.append_basic_block(function, "entry"));
+    builder.build_return(None);
+
+    let buffer = target_machine.write_to_memory_buffer(&module, FileType::Assembly).unwrap();
+    let string = from_utf8(buffer.as_slice()).unwrap();
+
+    // Options are applied to a clone, so emitting leaves the module as it was
+    assert!(string.contains(".text.my_fn"));
+    assert_eq!(function.as_global_value().get_section().to_str(), Ok(""));
+    assert!(function.get_string_attribute(AttributeLoc::Function, "use-soft-float").is_none());
+
+    target_machine.apply_options_to_module(&module);
+
+    assert_eq!(function.as_global_value().get_section().to_str(), Ok(".text.my_fn"));
+    assert_eq!(declaration.as_global_value().get_section().to_str(), Ok(""));
+    assert_eq!(zeroed.get_section().to_str(), Ok(".bss.zeroed"));
+    assert_eq!(constant.get_section().to_str(), Ok(".rodata.constant"));
+    assert_eq!(pointer.get_section().to_str(), Ok(".data.rel.ro.pointer"));
+    assert_eq!(explicit.get_section().to_str(), Ok("my_section"));
+    assert_eq!(greeting.get_section().to_str(), Ok(""));
+
+    #[cfg(any(feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0", feature = "llvm7-0", feature = "llvm8-0", feature = "llvm9-0"))]
+    let frame_pointer = function.get_string_attribute(AttributeLoc::Function, "no-frame-pointer-elim").unwrap();
+    #[cfg(not(any(feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0", feature = "llvm7-0", feature = "llvm8-0", feature = "llvm9-0")))]
+    let frame_pointer = function.get_string_attribute(AttributeLoc::Function, "frame-pointer").unwrap();
+    let soft_float = function.get_string_attribute(AttributeLoc::Function, "use-soft-float").unwrap();
+
+    #[cfg(any(feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0", feature = "llvm7-0", feature = "llvm8-0", feature = "llvm9-0"))]
+    assert_eq!(frame_pointer.get_string_value().to_str(), Ok("true"));
+    #[cfg(not(any(feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0", feature = "llvm7-0", feature = "llvm8-0", feature = "llvm9-0")))]
+    assert_eq!(frame_pointer.get_string_value().to_str(), Ok("all"));
+    assert_eq!(soft_float.get_string_value().to_str(), Ok("false"));
+    assert!(declaration.get_string_attribute(AttributeLoc::Function, "use-soft-float").is_none());
+
+    // LLVM options are process wide, so this sets the default to avoid affecting other tests
+    inkwell::support::parse_command_line_options(&["-x86-asm-syntax=att"]);
+}
+
+#[cfg(feature = "target-arm")]
+#[test]
+fn test_target_machine_options_float_abi() {
+    use inkwell::targets::{FloatABI, TargetMachineOptions};
+
+    Target::initialize_arm(&InitializationConfig::default());
+
+    let triple = TargetTriple::create("armv7-unknown-linux-gnueabi");
+    let target = Target::from_triple(&triple).unwrap();
+    let hard = TargetMachineOptions::default().set_float_abi(FloatABI::Hard);
+    let soft = TargetMachineOptions::default().set_float_abi(FloatABI::Soft);
+    let target_machine = target.create_target_machine_from_options(&triple, &hard).unwrap();
+
+    assert_eq!(target_machine.get_triple(), TargetTriple::create("armv7-unknown-linux-gnueabihf"));
+
+    let target_machine = target.create_target_machine_from_options(&triple, &soft).unwrap();
+
+    assert_eq!(target_machine.get_triple(), triple);
+
+    let target_machine = target.create_target_machine_from_options(&TargetTriple::create("thumbv7em-none-eabihf"), &soft).unwrap();
+
+    assert_eq!(target_machine.get_triple(), TargetTriple::create("thumbv7em-none-eabi"));
+}
+
+#[test]
+fn test_triple_parsing() {
+    use inkwell::targets::{Arch, Environment, ObjectFormat, OS, SubArch, Triple, TripleParseError, Vendor};
+
+    let triple = TargetTriple::create("x86_64-pc-linux-gnu").parse().unwrap();
+
+    assert_eq!(triple.get_arch(), Arch::X86_64);
+    assert_eq!(triple.get_sub_arch(), None);
+    assert_eq!(triple.get_vendor(), Vendor::PC);
+    assert_eq!(triple.get_os(), OS::Linux);
+    assert_eq!(triple.get_environment(), Environment::GNU);
+    assert_eq!(triple.get_object_format(), ObjectFormat::ELF);
+    assert_eq!(triple.get_pointer_width(), 64);
+    assert_eq!(triple.get_byte_ordering(), ByteOrdering::LittleEndian);
+    assert_eq!(triple.to_target_triple(), TargetTriple::create("x86_64-pc-linux-gnu"));
+
+    // The vendor may be left out
+    let triple: Triple = "aarch64-linux-android21".parse().unwrap();
+
+    assert_eq!(triple.get_arch(), Arch::AArch64);
+    assert_eq!(triple.get_vendor(), Vendor::Unknown);
+    assert_eq!(triple.get_os(), OS::Linux);
+    assert_eq!(triple.get_environment(), Environment::Android);
+
+    let triple: Triple = "thumbv7em-none-eabihf".parse().unwrap();
+
+    assert_eq!(triple.get_arch(), Arch::Thumb);
+    assert_eq!(triple.get_sub_arch(), Some(SubArch::ArmV7EM));
+    assert_eq!(triple.get_os(), OS::Unknown);
+    assert_eq!(triple.get_environment(), Environment::EABIHF);
+    assert_eq!(triple.get_pointer_width(), 32);
+
+    let triple: Triple = "arm64-apple-macosx10.15.0".parse().unwrap();
+
+    assert_eq!(triple.get_arch(), Arch::AArch64);
+    assert_eq!(triple.get_vendor(), Vendor::Apple);
+    assert_eq!(triple.get_os(), OS::MacOSX);
+    assert_eq!(triple.get_object_format(), ObjectFormat::MachO);
+
+    let triple: Triple = "x86_64-pc-windows-msvc".parse().unwrap();
+
+    assert_eq!(triple.get_os(), OS::Windows);
+    assert_eq!(triple.get_environment(), Environment::MSVC);
+    assert_eq!(triple.get_object_format(), ObjectFormat::COFF);
+
+    // MinGW and Cygwin imply both the OS and environment
+    let triple: Triple = "i686-w64-mingw32".parse().unwrap();
+
+    assert_eq!(triple.get_arch(), Arch::X86);
+    assert_eq!(triple.get_vendor(), Vendor::Unknown);
+    assert_eq!(triple.get_os(), OS::Windows);
+    assert_eq!(triple.get_environment(), Environment::GNU);
+    assert_eq!(triple.get_object_format(), ObjectFormat::COFF);
+
+    let triple: Triple = "x86_64-pc-cygwin".parse().unwrap();
+
+    assert_eq!(triple.get_vendor(), Vendor::PC);
+    assert_eq!(triple.get_os(), OS::Windows);
+    assert_eq!(triple.get_environment(), Environment::Cygnus);
+    assert_eq!(triple.get_object_format(), ObjectFormat::COFF);
+
+    // An explicit object format overrides the OS default
+    let triple: Triple = "x86_64-pc-windows-msvc-elf".parse().unwrap();
+
+    assert_eq!(triple.get_object_format(), ObjectFormat::ELF);
+
+    let triple: Triple = "wasm32-wasi".parse().unwrap();
+
+    assert_eq!(triple.get_os(), OS::WASI);
+    assert_eq!(triple.get_object_format(), ObjectFormat::Wasm);
+
+    let triple: Triple = "x86_64-unknown-linux-gnux32".parse().unwrap();
+
+    assert_eq!(triple.get_pointer_width(), 32);
+
+    let triple: Triple = "powerpc64-unknown-linux-gnu".parse().unwrap();
+
+    assert_eq!(triple.get_byte_ordering(), ByteOrdering::BigEndian);
+
+    // Display reproduces the original string
+    for string in &["x86_64-pc-linux-gnu", "amd64-linux", "armv7-unknown-linux-gnueabihf", "i686-pc-windows-msvc"] {
+        let triple: Triple = string.parse().unwrap();
+
+        assert_eq!(&triple.to_string(), string);
+        assert_eq!(triple.to_string().parse::<Triple>(), Ok(triple));
+    }
+
+    // Architectures inkwell doesn't know still parse, as in LLVM
+    for string in &["r600-unknown-unknown", "xcore-unknown-unknown", "spir64-unknown-unknown", "armfoo-linux"] {
+        let triple: Triple = string.parse().unwrap();
+
+        assert_eq!(triple.get_arch(), Arch::Unknown);
+        assert_eq!(triple.get_sub_arch(), None);
+        assert_eq!(triple.get_pointer_width(), 0);
+    }
+
+    let triple: Triple = "lanai-unknown-linux-gnu".parse().unwrap();
+
+    assert_eq!(triple.get_arch(), Arch::Unknown);
+    assert_eq!(triple.get_os(), OS::Linux);
+    assert_eq!(triple.get_environment(), Environment::GNU);
+
+    assert_eq!("".parse::<Triple>(), Err(TripleParseError::Empty));
+}
+
+#[test]
+fn test_validate_subtarget() {
+    use inkwell::targets::SubtargetError;
+
+    assert_eq!(Target::validate_subtarget("+avx2,-sse4.1"), Ok(()));
+    assert_eq!(Target::validate_subtarget(""), Ok(()));
+    assert_eq!(Target::validate_subtarget("avx2"), Err(SubtargetError::InvalidFeatureSyntax("avx2".to_string())));
+    assert_eq!(Target::validate_subtarget("+avx2,+"), Err(SubtargetError::InvalidFeatureSyntax("+".to_string())));
+}
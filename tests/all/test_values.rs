@@ -3,7 +3,7 @@ use inkwell::attributes::AttributeLoc;
 use inkwell::context::Context;
 use inkwell::module::Linkage::*;
 use inkwell::types::{AnyType, StringRadix, VectorType};
-use inkwell::values::{AnyValue, BasicValue, InstructionOpcode::*, FIRST_CUSTOM_METADATA_KIND_ID};
+use inkwell::values::{AnyValue, BasicValue, GEPError, InstructionOpcode::*, FIRST_CUSTOM_METADATA_KIND_ID};
 #[llvm_versions(7.0..=latest)]
 use inkwell::comdat::ComdatSelectionKind;
 
@@ -1203,3 +1203,35 @@ fn test_constant_expression() {
     assert!(expr.is_const());
     assert!(!expr.is_constant_int());
 }
+
+#[test]
+fn test_const_checked_gep() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let i32_type = context.i32_type();
+    let array_type = i32_type.array_type(3);
+    let struct_type = context.struct_type(&[i32_type.into(), array_type.into()], false);
+    let global = module.add_global(struct_type, None, "global");
+    let ptr = global.as_pointer_value();
+
+    let zero = i32_type.const_zero();
+    let one = i32_type.const_int(1, false);
+    let two = i32_type.const_int(2, false);
+
+    let elem_ptr = ptr.const_checked_gep(&[zero, one, two]).unwrap();
+
+    assert!(elem_ptr.is_const());
+    assert_eq!(elem_ptr.get_type(), i32_type.ptr_type(AddressSpace::Generic));
+    assert!(ptr.const_checked_in_bounds_gep(&[zero, zero]).is_ok());
+    assert!(ptr.const_checked_gep(&[zero, two]).is_err());
+    assert!(ptr.const_checked_gep(&[zero, zero, zero]).is_err());
+
+    // Non constant indices are rejected wherever they appear, not only for struct fields
+    let fn_type = context.void_type().fn_type(&[i32_type.into()], false);
+    let function = module.add_function("dynamic_index", fn_type, None);
+    let dynamic_index = function.get_first_param().unwrap().into_int_value();
+
+    assert_eq!(ptr.const_checked_gep(&[dynamic_index]), Err(GEPError::NonConstantIndex { position: 0 }));
+    assert_eq!(ptr.const_checked_gep(&[zero, one, dynamic_index]), Err(GEPError::NonConstantIndex { position: 2 }));
+    assert_eq!(ptr.const_checked_in_bounds_gep(&[zero, one, dynamic_index]), Err(GEPError::NonConstantIndex { position: 2 }));
+}